@@ -0,0 +1,62 @@
+CREATE TABLE designers (
+  id INTEGER PRIMARY KEY,
+  name TEXT NOT NULL
+);
+
+INSERT INTO designers (name) VALUES
+  ('Proenza Schouler'),
+  ('Ralph Lauren');
+
+-- `favorite_actor_or_designer_id` has no declared type, so its ids stay
+-- stored as `TEXT`
+CREATE TABLE actors (
+  id INTEGER PRIMARY KEY,
+  name TEXT NOT NULL,
+  expression TEXT NOT NULL,
+  favorite_actor_or_designer_type TEXT NOT NULL,
+  favorite_actor_or_designer_id NOT NULL,
+  birth_year INTEGER NOT NULL,
+  height_in_meters REAL NOT NULL,
+  is_main_cast INTEGER NOT NULL,
+  external_id TEXT NOT NULL,
+  debuted_at TEXT NOT NULL,
+  metadata TEXT NOT NULL,
+  salary_per_episode TEXT NOT NULL,
+  cast_status TEXT NOT NULL
+);
+
+INSERT INTO actors (name, expression, favorite_actor_or_designer_type, favorite_actor_or_designer_id, birth_year, height_in_meters, is_main_cast, external_id, debuted_at, metadata, salary_per_episode, cast_status) VALUES
+  ('Katie Cassidy', 'no Serena you can''t have the key', 'designers', (SELECT CAST(id AS TEXT) FROM designers WHERE name = 'Proenza Schouler'), 1986, 1.68, 0, 'c1a7f8a2-6b0e-4c38-9d0b-3f1b2a4d5e61', '2010-09-13T21:00:00Z', '{"character": "Juliet Sharp", "seasons": [4]}', '35000.50', 'RECURRING');
+INSERT INTO actors (name, expression, favorite_actor_or_designer_type, favorite_actor_or_designer_id, birth_year, height_in_meters, is_main_cast, external_id, debuted_at, metadata, salary_per_episode, cast_status) VALUES
+  ('Jessica Szohr', 'Dan where did you go I don''t like you', 'actors', (SELECT CAST(id AS TEXT) FROM actors WHERE name = 'Katie Cassidy'), 1985, 1.65, 1, '5f0c2d4e-8a1b-4e7f-b3c9-0d6e2a9f1b84', '2007-09-19T21:00:00Z', '{"character": "Vanessa Abrams", "seasons": [1, 2, 3, 4]}', '60000.00', 'MAIN');
+
+CREATE TABLE blasts (
+  id INTEGER PRIMARY KEY,
+  content TEXT NOT NULL,
+  sent_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  tone TEXT NOT NULL DEFAULT 'GOSSIP'
+);
+
+CREATE TABLE movies (
+  id INTEGER PRIMARY KEY,
+  title TEXT NOT NULL,
+  release_year INTEGER NOT NULL,
+  lead_actor_id INTEGER NOT NULL REFERENCES actors (id)
+);
+
+INSERT INTO movies (title, release_year, lead_actor_id) VALUES
+  ('Black Christmas', 2006, (SELECT id FROM actors WHERE name = 'Katie Cassidy')),
+  ('Piranha 3D', 2010, (SELECT id FROM actors WHERE name = 'Jessica Szohr')),
+  ('Monte Carlo', 2011, (SELECT id FROM actors WHERE name = 'Katie Cassidy'));
+
+CREATE TABLE actors_movies (
+  id INTEGER PRIMARY KEY,
+  actor_id INTEGER NOT NULL REFERENCES actors (id),
+  movie_id INTEGER NOT NULL REFERENCES movies (id)
+);
+
+INSERT INTO actors_movies (actor_id, movie_id) VALUES
+  ((SELECT id FROM actors WHERE name = 'Katie Cassidy'), (SELECT id FROM movies WHERE title = 'Black Christmas')),
+  ((SELECT id FROM actors WHERE name = 'Jessica Szohr'), (SELECT id FROM movies WHERE title = 'Piranha 3D')),
+  ((SELECT id FROM actors WHERE name = 'Katie Cassidy'), (SELECT id FROM movies WHERE title = 'Monte Carlo')),
+  ((SELECT id FROM actors WHERE name = 'Jessica Szohr'), (SELECT id FROM movies WHERE title = 'Monte Carlo'));
@@ -0,0 +1,524 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use futures::{future::LocalBoxFuture, FutureExt};
+use squalid::_d;
+
+use crate::{
+    schema::resolve_list_query, ArgumentInternalDependencyResolver, BooleanCarver, Carver,
+    CarverOrPopulator, ColumnGetterListValue, CustomInternalDependencyResolver, DataSource,
+    DependencyType, DependencyValue, ExternalDependency, ExternalDependencyValues, FieldError,
+    FieldResolver, FieldResult, Id, IndexMap, InternalDependency, InternalDependencyResolver,
+    InternalDependencyValues, ListOrder, ObjectTypeBuilder, OrderDirection, Param, Populator,
+    PopulatorList, ResponseValue, StringCarver, Type, TypeField, TypeFieldBuilder, TypeFull,
+    ValuePopulator,
+};
+
+/// Generates a Relay connection (the `{node}Connection` and `{node}Edge`
+/// object types plus a field with `first`/`after`/`last`/`before` params)
+/// over the rows of a table, paginated by id with opaque cursors. The
+/// schema also needs `page_info_type()`
+pub struct Connection {
+    pub node_type_name: String,
+    pub table_name: String,
+    /// Scopes the rows to the ones whose column is the parent's `"id"`
+    /// external dependency
+    pub foreign_key_column_name: Option<String>,
+    pub conditions: Vec<(String, DependencyValue)>,
+    pub argument_conditions: Vec<ConnectionArgumentCondition>,
+}
+
+/// A condition on `column_name` whose value comes from an extra param of the
+/// connection field. Skipped if the argument is omitted (or `null`)
+pub struct ConnectionArgumentCondition {
+    pub column_name: String,
+    pub param_name: String,
+    pub param_type: TypeFull,
+    pub dependency_type: DependencyType,
+}
+
+impl Connection {
+    pub fn new(node_type_name: String, table_name: String) -> Self {
+        Self {
+            node_type_name,
+            table_name,
+            foreign_key_column_name: _d(),
+            conditions: _d(),
+            argument_conditions: _d(),
+        }
+    }
+
+    pub fn foreign_key(self, column_name: String) -> Self {
+        let mut new = self;
+        new.foreign_key_column_name = Some(column_name);
+        new
+    }
+
+    pub fn condition(self, column_name: String, value: DependencyValue) -> Self {
+        let mut new = self;
+        new.conditions.push((column_name, value));
+        new
+    }
+
+    pub fn argument_condition(
+        self,
+        column_name: String,
+        param_name: String,
+        param_type: TypeFull,
+        dependency_type: DependencyType,
+    ) -> Self {
+        let mut new = self;
+        new.argument_conditions.push(ConnectionArgumentCondition {
+            column_name,
+            param_name,
+            param_type,
+            dependency_type,
+        });
+        new
+    }
+
+    pub fn connection_type_name(&self) -> String {
+        format!("{}Connection", self.node_type_name)
+    }
+
+    pub fn edge_type_name(&self) -> String {
+        format!("{}Edge", self.node_type_name)
+    }
+
+    /// The `{node}Connection` and `{node}Edge` object types
+    pub fn types(&self) -> Vec<Type> {
+        vec![self.connection_type(), self.edge_type()]
+    }
+
+    fn connection_type(&self) -> Type {
+        Type::Object(
+            ObjectTypeBuilder::default()
+                .name(self.connection_type_name())
+                .fields([
+                    TypeFieldBuilder::default()
+                        .name("edges")
+                        .type_(non_null(TypeFull::List(Box::new(non_null(
+                            TypeFull::Type(self.edge_type_name()),
+                        )))))
+                        .resolver(FieldResolver::new(
+                            vec![ExternalDependency::new(
+                                "ids".to_owned(),
+                                DependencyType::ListOfIds,
+                            )],
+                            vec![],
+                            CarverOrPopulator::PopulatorList(Box::new(EdgesPopulator {})),
+                        ))
+                        .build()
+                        .unwrap(),
+                    TypeFieldBuilder::default()
+                        .name("pageInfo")
+                        .type_(non_null(TypeFull::Type("PageInfo".to_owned())))
+                        .resolver(FieldResolver::new(
+                            vec![
+                                ExternalDependency::new(
+                                    "ids".to_owned(),
+                                    DependencyType::ListOfIds,
+                                ),
+                                ExternalDependency::new(
+                                    "has_next_page".to_owned(),
+                                    DependencyType::Boolean,
+                                ),
+                                ExternalDependency::new(
+                                    "has_previous_page".to_owned(),
+                                    DependencyType::Boolean,
+                                ),
+                            ],
+                            vec![],
+                            CarverOrPopulator::Populator(Box::new(PageInfoPopulator {})),
+                        ))
+                        .build()
+                        .unwrap(),
+                ])
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn edge_type(&self) -> Type {
+        Type::Object(
+            ObjectTypeBuilder::default()
+                .name(self.edge_type_name())
+                .fields([
+                    TypeFieldBuilder::default()
+                        .name("node")
+                        .type_(non_null(TypeFull::Type(self.node_type_name.clone())))
+                        .resolver(FieldResolver::new(
+                            vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                            vec![],
+                            CarverOrPopulator::Populator(Box::new(ValuePopulator::new(
+                                "id".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap(),
+                    TypeFieldBuilder::default()
+                        .name("cursor")
+                        .type_(non_null(TypeFull::Type("String".to_owned())))
+                        .resolver(FieldResolver::new(
+                            vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                            vec![],
+                            CarverOrPopulator::Carver(Box::new(CursorCarver {})),
+                        ))
+                        .build()
+                        .unwrap(),
+                ])
+                .build()
+                .unwrap(),
+        )
+    }
+
+    /// A field resolving to the connection, eg on the query type (or, with
+    /// `foreign_key()`, on the parent type)
+    pub fn field(&self, name: impl Into<String>) -> TypeField {
+        let mut internal_dependencies = self
+            .argument_conditions
+            .iter()
+            .map(|argument_condition| {
+                argument_internal_dependency(
+                    &argument_condition.param_name,
+                    argument_condition.dependency_type,
+                )
+            })
+            .collect::<Vec<_>>();
+        internal_dependencies.extend([
+            argument_internal_dependency("first", DependencyType::Int),
+            argument_internal_dependency("after", DependencyType::String),
+            argument_internal_dependency("last", DependencyType::Int),
+            argument_internal_dependency("before", DependencyType::String),
+            InternalDependency::new(
+                "connection".to_owned(),
+                DependencyType::Object,
+                InternalDependencyResolver::Custom(Box::new(ConnectionResolver {
+                    table_name: self.table_name.clone(),
+                    foreign_key_column_name: self.foreign_key_column_name.clone(),
+                    conditions: self
+                        .conditions
+                        .iter()
+                        .map(|(column_name, value)| {
+                            (
+                                column_name.clone(),
+                                ColumnGetterListValue::Literal(value.clone()),
+                            )
+                        })
+                        .chain(self.argument_conditions.iter().map(|argument_condition| {
+                            (
+                                argument_condition.column_name.clone(),
+                                ColumnGetterListValue::InternalDependency(
+                                    argument_condition.param_name.clone(),
+                                ),
+                            )
+                        }))
+                        .collect(),
+                })),
+            ),
+        ]);
+        TypeFieldBuilder::default()
+            .name(name)
+            .type_(non_null(TypeFull::Type(self.connection_type_name())))
+            .resolver(FieldResolver::new(
+                self.foreign_key_column_name
+                    .iter()
+                    .map(|_| ExternalDependency::new("id".to_owned(), DependencyType::Id))
+                    .collect(),
+                internal_dependencies,
+                CarverOrPopulator::Populator(Box::new(ConnectionPopulator {})),
+            ))
+            .params(
+                self.argument_conditions
+                    .iter()
+                    .map(|argument_condition| {
+                        Param::new(
+                            argument_condition.param_name.clone(),
+                            argument_condition.param_type.clone(),
+                        )
+                    })
+                    .chain([
+                        Param::new("first".to_owned(), TypeFull::Type("Int".to_owned())),
+                        Param::new("after".to_owned(), TypeFull::Type("String".to_owned())),
+                        Param::new("last".to_owned(), TypeFull::Type("Int".to_owned())),
+                        Param::new("before".to_owned(), TypeFull::Type("String".to_owned())),
+                    ]),
+            )
+            .build()
+            .unwrap()
+    }
+}
+
+/// The `PageInfo` object type shared by all connections
+pub fn page_info_type() -> Type {
+    Type::Object(
+        ObjectTypeBuilder::default()
+            .name("PageInfo")
+            .fields([
+                page_info_field("hasNextPage", "has_next_page", DependencyType::Boolean),
+                page_info_field(
+                    "hasPreviousPage",
+                    "has_previous_page",
+                    DependencyType::Boolean,
+                ),
+                page_info_field("startCursor", "start_cursor", DependencyType::String),
+                page_info_field("endCursor", "end_cursor", DependencyType::String),
+            ])
+            .build()
+            .unwrap(),
+    )
+}
+
+fn page_info_field(name: &str, dependency_name: &str, type_: DependencyType) -> TypeField {
+    let (field_type, carver): (_, Box<dyn Carver>) = match type_ {
+        DependencyType::Boolean => (
+            non_null(TypeFull::Type("Boolean".to_owned())),
+            Box::new(BooleanCarver::new(dependency_name.to_owned())),
+        ),
+        // cursors are `null` for an empty page
+        _ => (
+            TypeFull::Type("String".to_owned()),
+            Box::new(StringCarver::new(dependency_name.to_owned())),
+        ),
+    };
+    TypeFieldBuilder::default()
+        .name(name)
+        .type_(field_type)
+        .resolver(FieldResolver::new(
+            vec![ExternalDependency::new(dependency_name.to_owned(), type_)],
+            vec![],
+            CarverOrPopulator::Carver(carver),
+        ))
+        .build()
+        .unwrap()
+}
+
+fn non_null(type_: TypeFull) -> TypeFull {
+    TypeFull::NonNull(Box::new(type_))
+}
+
+fn argument_internal_dependency(name: &str, type_: DependencyType) -> InternalDependency {
+    InternalDependency::new(
+        name.to_owned(),
+        type_,
+        InternalDependencyResolver::Argument(ArgumentInternalDependencyResolver::new(
+            name.to_owned(),
+        )),
+    )
+}
+
+pub fn encode_cursor(id: Id) -> String {
+    BASE64.encode(format!("cursor:{id}"))
+}
+
+pub fn decode_cursor(cursor: &str) -> FieldResult<Id> {
+    BASE64
+        .decode(cursor)
+        .ok()
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+        .and_then(|decoded| decoded.strip_prefix("cursor:")?.parse().ok())
+        .ok_or_else(|| FieldError::new(format!("Invalid cursor `{cursor}`")))
+}
+
+/// Resolves to an object with the page's `"ids"` and its
+/// `"has_next_page"`/`"has_previous_page"`. Per the Relay spec, `first` gets
+/// applied before `last`, and `hasNextPage` (`hasPreviousPage`) is only
+/// determined when paginating forward (backward)
+struct ConnectionResolver {
+    table_name: String,
+    foreign_key_column_name: Option<String>,
+    conditions: Vec<(String, ColumnGetterListValue)>,
+}
+
+impl CustomInternalDependencyResolver for ConnectionResolver {
+    fn resolve<'a>(
+        &'a self,
+        external_dependencies: &'a ExternalDependencyValues,
+        internal_dependencies: &'a InternalDependencyValues,
+        data_source: &'a dyn DataSource,
+    ) -> LocalBoxFuture<'a, FieldResult<DependencyValue>> {
+        async move {
+            let get_count = |name: &str| match internal_dependencies.get(name) {
+                Some(DependencyValue::Int(count)) if *count < 0 => Err(FieldError::new(format!(
+                    "`{name}` must not be negative, got `{count}`"
+                ))),
+                Some(DependencyValue::Int(count)) => Ok(Some(i64::from(*count))),
+                _ => Ok(None),
+            };
+            let get_cursor = |name: &str| match internal_dependencies.get(name) {
+                Some(DependencyValue::String(cursor)) => decode_cursor(cursor).map(Some),
+                _ => Ok(None),
+            };
+            let first = get_count("first")?;
+            let last = get_count("last")?;
+
+            let mut list_query =
+                resolve_list_query(&self.conditions, None, None, internal_dependencies)?;
+            if let Some(foreign_key_column_name) = self.foreign_key_column_name.as_ref() {
+                list_query.conditions.push((
+                    foreign_key_column_name.clone(),
+                    external_dependencies
+                        .get("id")
+                        .cloned()
+                        .unwrap_or(DependencyValue::Null),
+                ));
+            }
+            list_query.after_id = get_cursor("after")?;
+            list_query.before_id = get_cursor("before")?;
+            // fetching one extra row tells whether there are more
+            let backward = first.is_none() && last.is_some();
+            list_query.order_by = Some(ListOrder::new(
+                "id".to_owned(),
+                if backward {
+                    OrderDirection::Desc
+                } else {
+                    OrderDirection::Asc
+                },
+            ));
+            list_query.limit = first.or(last).map(|count| count + 1);
+
+            let mut ids = data_source
+                .get_column_values(&self.table_name, "id", DependencyType::Id, &list_query)
+                .await?
+                .into_iter()
+                .filter_map(|id| match id {
+                    DependencyValue::Id(id) => Some(id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let mut has_next_page = false;
+            let mut has_previous_page = false;
+            if backward {
+                ids.reverse();
+            }
+            if let Some(first) = first {
+                has_next_page = truncate(&mut ids, first, false);
+            }
+            if let Some(last) = last {
+                has_previous_page = truncate(&mut ids, last, true);
+            }
+
+            Ok(DependencyValue::Object(IndexMap::from_iter([
+                (
+                    "ids".to_owned(),
+                    DependencyValue::List(ids.into_iter().map(DependencyValue::Id).collect()),
+                ),
+                (
+                    "has_next_page".to_owned(),
+                    DependencyValue::Boolean(has_next_page),
+                ),
+                (
+                    "has_previous_page".to_owned(),
+                    DependencyValue::Boolean(has_previous_page),
+                ),
+            ])))
+        }
+        .boxed_local()
+    }
+}
+
+/// Keeps `count` ids (from the end if `from_end`), returning whether any got
+/// dropped
+fn truncate(ids: &mut Vec<Id>, count: i64, from_end: bool) -> bool {
+    let count = usize::try_from(count).unwrap_or(usize::MAX);
+    if ids.len() <= count {
+        return false;
+    }
+    if from_end {
+        ids.drain(..ids.len() - count);
+    } else {
+        ids.truncate(count);
+    }
+    true
+}
+
+struct ConnectionPopulator {}
+
+impl Populator for ConnectionPopulator {
+    fn populate(
+        &self,
+        _external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ExternalDependencyValues> {
+        let mut ret = ExternalDependencyValues::default();
+        let connection = internal_dependencies
+            .get("connection")
+            .ok_or_else(|| FieldError::new("Missing dependency `connection`"))?;
+        for (name, value) in connection.as_object() {
+            ret.insert(name.clone(), value.clone()).unwrap();
+        }
+        Ok(ret)
+    }
+}
+
+struct EdgesPopulator {}
+
+impl PopulatorList for EdgesPopulator {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        _internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Vec<ExternalDependencyValues>> {
+        Ok(get_ids(external_dependencies)?
+            .iter()
+            .map(|id| {
+                let mut ret = ExternalDependencyValues::default();
+                ret.insert("id".to_owned(), id.clone()).unwrap();
+                ret
+            })
+            .collect())
+    }
+}
+
+struct PageInfoPopulator {}
+
+impl Populator for PageInfoPopulator {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        _internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ExternalDependencyValues> {
+        let ids = get_ids(external_dependencies)?;
+        let cursor = |id: Option<&DependencyValue>| {
+            id.map_or(DependencyValue::Null, |id| {
+                DependencyValue::String(encode_cursor(*id.as_id()))
+            })
+        };
+        let mut ret = ExternalDependencyValues::default();
+        for name in ["has_next_page", "has_previous_page"] {
+            ret.insert(
+                name.to_owned(),
+                external_dependencies
+                    .get(name)
+                    .cloned()
+                    .unwrap_or(DependencyValue::Boolean(false)),
+            )
+            .unwrap();
+        }
+        ret.insert("start_cursor".to_owned(), cursor(ids.first()))
+            .unwrap();
+        ret.insert("end_cursor".to_owned(), cursor(ids.last()))
+            .unwrap();
+        Ok(ret)
+    }
+}
+
+fn get_ids(external_dependencies: &ExternalDependencyValues) -> FieldResult<&Vec<DependencyValue>> {
+    match external_dependencies.get("ids") {
+        Some(DependencyValue::List(ids)) => Ok(ids),
+        _ => Err(FieldError::new("Missing dependency `ids`")),
+    }
+}
+
+struct CursorCarver {}
+
+impl Carver for CursorCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        _internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        match external_dependencies.get("id") {
+            Some(DependencyValue::Id(id)) => Ok(ResponseValue::String(encode_cursor(*id))),
+            _ => Err(FieldError::new("Missing dependency `id`")),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::{cmp::Ordering, collections::HashMap, sync::RwLock};
+
+use futures::{
+    future::{self, LocalBoxFuture},
+    FutureExt,
+};
+use itertools::Itertools;
+
+use crate::{
+    DataSource, DependencyType, DependencyValue, FieldError, FieldResult, Id, ListQuery,
+    OrderDirection,
+};
+
+/// A row's column values, including its `"id"`
+pub type InMemoryRow = HashMap<String, DependencyValue>;
+
+/// Keeps each table's rows in memory, eg for tests that shouldn't need a
+/// running database. Absent columns read as `NULL`
+pub struct InMemoryDataSource {
+    tables: RwLock<HashMap<String, Vec<InMemoryRow>>>,
+}
+
+impl InMemoryDataSource {
+    pub fn new(tables: impl IntoIterator<Item = (String, Vec<InMemoryRow>)>) -> Self {
+        Self {
+            tables: RwLock::new(tables.into_iter().collect()),
+        }
+    }
+}
+
+impl DataSource for InMemoryDataSource {
+    fn get_column_values_by_id<'a>(
+        &'a self,
+        table_name: &'a str,
+        columns: &'a [(String, DependencyType)],
+        row_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<DependencyValue>>>> {
+        let tables = self.tables.read().unwrap();
+        future::ready(get_table(&tables, table_name).map(|rows| {
+            rows.iter()
+                .filter_map(|row| {
+                    let id = row_id(row)?;
+                    row_ids.contains(&id).then(|| {
+                        (
+                            id,
+                            columns
+                                .iter()
+                                .map(|(column_name, type_)| get_row_value(row, column_name, *type_))
+                                .collect(),
+                        )
+                    })
+                })
+                .collect()
+        }))
+        .boxed_local()
+    }
+
+    fn get_column_values<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_name: &'a str,
+        type_: DependencyType,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<DependencyValue>>> {
+        let tables = self.tables.read().unwrap();
+        future::ready(get_table(&tables, table_name).map(|rows| {
+            query_rows(rows.iter(), list_query)
+                .into_iter()
+                .map(|row| get_row_value(row, column_name, type_))
+                .collect()
+        }))
+        .boxed_local()
+    }
+
+    fn get_ids_by_foreign_key<'a>(
+        &'a self,
+        table_name: &'a str,
+        foreign_key_column_name: &'a str,
+        parent_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<Id>>>> {
+        let tables = self.tables.read().unwrap();
+        future::ready(get_table(&tables, table_name).map(|rows| {
+            let mut ret: HashMap<Id, Vec<Id>> = HashMap::new();
+            for row in rows.iter().sorted_by_key(|row| row_id(row)) {
+                let (
+                    Some(id),
+                    Some(DependencyValue::Id(parent_id) | DependencyValue::Int(parent_id)),
+                ) = (row_id(row), row.get(foreign_key_column_name))
+                else {
+                    continue;
+                };
+                if parent_ids.contains(parent_id) {
+                    ret.entry(*parent_id).or_default().push(id);
+                }
+            }
+            ret
+        }))
+        .boxed_local()
+    }
+
+    fn get_ids_via_join_table<'a>(
+        &'a self,
+        join_table_name: &'a str,
+        parent_column_name: &'a str,
+        target_column_name: &'a str,
+        target_table_name: &'a str,
+        parent_id: Id,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<Id>>> {
+        let tables = self.tables.read().unwrap();
+        let ret = get_table(&tables, join_table_name).and_then(|join_rows| {
+            let target_ids = join_rows
+                .iter()
+                .filter(|join_row| {
+                    join_row
+                        .get(parent_column_name)
+                        .is_some_and(|value| values_equal(value, &DependencyValue::Id(parent_id)))
+                })
+                .filter_map(|join_row| match join_row.get(target_column_name)? {
+                    DependencyValue::Id(id) | DependencyValue::Int(id) => Some(*id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let target_rows = get_table(&tables, target_table_name)?;
+            Ok(query_rows(
+                target_rows
+                    .iter()
+                    .filter(|row| row_id(row).is_some_and(|id| target_ids.contains(&id))),
+                list_query,
+            )
+            .into_iter()
+            .filter_map(row_id)
+            .collect())
+        });
+        future::ready(ret).boxed_local()
+    }
+
+    fn insert_row<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_values: Vec<(String, DependencyValue)>,
+    ) -> LocalBoxFuture<'a, FieldResult<Id>> {
+        let mut tables = self.tables.write().unwrap();
+        let ret = match tables.get_mut(table_name) {
+            None => Err(non_existent_table_error(table_name)),
+            Some(rows) => {
+                let id = rows.iter().filter_map(row_id).max().unwrap_or_default() + 1;
+                let mut row: InMemoryRow = column_values.into_iter().collect();
+                row.insert("id".to_owned(), DependencyValue::Id(id));
+                rows.push(row);
+                Ok(id)
+            }
+        };
+        future::ready(ret).boxed_local()
+    }
+}
+
+fn get_table<'a>(
+    tables: &'a HashMap<String, Vec<InMemoryRow>>,
+    table_name: &str,
+) -> FieldResult<&'a Vec<InMemoryRow>> {
+    tables
+        .get(table_name)
+        .ok_or_else(|| non_existent_table_error(table_name))
+}
+
+fn non_existent_table_error(table_name: &str) -> FieldError {
+    FieldError::new(format!("Non-existent table `{table_name}`"))
+}
+
+fn row_id(row: &InMemoryRow) -> Option<Id> {
+    match row.get("id")? {
+        DependencyValue::Id(id) | DependencyValue::Int(id) => Some(*id),
+        _ => None,
+    }
+}
+
+fn get_row_value(row: &InMemoryRow, column_name: &str, type_: DependencyType) -> DependencyValue {
+    match (row.get(column_name), type_) {
+        (None, _) => DependencyValue::Null,
+        (Some(DependencyValue::Int(value)), DependencyType::Id) => DependencyValue::Id(*value),
+        (Some(DependencyValue::Id(value)), DependencyType::Int) => DependencyValue::Int(*value),
+        (Some(value), _) => value.clone(),
+    }
+}
+
+/// Applies `list_query`'s conditions, ordering and limit
+fn query_rows<'a>(
+    rows: impl Iterator<Item = &'a InMemoryRow>,
+    list_query: &ListQuery,
+) -> Vec<&'a InMemoryRow> {
+    let mut rows = rows
+        .filter(|row| {
+            list_query.conditions.iter().all(|(column_name, value)| {
+                row.get(column_name)
+                    .is_some_and(|row_value| values_equal(row_value, value))
+            })
+        })
+        .filter(|row| {
+            let id = row_id(row);
+            list_query
+                .after_id
+                .is_none_or(|after_id| id.is_some_and(|id| id > after_id))
+                && list_query
+                    .before_id
+                    .is_none_or(|before_id| id.is_some_and(|id| id < before_id))
+        })
+        .collect::<Vec<_>>();
+    if let Some(order_by) = list_query.order_by.as_ref() {
+        rows.sort_by(|a, b| {
+            let ordering =
+                compare_values(a.get(&order_by.column_name), b.get(&order_by.column_name));
+            match order_by.direction {
+                OrderDirection::Asc => ordering,
+                OrderDirection::Desc => ordering.reverse(),
+            }
+        });
+    }
+    rows.truncate(list_query.limit.map_or(usize::MAX, |limit| {
+        usize::try_from(limit).unwrap_or_default()
+    }));
+    rows
+}
+
+fn values_equal(a: &DependencyValue, b: &DependencyValue) -> bool {
+    match (a, b) {
+        (
+            DependencyValue::Id(a) | DependencyValue::Int(a),
+            DependencyValue::Id(b) | DependencyValue::Int(b),
+        ) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Like Postgres, sorts `NULL`s after everything else (in ascending order)
+fn compare_values(a: Option<&DependencyValue>, b: Option<&DependencyValue>) -> Ordering {
+    let a = a.filter(|a| !matches!(a, DependencyValue::Null));
+    let b = b.filter(|b| !matches!(b, DependencyValue::Null));
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => match (a, b) {
+            (
+                DependencyValue::Id(a) | DependencyValue::Int(a),
+                DependencyValue::Id(b) | DependencyValue::Int(b),
+            ) => a.cmp(b),
+            (DependencyValue::String(a), DependencyValue::String(b)) => a.cmp(b),
+            (DependencyValue::Float(a), DependencyValue::Float(b)) => a.total_cmp(b),
+            (DependencyValue::Boolean(a), DependencyValue::Boolean(b)) => a.cmp(b),
+            (DependencyValue::DateTime(a), DependencyValue::DateTime(b)) => a.cmp(b),
+            (DependencyValue::Uuid(a), DependencyValue::Uuid(b)) => a.cmp(b),
+            (DependencyValue::Decimal(a), DependencyValue::Decimal(b)) => a.cmp(b),
+            _ => Ordering::Equal,
+        },
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use futures::future::LocalBoxFuture;
+
+use crate::{
+    DependencyType, DependencyValue, FieldError, FieldResult, Id, ListOrder, OrderDirection,
+};
+
+mod in_memory;
+mod postgres;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use in_memory::{InMemoryDataSource, InMemoryRow};
+
+/// Table and column names get checked with this when the schema is built
+pub(crate) fn is_valid_sql_identifier(identifier: &str) -> bool {
+    let mut chars = identifier.chars();
+    chars
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+        // Postgres truncates longer identifiers
+        && identifier.len() <= 63
+}
+
+/// Lowercases the identifier the same as Postgres does for an unquoted one
+/// (SQLite's are case-insensitive either way), which is also the name that
+/// a selected column comes back under
+pub(crate) fn fold_identifier(identifier: &str) -> String {
+    identifier.to_lowercase()
+}
+
+/// Works for both Postgres and SQLite. Since a quoted identifier is
+/// case-sensitive, it gets folded first
+pub(crate) fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", fold_identifier(identifier).replace('"', "\"\""))
+}
+
+/// Eg for a `RowInserter` column that got mapped to a list argument
+pub(crate) fn unsupported_column_value_error(column_name: &str) -> FieldError {
+    FieldError::new(format!("Unsupported value for column `{column_name}`"))
+}
+
+/// Eg for a `ColumnGetterList` condition that got mapped to a list argument
+pub(crate) fn unsupported_condition_value_error(column_name: &str) -> FieldError {
+    FieldError::new(format!(
+        "Unsupported condition value for column `{column_name}`"
+    ))
+}
+
+pub(crate) fn column_decode_error(
+    column_name: &str,
+    source: Box<dyn std::error::Error + Send + Sync>,
+) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: format!("{column_name:?}"),
+        source,
+    }
+}
+
+/// Eg for a `ColumnGetter` whose dependency type is a list
+pub(crate) fn unsupported_column_type_error(column_name: &str) -> sqlx::Error {
+    column_decode_error(column_name, "unsupported dependency type".into())
+}
+
+/// The ` WHERE ...`/` ORDER BY ...`/` LIMIT ...` clauses for `list_query`,
+/// with the condition values, then the id bounds and then the limit as
+/// numbered parameters. A `base_condition` gets ANDed in first and gets to
+/// use parameter 1
+pub(crate) fn list_query_clauses(
+    list_query: &ListQuery,
+    base_condition: Option<String>,
+    placeholder: impl Fn(usize) -> String,
+) -> String {
+    let mut placeholder_index = usize::from(base_condition.is_some());
+    let mut next_placeholder = || {
+        placeholder_index += 1;
+        placeholder(placeholder_index)
+    };
+    let mut conditions = base_condition.into_iter().collect::<Vec<_>>();
+    for (column_name, _) in &list_query.conditions {
+        conditions.push(format!(
+            "{} = {}",
+            quote_identifier(column_name),
+            next_placeholder()
+        ));
+    }
+    if list_query.after_id.is_some() {
+        conditions.push(format!("id > {}", next_placeholder()));
+    }
+    if list_query.before_id.is_some() {
+        conditions.push(format!("id < {}", next_placeholder()));
+    }
+    let mut clauses = String::new();
+    if !conditions.is_empty() {
+        clauses.push_str(" WHERE ");
+        clauses.push_str(&conditions.join(" AND "));
+    }
+    if let Some(order_by) = list_query.order_by.as_ref() {
+        clauses.push_str(&format!(
+            " ORDER BY {} {}",
+            quote_identifier(&order_by.column_name),
+            match order_by.direction {
+                OrderDirection::Asc => "ASC",
+                OrderDirection::Desc => "DESC",
+            }
+        ));
+    }
+    if list_query.limit.is_some() {
+        clauses.push_str(&format!(" LIMIT {}", next_placeholder()));
+    }
+    clauses
+}
+
+/// A `ColumnGetterList`'s conditions, ordering and limit, with any values
+/// taken from other internal dependencies already resolved
+#[derive(Clone, Debug, Default)]
+pub struct ListQuery {
+    /// (column name, value) pairs that rows must be equal to
+    pub conditions: Vec<(String, DependencyValue)>,
+    pub order_by: Option<ListOrder>,
+    pub limit: Option<i64>,
+    /// Only rows with a greater id, eg for keyset pagination
+    pub after_id: Option<Id>,
+    /// Only rows with a lesser id
+    pub before_id: Option<Id>,
+}
+
+/// Where `ColumnGetter`s, `ColumnGetterList`s, `HasManyViaForeignKey`s,
+/// `ManyToManyViaJoinTable`s and `RowInserter`s get resolved from.
+/// Implemented for `Pool<Postgres>` (and for `Pool<Sqlite>` with the `sqlite`
+/// feature)
+pub trait DataSource {
+    /// Returns the requested columns' values (in the requested order) for
+    /// each of the given rows that exist
+    fn get_column_values_by_id<'a>(
+        &'a self,
+        table_name: &'a str,
+        columns: &'a [(String, DependencyType)],
+        row_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<DependencyValue>>>>;
+
+    /// Returns the column's value for every row of the table that matches
+    /// `list_query`'s conditions, in its order and up to its limit
+    fn get_column_values<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_name: &'a str,
+        type_: DependencyType,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<DependencyValue>>>;
+
+    /// Returns the ids (in id order) of the rows whose
+    /// `foreign_key_column_name` is one of `parent_ids`, keyed by that
+    /// parent id. Parents without any such rows may be absent
+    fn get_ids_by_foreign_key<'a>(
+        &'a self,
+        table_name: &'a str,
+        foreign_key_column_name: &'a str,
+        parent_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<Id>>>>;
+
+    /// Returns the ids of the rows of `target_table_name` that are joined via
+    /// `join_table_name` to the parent row and match `list_query`'s
+    /// conditions, in its order and up to its limit
+    fn get_ids_via_join_table<'a>(
+        &'a self,
+        join_table_name: &'a str,
+        parent_column_name: &'a str,
+        target_column_name: &'a str,
+        target_table_name: &'a str,
+        parent_id: Id,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<Id>>>;
+
+    /// Returns the inserted row's id. Columns that aren't provided get their
+    /// default values
+    fn insert_row<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_values: Vec<(String, DependencyValue)>,
+    ) -> LocalBoxFuture<'a, FieldResult<Id>>;
+}
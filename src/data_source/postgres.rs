@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use futures::{future::LocalBoxFuture, FutureExt};
+use itertools::Itertools;
+use sqlx::{
+    postgres::{PgArguments, PgRow},
+    query::Query,
+    Pool, Postgres, Row,
+};
+
+use crate::{
+    data_source::{
+        fold_identifier, list_query_clauses, quote_identifier, unsupported_column_type_error,
+        unsupported_column_value_error, unsupported_condition_value_error,
+    },
+    DataSource, DependencyType, DependencyValue, FieldResult, Id, ListQuery,
+};
+
+impl DataSource for Pool<Postgres> {
+    fn get_column_values_by_id<'a>(
+        &'a self,
+        table_name: &'a str,
+        columns: &'a [(String, DependencyType)],
+        row_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<DependencyValue>>>> {
+        async move {
+            let query = format!(
+                "SELECT id, {} FROM {} WHERE id = ANY($1)",
+                columns
+                    .iter()
+                    .map(|(column_name, _)| column_name)
+                    .unique()
+                    .map(|column_name| quote_identifier(column_name))
+                    .join(", "),
+                quote_identifier(table_name)
+            );
+            let rows = sqlx::query(&query).bind(row_ids).fetch_all(self).await?;
+            Ok(rows
+                .into_iter()
+                .map(|row| {
+                    let id: Id = row.try_get("id")?;
+                    let values = columns
+                        .iter()
+                        .map(|(column_name, type_)| get_row_value(&row, column_name, *type_))
+                        .collect::<Result<_, sqlx::Error>>()?;
+                    Ok((id, values))
+                })
+                .collect::<Result<_, sqlx::Error>>()?)
+        }
+        .boxed_local()
+    }
+
+    fn get_column_values<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_name: &'a str,
+        type_: DependencyType,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<DependencyValue>>> {
+        async move {
+            let query = format!(
+                "SELECT {} FROM {}{}",
+                quote_identifier(column_name),
+                quote_identifier(table_name),
+                list_query_clauses(list_query, None, |index| format!("${index}"))
+            );
+            let query = bind_list_query(sqlx::query(&query), list_query)?;
+            let rows = query.fetch_all(self).await?;
+            Ok(rows
+                .iter()
+                .map(|row| get_row_value(row, column_name, type_))
+                .collect::<Result<_, _>>()?)
+        }
+        .boxed_local()
+    }
+
+    fn get_ids_by_foreign_key<'a>(
+        &'a self,
+        table_name: &'a str,
+        foreign_key_column_name: &'a str,
+        parent_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<Id>>>> {
+        async move {
+            let query = format!(
+                "SELECT id, {0} FROM {1} WHERE {0} = ANY($1) ORDER BY id",
+                quote_identifier(foreign_key_column_name),
+                quote_identifier(table_name)
+            );
+            let rows = sqlx::query(&query).bind(parent_ids).fetch_all(self).await?;
+            let mut ret: HashMap<Id, Vec<Id>> = HashMap::new();
+            for row in rows {
+                ret.entry(row.try_get(&*fold_identifier(foreign_key_column_name))?)
+                    .or_default()
+                    .push(row.try_get("id")?);
+            }
+            Ok(ret)
+        }
+        .boxed_local()
+    }
+
+    fn get_ids_via_join_table<'a>(
+        &'a self,
+        join_table_name: &'a str,
+        parent_column_name: &'a str,
+        target_column_name: &'a str,
+        target_table_name: &'a str,
+        parent_id: Id,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<Id>>> {
+        async move {
+            let query = format!(
+                "SELECT id FROM {}{}",
+                quote_identifier(target_table_name),
+                list_query_clauses(
+                    list_query,
+                    Some(format!(
+                        "id IN (SELECT {} FROM {} WHERE {} = $1)",
+                        quote_identifier(target_column_name),
+                        quote_identifier(join_table_name),
+                        quote_identifier(parent_column_name)
+                    )),
+                    |index| format!("${index}")
+                )
+            );
+            let query = bind_list_query(sqlx::query(&query).bind(parent_id), list_query)?;
+            let rows = query.fetch_all(self).await?;
+            Ok(rows
+                .iter()
+                .map(|row| row.try_get("id"))
+                .collect::<Result<_, _>>()?)
+        }
+        .boxed_local()
+    }
+
+    fn insert_row<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_values: Vec<(String, DependencyValue)>,
+    ) -> LocalBoxFuture<'a, FieldResult<Id>> {
+        async move {
+            let mut placeholder_index = 0;
+            let query = if column_values.is_empty() {
+                format!(
+                    "INSERT INTO {} DEFAULT VALUES RETURNING id",
+                    quote_identifier(table_name)
+                )
+            } else {
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({}) RETURNING id",
+                    quote_identifier(table_name),
+                    column_values
+                        .iter()
+                        .map(|(column_name, _)| quote_identifier(column_name))
+                        .join(", "),
+                    column_values
+                        .iter()
+                        .map(|(_, value)| match value {
+                            // an untyped `NULL` lets Postgres infer the column type
+                            DependencyValue::Null => "NULL".to_owned(),
+                            _ => {
+                                placeholder_index += 1;
+                                format!("${placeholder_index}")
+                            }
+                        })
+                        .join(", ")
+                )
+            };
+            let mut query = sqlx::query(&query);
+            for (column_name, value) in column_values {
+                if !matches!(value, DependencyValue::Null) {
+                    query = bind_dependency_value(query, value)
+                        .ok_or_else(|| unsupported_column_value_error(&column_name))?;
+                }
+            }
+            let row = query.fetch_one(self).await?;
+            Ok(row.try_get("id")?)
+        }
+        .boxed_local()
+    }
+}
+
+fn get_row_value(
+    row: &PgRow,
+    column_name: &str,
+    type_: DependencyType,
+) -> Result<DependencyValue, sqlx::Error> {
+    let column_name = &*fold_identifier(column_name);
+    Ok(match type_ {
+        DependencyType::Id => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::Id),
+        DependencyType::String => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::String),
+        DependencyType::Int => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::Int),
+        DependencyType::Float => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::Float),
+        DependencyType::Boolean => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::Boolean),
+        DependencyType::DateTime => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::DateTime),
+        DependencyType::Uuid => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::Uuid),
+        DependencyType::Json => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::Json),
+        DependencyType::Decimal => row
+            .try_get::<Option<_>, _>(column_name)?
+            .map_or(DependencyValue::Null, DependencyValue::Decimal),
+        DependencyType::ListOfIds | DependencyType::ListOfStrings | DependencyType::Object => {
+            return Err(unsupported_column_type_error(column_name));
+        }
+    })
+}
+
+fn bind_list_query<'q>(
+    query: Query<'q, Postgres, PgArguments>,
+    list_query: &ListQuery,
+) -> FieldResult<Query<'q, Postgres, PgArguments>> {
+    let mut query = query;
+    for (column_name, value) in &list_query.conditions {
+        query = bind_dependency_value(query, value.clone())
+            .ok_or_else(|| unsupported_condition_value_error(column_name))?;
+    }
+    for id in list_query.after_id.iter().chain(&list_query.before_id) {
+        query = query.bind(*id);
+    }
+    if let Some(limit) = list_query.limit {
+        query = query.bind(limit);
+    }
+    Ok(query)
+}
+
+/// Returns `None` for values that don't map to a single SQL value
+fn bind_dependency_value<'q>(
+    query: Query<'q, Postgres, PgArguments>,
+    value: DependencyValue,
+) -> Option<Query<'q, Postgres, PgArguments>> {
+    Some(match value {
+        DependencyValue::Id(value) => query.bind(value),
+        DependencyValue::String(value) => query.bind(value),
+        DependencyValue::Int(value) => query.bind(value),
+        DependencyValue::Float(value) => query.bind(value),
+        DependencyValue::Boolean(value) => query.bind(value),
+        DependencyValue::DateTime(value) => query.bind(value),
+        DependencyValue::Uuid(value) => query.bind(value),
+        DependencyValue::Json(value) => query.bind(value),
+        DependencyValue::Decimal(value) => query.bind(value),
+        DependencyValue::List(_) | DependencyValue::Object(_) | DependencyValue::Null => {
+            return None;
+        }
+    })
+}
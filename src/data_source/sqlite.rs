@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use futures::{future::LocalBoxFuture, FutureExt};
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use sqlx::{
+    query::Query,
+    sqlite::{SqliteArguments, SqliteRow},
+    Pool, Row, Sqlite, TypeInfo, ValueRef,
+};
+use uuid::Uuid;
+
+use crate::{
+    data_source::{
+        column_decode_error, fold_identifier, list_query_clauses, quote_identifier,
+        unsupported_column_type_error, unsupported_column_value_error,
+        unsupported_condition_value_error,
+    },
+    DataSource, DependencyType, DependencyValue, FieldResult, Id, ListQuery,
+};
+
+impl DataSource for Pool<Sqlite> {
+    fn get_column_values_by_id<'a>(
+        &'a self,
+        table_name: &'a str,
+        columns: &'a [(String, DependencyType)],
+        row_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<DependencyValue>>>> {
+        async move {
+            let query = format!(
+                "SELECT id, {} FROM {} WHERE id IN ({})",
+                columns
+                    .iter()
+                    .map(|(column_name, _)| column_name)
+                    .unique()
+                    .map(|column_name| quote_identifier(column_name))
+                    .join(", "),
+                quote_identifier(table_name),
+                row_ids.iter().map(|_| "?").join(", ")
+            );
+            let mut query = sqlx::query(&query);
+            for row_id in row_ids {
+                query = query.bind(row_id);
+            }
+            let rows = query.fetch_all(self).await?;
+            Ok(rows
+                .into_iter()
+                .map(|row| {
+                    let id = get_id(&row, "id")?;
+                    let values = columns
+                        .iter()
+                        .map(|(column_name, type_)| get_row_value(&row, column_name, *type_))
+                        .collect::<Result<_, sqlx::Error>>()?;
+                    Ok((id, values))
+                })
+                .collect::<Result<_, sqlx::Error>>()?)
+        }
+        .boxed_local()
+    }
+
+    fn get_column_values<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_name: &'a str,
+        type_: DependencyType,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<DependencyValue>>> {
+        async move {
+            let query = format!(
+                "SELECT {} FROM {}{}",
+                quote_identifier(column_name),
+                quote_identifier(table_name),
+                list_query_clauses(list_query, None, |_| "?".to_owned())
+            );
+            let query = bind_list_query(sqlx::query(&query), list_query)?;
+            let rows = query.fetch_all(self).await?;
+            Ok(rows
+                .iter()
+                .map(|row| get_row_value(row, column_name, type_))
+                .collect::<Result<_, _>>()?)
+        }
+        .boxed_local()
+    }
+
+    fn get_ids_by_foreign_key<'a>(
+        &'a self,
+        table_name: &'a str,
+        foreign_key_column_name: &'a str,
+        parent_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<Id>>>> {
+        async move {
+            let query = format!(
+                "SELECT id, {0} FROM {1} WHERE {0} IN ({2}) ORDER BY id",
+                quote_identifier(foreign_key_column_name),
+                quote_identifier(table_name),
+                parent_ids.iter().map(|_| "?").join(", ")
+            );
+            let mut query = sqlx::query(&query);
+            for parent_id in parent_ids {
+                query = query.bind(parent_id);
+            }
+            let rows = query.fetch_all(self).await?;
+            let mut ret: HashMap<Id, Vec<Id>> = HashMap::new();
+            for row in rows {
+                ret.entry(get_id(&row, &fold_identifier(foreign_key_column_name))?)
+                    .or_default()
+                    .push(get_id(&row, "id")?);
+            }
+            Ok(ret)
+        }
+        .boxed_local()
+    }
+
+    fn get_ids_via_join_table<'a>(
+        &'a self,
+        join_table_name: &'a str,
+        parent_column_name: &'a str,
+        target_column_name: &'a str,
+        target_table_name: &'a str,
+        parent_id: Id,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<Id>>> {
+        async move {
+            let query = format!(
+                "SELECT id FROM {}{}",
+                quote_identifier(target_table_name),
+                list_query_clauses(
+                    list_query,
+                    Some(format!(
+                        "id IN (SELECT {} FROM {} WHERE {} = ?)",
+                        quote_identifier(target_column_name),
+                        quote_identifier(join_table_name),
+                        quote_identifier(parent_column_name)
+                    )),
+                    |_| "?".to_owned()
+                )
+            );
+            let query = bind_list_query(sqlx::query(&query).bind(parent_id), list_query)?;
+            let rows = query.fetch_all(self).await?;
+            Ok(rows
+                .iter()
+                .map(|row| get_id(row, "id"))
+                .collect::<Result<_, _>>()?)
+        }
+        .boxed_local()
+    }
+
+    fn insert_row<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_values: Vec<(String, DependencyValue)>,
+    ) -> LocalBoxFuture<'a, FieldResult<Id>> {
+        async move {
+            let query = if column_values.is_empty() {
+                format!(
+                    "INSERT INTO {} DEFAULT VALUES RETURNING id",
+                    quote_identifier(table_name)
+                )
+            } else {
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({}) RETURNING id",
+                    quote_identifier(table_name),
+                    column_values
+                        .iter()
+                        .map(|(column_name, _)| quote_identifier(column_name))
+                        .join(", "),
+                    column_values.iter().map(|_| "?").join(", ")
+                )
+            };
+            let mut query = sqlx::query(&query);
+            for (column_name, value) in column_values {
+                query = bind_dependency_value(query, value)
+                    .ok_or_else(|| unsupported_column_value_error(&column_name))?;
+            }
+            let row = query.fetch_one(self).await?;
+            Ok(get_id(&row, "id")?)
+        }
+        .boxed_local()
+    }
+}
+
+/// Per SQLite's type affinities, a column's values aren't necessarily stored
+/// with the column's declared type (eg an id stored as `TEXT`), so values
+/// get read via SQLite's own conversions rather than sqlx's type checks
+fn get_row_value(
+    row: &SqliteRow,
+    column_name: &str,
+    type_: DependencyType,
+) -> Result<DependencyValue, sqlx::Error> {
+    let column_name = &*fold_identifier(column_name);
+    if row.try_get_raw(column_name)?.is_null() {
+        return Ok(DependencyValue::Null);
+    }
+    Ok(match type_ {
+        DependencyType::Id => DependencyValue::Id(get_id(row, column_name)?),
+        DependencyType::String => DependencyValue::String(get_text(row, column_name)?),
+        DependencyType::Int => DependencyValue::Int(row.try_get_unchecked(column_name)?),
+        DependencyType::Float => DependencyValue::Float(row.try_get_unchecked(column_name)?),
+        DependencyType::Boolean => DependencyValue::Boolean(row.try_get_unchecked(column_name)?),
+        DependencyType::DateTime => DependencyValue::DateTime(row.try_get(column_name)?),
+        DependencyType::Uuid => DependencyValue::Uuid(
+            Uuid::parse_str(&get_text(row, column_name)?)
+                .map_err(|error| column_decode_error(column_name, error.into()))?,
+        ),
+        DependencyType::Json => DependencyValue::Json(
+            serde_json::from_str(&get_text(row, column_name)?)
+                .map_err(|error| column_decode_error(column_name, error.into()))?,
+        ),
+        DependencyType::Decimal => DependencyValue::Decimal(
+            get_text(row, column_name)?
+                .parse::<Decimal>()
+                .map_err(|error| column_decode_error(column_name, error.into()))?,
+        ),
+        DependencyType::ListOfIds | DependencyType::ListOfStrings | DependencyType::Object => {
+            return Err(unsupported_column_type_error(column_name));
+        }
+    })
+}
+
+fn get_id(row: &SqliteRow, column_name: &str) -> Result<Id, sqlx::Error> {
+    let id: i64 = if row.try_get_raw(column_name)?.type_info().name() == "TEXT" {
+        get_text(row, column_name)?
+            .parse()
+            .map_err(|error: std::num::ParseIntError| {
+                column_decode_error(column_name, error.into())
+            })?
+    } else {
+        row.try_get_unchecked(column_name)?
+    };
+    Id::try_from(id).map_err(|error| column_decode_error(column_name, error.into()))
+}
+
+fn get_text(row: &SqliteRow, column_name: &str) -> Result<String, sqlx::Error> {
+    row.try_get_unchecked(column_name)
+}
+
+fn bind_list_query<'q>(
+    query: Query<'q, Sqlite, SqliteArguments<'q>>,
+    list_query: &ListQuery,
+) -> FieldResult<Query<'q, Sqlite, SqliteArguments<'q>>> {
+    let mut query = query;
+    for (column_name, value) in &list_query.conditions {
+        query = bind_dependency_value(query, value.clone())
+            .ok_or_else(|| unsupported_condition_value_error(column_name))?;
+    }
+    for id in list_query.after_id.iter().chain(&list_query.before_id) {
+        query = query.bind(*id);
+    }
+    if let Some(limit) = list_query.limit {
+        query = query.bind(limit);
+    }
+    Ok(query)
+}
+
+/// Returns `None` for values that don't map to a single SQL value
+fn bind_dependency_value<'q>(
+    query: Query<'q, Sqlite, SqliteArguments<'q>>,
+    value: DependencyValue,
+) -> Option<Query<'q, Sqlite, SqliteArguments<'q>>> {
+    Some(match value {
+        DependencyValue::Null => query.bind(None::<String>),
+        DependencyValue::Id(value) => query.bind(value),
+        DependencyValue::String(value) => query.bind(value),
+        DependencyValue::Int(value) => query.bind(value),
+        DependencyValue::Float(value) => query.bind(value),
+        DependencyValue::Boolean(value) => query.bind(value),
+        DependencyValue::DateTime(value) => query.bind(value),
+        DependencyValue::Uuid(value) => query.bind(value.to_string()),
+        DependencyValue::Json(value) => query.bind(value.to_string()),
+        DependencyValue::Decimal(value) => query.bind(value.to_string()),
+        DependencyValue::List(_) | DependencyValue::Object(_) => return None,
+    })
+}
@@ -1,15 +1,29 @@
 use std::collections::HashMap;
 
-use squalid::OptionExt;
+use chrono::{DateTime, Utc};
+use futures::future::LocalBoxFuture;
+use rust_decimal::Decimal;
+use serde_json::Value as JsonValue;
+use squalid::{OptionExt, _d};
+use uuid::Uuid;
 
-use crate::{AnyHashMap, Error};
+use crate::{AnyHashMap, DataSource, Error, FieldResult, IndexMap};
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
 pub enum DependencyType {
     Id,
     String,
+    Int,
+    Float,
+    Boolean,
+    DateTime,
+    Uuid,
+    Json,
+    Decimal,
     ListOfIds,
     ListOfStrings,
+    /// An input object value
+    Object,
 }
 
 pub struct ExternalDependency {
@@ -44,7 +58,166 @@ pub enum InternalDependencyResolver {
     Argument(ArgumentInternalDependencyResolver),
     ColumnGetterList(ColumnGetterList),
     LiteralValue(LiteralValueInternalDependencyResolver),
+    IntrospectionSchema,
+    IntrospectionTypeByName,
+    IntrospectionTypeKind,
+    IntrospectionTypeDescription,
+    IntrospectionTypeFields,
     IntrospectionTypeInterfaces,
+    IntrospectionTypePossibleTypes,
+    IntrospectionTypeEnumValues,
+    IntrospectionTypeInputFields,
+    RowInserter(RowInserter),
+    HasManyViaForeignKey(HasManyViaForeignKey),
+    ManyToManyViaJoinTable(ManyToManyViaJoinTable),
+    Custom(Box<dyn CustomInternalDependencyResolver>),
+}
+
+impl InternalDependencyResolver {
+    /// The table and column names that get interpolated into SQL
+    pub fn sql_identifiers(&self) -> Vec<&str> {
+        match self {
+            Self::ColumnGetter(ColumnGetter {
+                table_name,
+                column_name,
+            }) => vec![table_name, column_name],
+            Self::ColumnGetterList(column_getter_list) => [
+                column_getter_list.table_name.as_str(),
+                &column_getter_list.column_name,
+            ]
+            .into_iter()
+            .chain(list_sql_identifiers(
+                &column_getter_list.conditions,
+                column_getter_list.order_by.as_ref(),
+            ))
+            .collect(),
+            Self::RowInserter(row_inserter) => [&row_inserter.table_name]
+                .into_iter()
+                .chain(
+                    row_inserter
+                        .columns
+                        .iter()
+                        .map(|(column_name, _)| column_name),
+                )
+                .map(String::as_str)
+                .collect(),
+            Self::HasManyViaForeignKey(HasManyViaForeignKey {
+                table_name,
+                foreign_key_column_name,
+            }) => vec![table_name, foreign_key_column_name],
+            Self::ManyToManyViaJoinTable(many_to_many) => [
+                &many_to_many.join_table_name,
+                &many_to_many.parent_column_name,
+                &many_to_many.target_column_name,
+                &many_to_many.target_table_name,
+            ]
+            .into_iter()
+            .map(String::as_str)
+            .chain(list_sql_identifiers(
+                &many_to_many.conditions,
+                many_to_many.order_by.as_ref(),
+            ))
+            .collect(),
+            Self::Argument(_)
+            | Self::LiteralValue(_)
+            | Self::Custom(_)
+            | Self::IntrospectionSchema
+            | Self::IntrospectionTypeByName
+            | Self::IntrospectionTypeKind
+            | Self::IntrospectionTypeDescription
+            | Self::IntrospectionTypeFields
+            | Self::IntrospectionTypeInterfaces
+            | Self::IntrospectionTypePossibleTypes
+            | Self::IntrospectionTypeEnumValues
+            | Self::IntrospectionTypeInputFields => vec![],
+        }
+    }
+
+    /// The names of the field's arguments that this reads
+    pub fn argument_names(&self) -> Vec<&str> {
+        match self {
+            Self::Argument(ArgumentInternalDependencyResolver { name }) => vec![name],
+            Self::RowInserter(RowInserter {
+                input_object_argument_name: Some(input_object_argument_name),
+                ..
+            }) => vec![input_object_argument_name],
+            Self::RowInserter(row_inserter) => row_inserter
+                .columns
+                .iter()
+                .map(|(_, argument_name)| argument_name.as_str())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// The names of the field's (preceding) internal dependencies that this
+    /// reads
+    pub fn internal_dependency_names(&self) -> Vec<&str> {
+        match self {
+            Self::ColumnGetterList(column_getter_list) => list_internal_dependency_names(
+                &column_getter_list.conditions,
+                column_getter_list.order_by.as_ref(),
+                column_getter_list.limit.as_ref(),
+            )
+            .collect(),
+            Self::ManyToManyViaJoinTable(many_to_many) => list_internal_dependency_names(
+                &many_to_many.conditions,
+                many_to_many.order_by.as_ref(),
+                many_to_many.limit.as_ref(),
+            )
+            .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+fn list_internal_dependency_names<'a>(
+    conditions: &'a [(String, ColumnGetterListValue)],
+    order_by: Option<&'a ColumnGetterListOrderBy>,
+    limit: Option<&'a ColumnGetterListValue>,
+) -> impl Iterator<Item = &'a str> {
+    conditions
+        .iter()
+        .map(|(_, value)| value)
+        .chain(limit)
+        .filter_map(|value| match value {
+            ColumnGetterListValue::Literal(_) => None,
+            ColumnGetterListValue::InternalDependency(name) => Some(name),
+        })
+        .chain(order_by.and_then(|order_by| match order_by {
+            ColumnGetterListOrderBy::Fixed(_) => None,
+            ColumnGetterListOrderBy::InternalDependency(name, _) => Some(name),
+        }))
+        .map(String::as_str)
+}
+
+fn list_sql_identifiers<'a>(
+    conditions: &'a [(String, ColumnGetterListValue)],
+    order_by: Option<&'a ColumnGetterListOrderBy>,
+) -> impl Iterator<Item = &'a str> {
+    conditions
+        .iter()
+        .map(|(column_name, _)| column_name)
+        .chain(order_by.into_iter().flat_map(|order_by| match order_by {
+            ColumnGetterListOrderBy::Fixed(order) => vec![&order.column_name],
+            ColumnGetterListOrderBy::InternalDependency(_, orders) => {
+                orders.values().map(|order| &order.column_name).collect()
+            }
+        }))
+        .map(String::as_str)
+}
+
+/// For resolving an internal dependency in a way that none of the built-in
+/// resolvers cover, eg with a hand-written query
+pub trait CustomInternalDependencyResolver {
+    /// `internal_dependencies` has the values of the internal dependencies
+    /// listed before this one
+    fn resolve<'a>(
+        &'a self,
+        external_dependencies: &'a ExternalDependencyValues,
+        internal_dependencies: &'a InternalDependencyValues,
+        data_source: &'a dyn DataSource,
+    ) -> LocalBoxFuture<'a, FieldResult<DependencyValue>>;
 }
 
 pub struct ColumnGetter {
@@ -71,9 +244,19 @@ impl ArgumentInternalDependencyResolver {
     }
 }
 
+/// Resolves to a column's values across the rows of a table, optionally
+/// filtered, ordered and limited. Values referring to other internal
+/// dependencies (eg ones resolved from arguments) require those to be listed
+/// before this one
 pub struct ColumnGetterList {
     pub table_name: String,
     pub column_name: String,
+    /// (column name, value) pairs that rows must match. A condition whose
+    /// value resolves to `null` is skipped
+    pub conditions: Vec<(String, ColumnGetterListValue)>,
+    pub order_by: Option<ColumnGetterListOrderBy>,
+    /// Unlimited if this resolves to `null`
+    pub limit: Option<ColumnGetterListValue>,
 }
 
 impl ColumnGetterList {
@@ -81,12 +264,173 @@ impl ColumnGetterList {
         Self {
             table_name,
             column_name,
+            conditions: _d(),
+            order_by: _d(),
+            limit: _d(),
+        }
+    }
+
+    pub fn condition(self, column_name: String, value: ColumnGetterListValue) -> Self {
+        let mut new = self;
+        new.conditions.push((column_name, value));
+        new
+    }
+
+    pub fn order_by(self, order_by: ColumnGetterListOrderBy) -> Self {
+        let mut new = self;
+        new.order_by = Some(order_by);
+        new
+    }
+
+    pub fn limit(self, limit: ColumnGetterListValue) -> Self {
+        let mut new = self;
+        new.limit = Some(limit);
+        new
+    }
+}
+
+pub enum ColumnGetterListValue {
+    Literal(DependencyValue),
+    /// The value of another of the field's internal dependencies
+    InternalDependency(String),
+}
+
+pub enum ColumnGetterListOrderBy {
+    Fixed(ListOrder),
+    /// Looks up the ordering by the (eg enum argument) string value of
+    /// another of the field's internal dependencies. Unordered if that value
+    /// is `null`
+    InternalDependency(String, HashMap<String, ListOrder>),
+}
+
+#[derive(Clone, Debug)]
+pub struct ListOrder {
+    pub column_name: String,
+    pub direction: OrderDirection,
+}
+
+impl ListOrder {
+    pub fn new(column_name: String, direction: OrderDirection) -> Self {
+        Self {
+            column_name,
+            direction,
         }
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum OrderDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
 pub struct LiteralValueInternalDependencyResolver(pub DependencyValue);
 
+/// Inserts a row into `table_name` (populating each column from the
+/// argument it's mapped to) and resolves to the new row's id
+pub struct RowInserter {
+    pub table_name: String,
+    /// (column name, argument name) pairs, or (column name, input object
+    /// field name) pairs if `input_object_argument_name` is set
+    pub columns: Vec<(String, String)>,
+    pub input_object_argument_name: Option<String>,
+}
+
+impl RowInserter {
+    pub fn new(table_name: String, columns: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            table_name,
+            columns: columns.into_iter().collect(),
+            input_object_argument_name: None,
+        }
+    }
+
+    /// Populates the columns from the fields of a single input object
+    /// argument
+    pub fn new_from_input_object(
+        table_name: String,
+        input_object_argument_name: String,
+        columns: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        Self {
+            table_name,
+            columns: columns.into_iter().collect(),
+            input_object_argument_name: Some(input_object_argument_name),
+        }
+    }
+}
+
+/// Resolves to the ids of the rows of `table_name` whose
+/// `foreign_key_column_name` is the parent's `"id"` external dependency.
+/// Batched across all of the parents pending within a tick
+pub struct HasManyViaForeignKey {
+    pub table_name: String,
+    pub foreign_key_column_name: String,
+}
+
+impl HasManyViaForeignKey {
+    pub fn new(table_name: String, foreign_key_column_name: String) -> Self {
+        Self {
+            table_name,
+            foreign_key_column_name,
+        }
+    }
+}
+
+/// Resolves to the ids of the rows of `target_table_name` that are joined
+/// via `join_table_name` to the parent's `"id"` external dependency, ie
+/// `target_table_name.id = join_table_name.target_column_name` where
+/// `join_table_name.parent_column_name` is the parent id. The conditions,
+/// ordering and limit apply to the target table's columns, like for a
+/// `ColumnGetterList`
+pub struct ManyToManyViaJoinTable {
+    pub join_table_name: String,
+    pub parent_column_name: String,
+    pub target_column_name: String,
+    pub target_table_name: String,
+    pub conditions: Vec<(String, ColumnGetterListValue)>,
+    pub order_by: Option<ColumnGetterListOrderBy>,
+    pub limit: Option<ColumnGetterListValue>,
+}
+
+impl ManyToManyViaJoinTable {
+    pub fn new(
+        join_table_name: String,
+        parent_column_name: String,
+        target_column_name: String,
+        target_table_name: String,
+    ) -> Self {
+        Self {
+            join_table_name,
+            parent_column_name,
+            target_column_name,
+            target_table_name,
+            conditions: _d(),
+            order_by: _d(),
+            limit: _d(),
+        }
+    }
+
+    pub fn condition(self, column_name: String, value: ColumnGetterListValue) -> Self {
+        let mut new = self;
+        new.conditions.push((column_name, value));
+        new
+    }
+
+    pub fn order_by(self, order_by: ColumnGetterListOrderBy) -> Self {
+        let mut new = self;
+        new.order_by = Some(order_by);
+        new
+    }
+
+    pub fn limit(self, limit: ColumnGetterListValue) -> Self {
+        let mut new = self;
+        new.limit = Some(limit);
+        new
+    }
+}
+
 pub struct ExternalDependencyValue {
     pub name: String,
     pub value: DependencyValue,
@@ -94,11 +438,21 @@ pub struct ExternalDependencyValue {
 
 pub type Id = i32;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DependencyValue {
     Id(Id),
     String(String),
+    Int(i32),
+    Float(f64),
+    Boolean(bool),
+    DateTime(DateTime<Utc>),
+    Uuid(Uuid),
+    Json(JsonValue),
+    Decimal(Decimal),
     List(Vec<DependencyValue>),
+    /// An input object value
+    Object(IndexMap<String, DependencyValue>),
+    Null,
 }
 
 impl DependencyValue {
@@ -116,17 +470,73 @@ impl DependencyValue {
         }
     }
 
+    pub fn as_int(&self) -> &i32 {
+        match self {
+            Self::Int(int) => int,
+            _ => panic!("Expected int"),
+        }
+    }
+
+    pub fn as_float(&self) -> &f64 {
+        match self {
+            Self::Float(float) => float,
+            _ => panic!("Expected float"),
+        }
+    }
+
+    pub fn as_boolean(&self) -> &bool {
+        match self {
+            Self::Boolean(boolean) => boolean,
+            _ => panic!("Expected boolean"),
+        }
+    }
+
+    pub fn as_date_time(&self) -> &DateTime<Utc> {
+        match self {
+            Self::DateTime(date_time) => date_time,
+            _ => panic!("Expected date-time"),
+        }
+    }
+
+    pub fn as_uuid(&self) -> &Uuid {
+        match self {
+            Self::Uuid(uuid) => uuid,
+            _ => panic!("Expected UUID"),
+        }
+    }
+
+    pub fn as_json(&self) -> &JsonValue {
+        match self {
+            Self::Json(json) => json,
+            _ => panic!("Expected JSON"),
+        }
+    }
+
+    pub fn as_decimal(&self) -> &Decimal {
+        match self {
+            Self::Decimal(decimal) => decimal,
+            _ => panic!("Expected decimal"),
+        }
+    }
+
     pub fn as_list(&self) -> &Vec<DependencyValue> {
         match self {
             Self::List(values) => values,
             _ => panic!("Expected list"),
         }
     }
+
+    pub fn as_object(&self) -> &IndexMap<String, DependencyValue> {
+        match self {
+            Self::Object(fields) => fields,
+            _ => panic!("Expected object"),
+        }
+    }
 }
 
 pub type InternalDependencyValue = ExternalDependencyValue;
 
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct ExternalDependencyValues {
     knowns: HashMap<String, DependencyValue>,
     anys: AnyHashMap,
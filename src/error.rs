@@ -1,11 +1,131 @@
 use thiserror::Error;
 
+use crate::ParseOrLexError;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("must provide query type")]
     NoQueryTypeSpecified,
     #[error("dependency already populated: `{0}`")]
     DependencyAlreadyPopulated(String),
+    #[error("invalid SQL identifier: `{0}`")]
+    InvalidSqlIdentifier(String),
+    #[error("invalid SDL: {}", .0.message())]
+    InvalidSdl(ParseOrLexError),
+    #[error("missing resolver for field `{0}`")]
+    MissingResolver(String),
+    #[error("resolver provided for unknown field `{0}`")]
+    ExtraResolver(String),
+    #[error("missing subscription source for field `{0}`")]
+    MissingSubscriptionSource(String),
+    #[error("subscription source provided for non-subscription field `{0}`")]
+    ExtraSubscriptionSource(String),
+    #[error("missing custom scalar `{0}`")]
+    MissingCustomScalar(String),
+    #[error("custom scalar provided for unknown scalar `{0}`")]
+    ExtraCustomScalar(String),
+    #[error("duplicate type name `{0}`")]
+    DuplicateTypeName(String),
+    #[error("`{coordinate}` refers to unknown type `{type_name}`")]
+    UnknownType {
+        coordinate: String,
+        type_name: String,
+    },
+    #[error("`{coordinate}` has non-output type `{type_name}`")]
+    NonOutputType {
+        coordinate: String,
+        type_name: String,
+    },
+    #[error("`{coordinate}` has non-input type `{type_name}`")]
+    NonInputType {
+        coordinate: String,
+        type_name: String,
+    },
+    #[error("union `{union}` has unknown member type `{type_name}`")]
+    UnknownUnionMember { union: String, type_name: String },
+    #[error("union `{union}` has non-object member type `{type_name}`")]
+    NonObjectUnionMember { union: String, type_name: String },
+    #[error("`{type_name}` implements unknown interface `{interface}`")]
+    UnknownInterface {
+        type_name: String,
+        interface: String,
+    },
+    #[error("`{type_name}` implements `{interface}` so must implement `{transitive_interface}`")]
+    MissingTransitiveInterface {
+        type_name: String,
+        interface: String,
+        transitive_interface: String,
+    },
+    #[error("`{type_name}` implements `{interface}` but lacks field `{field}`")]
+    MissingInterfaceField {
+        type_name: String,
+        interface: String,
+        field: String,
+    },
+    #[error("`{type_name}.{field}` has a type incompatible with `{interface}.{field}`")]
+    IncompatibleInterfaceField {
+        type_name: String,
+        interface: String,
+        field: String,
+    },
+    #[error("`{type_name}.{field}` doesn't match `{interface}.{field}`'s argument `{argument}`")]
+    IncompatibleInterfaceFieldArgument {
+        type_name: String,
+        interface: String,
+        field: String,
+        argument: String,
+    },
+    #[error("`{type_name}.{field}` has required argument `{argument}` not on `{interface}`")]
+    RequiredExtraFieldArgument {
+        type_name: String,
+        interface: String,
+        field: String,
+        argument: String,
+    },
+    #[error("`{coordinate}` has duplicate dependency `{dependency}`")]
+    DuplicateDependency {
+        coordinate: String,
+        dependency: String,
+    },
+    #[error("`{coordinate}` uses unknown dependency `{dependency}`")]
+    UnknownDependency {
+        coordinate: String,
+        dependency: String,
+    },
+    #[error("`{coordinate}` depends on unknown argument `{argument}`")]
+    UnknownArgument {
+        coordinate: String,
+        argument: String,
+    },
+    #[error("`{coordinate}` depends on argument `{argument}` with an incompatible type")]
+    IncompatibleArgumentDependency {
+        coordinate: String,
+        argument: String,
+    },
 }
 
 pub type Result<TSuccess> = std::result::Result<TSuccess, Error>;
+
+/// An error encountered while resolving a field, which gets reported
+/// (along with the field's path) next to the partial response data
+#[derive(Clone, Error, Debug)]
+#[error("{message}")]
+pub struct FieldError {
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl From<sqlx::Error> for FieldError {
+    fn from(value: sqlx::Error) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+pub type FieldResult<TSuccess> = std::result::Result<TSuccess, FieldError>;
@@ -1,4 +1,6 @@
 mod any_hash_map;
+mod connection;
+mod data_source;
 mod dependencies;
 mod error;
 mod hash;
@@ -11,43 +13,59 @@ mod request;
 mod resolve;
 mod response;
 mod schema;
+mod subscription;
 mod types;
 
 pub use indexmap::IndexMap;
 
 pub use crate::any_hash_map::AnyHashMap;
+pub use crate::connection::{
+    decode_cursor, encode_cursor, page_info_type, Connection, ConnectionArgumentCondition,
+};
+pub use crate::data_source::{DataSource, InMemoryDataSource, InMemoryRow, ListQuery};
 pub use crate::dependencies::{
-    ArgumentInternalDependencyResolver, ColumnGetter, ColumnGetterList, DependencyType,
-    DependencyValue, ExternalDependency, ExternalDependencyValue, ExternalDependencyValues, Id,
-    InternalDependency, InternalDependencyResolver, InternalDependencyValue,
-    InternalDependencyValues, LiteralValueInternalDependencyResolver,
+    ArgumentInternalDependencyResolver, ColumnGetter, ColumnGetterList, ColumnGetterListOrderBy,
+    ColumnGetterListValue, CustomInternalDependencyResolver, DependencyType, DependencyValue,
+    ExternalDependency, ExternalDependencyValue, ExternalDependencyValues, HasManyViaForeignKey,
+    Id, InternalDependency, InternalDependencyResolver, InternalDependencyValue,
+    InternalDependencyValues, ListOrder, LiteralValueInternalDependencyResolver,
+    ManyToManyViaJoinTable, OrderDirection, RowInserter,
 };
-pub use crate::error::{Error, Result};
+pub use crate::error::{Error, FieldError, FieldResult, Result};
 pub use crate::hash::get_hash;
 pub use crate::inscribe::json_from_response;
 pub use crate::operation::OperationType;
-pub use crate::parser::{lex, parse, LexError, ParseError, ParseOrLexError, Token};
+pub use crate::parser::{
+    lex, parse, parse_sdl, FieldDefinition, LexError, ObjectTypeDefinition, ParseError,
+    ParseOrLexError, Token, TypeSystemDefinition, TypeSystemDocument,
+};
 pub use crate::plan::{FieldPlan, QueryPlan};
 pub use crate::positions::{CharsEmitter, Location, PositionsTracker};
 pub use crate::request::{
     Argument, Directive, Document, ExecutableDefinition, Field as SelectionField,
     FieldBuilder as SelectionFieldBuilder, FragmentDefinition, FragmentSpread, InlineFragment,
-    OperationDefinition, OperationDefinitionBuilder, Request, Selection, Value,
+    OperationDefinition, OperationDefinitionBuilder, Request, RequestOptions,
+    RequestOptionsBuilder, Selection, Value, VariableDefinition,
 };
 pub use crate::resolve::{
-    Carver, CarverOrPopulator, FieldResolver, Populator, PopulatorList, StringCarver,
-    TypeDepluralizer, UnionOrInterfaceTypePopulator, UnionOrInterfaceTypePopulatorList,
-    ValuePopulator, ValuePopulatorList, ValuesPopulator,
+    BooleanCarver, Carver, CarverOrPopulator, CustomScalarCarver, EnumCarver, FieldResolver,
+    FloatCarver, IdCarver, IntCarver, OptionalPopulator, OptionalPopulatorList, Populator,
+    PopulatorList, StringCarver, TypeDepluralizer, UnionOrInterfaceTypePopulator,
+    UnionOrInterfaceTypePopulatorList, ValuePopulator, ValuePopulatorList, ValuesPopulator,
 };
 pub use crate::response::{
     fields_in_progress_new, FieldsInProgress, InProgress, InProgressRecursing,
-    InProgressRecursingList, Response, ResponseInProgress, ResponseValue,
-    ResponseValueOrInProgress,
+    InProgressRecursingList, PathSegment, Response, ResponseError, ResponseInProgress,
+    ResponseValue, ResponseValueOrInProgress,
 };
-pub use crate::schema::{Schema, TypeOrUnionOrInterface, ValidationError};
+pub use crate::schema::{Schema, SdlResolvers, TypeOrUnionOrInterface, ValidationError};
+pub use crate::subscription::{BroadcastSubscriptionSource, SubscriptionSource};
 pub use crate::types::{
-    builtin_types, string_type, BuiltInScalarType, DummyUnionTypenameField, Field as TypeField,
-    FieldBuilder as TypeFieldBuilder, FieldInterface, Interface, InterfaceBuilder, InterfaceField,
-    ObjectType, ObjectTypeBuilder, Param, ScalarType, StringType, Type, TypeFull, TypeInterface,
-    TypeOrInterfaceField, Union,
+    boolean_type, builtin_types, float_type, id_type, int_type, string_type, BooleanType,
+    BuiltInScalarType, CustomScalar, DummyUnionTypenameField, EnumType, EnumTypeBuilder, EnumValue,
+    EnumValueBuilder, Field as TypeField, FieldBuilder as TypeFieldBuilder, FieldInterface,
+    FloatType, IdType, InputField, InputFieldBuilder, InputObjectType, InputObjectTypeBuilder,
+    IntType, Interface, InterfaceBuilder, InterfaceField, ObjectType, ObjectTypeBuilder, Param,
+    ScalarType, StringType, Type, TypeFull, TypeInterface, TypeOrInterfaceField, Union,
+    DEFAULT_DEPRECATION_REASON,
 };
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use rkyv::{Archive, Deserialize, Serialize};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Archive, Serialize, Deserialize)]
@@ -6,3 +8,13 @@ pub enum OperationType {
     Mutation,
     Subscription,
 }
+
+impl fmt::Display for OperationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Query => "query",
+            Self::Mutation => "mutation",
+            Self::Subscription => "subscription",
+        })
+    }
+}
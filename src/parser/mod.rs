@@ -5,7 +5,13 @@ use squalid::_d;
 use crate::{
     Argument, CharsEmitter, Directive, Document, ExecutableDefinition, FragmentDefinition,
     FragmentSpread, InlineFragment, Location, OperationDefinitionBuilder, OperationType,
-    PositionsTracker, Request, Selection, SelectionFieldBuilder, Value,
+    PositionsTracker, Request, Selection, SelectionFieldBuilder, TypeFull, Value,
+    VariableDefinition,
+};
+
+mod sdl;
+pub use sdl::{
+    parse_sdl, FieldDefinition, ObjectTypeDefinition, TypeSystemDefinition, TypeSystemDocument,
 };
 
 const UNICODE_BOM: char = '\u{feff}';
@@ -118,9 +124,38 @@ where
                             Some(Token::Name(chars.iter().collect()))
                         }
                         '"' => match self.request.next() {
-                            Some('"') => match self.request.next() {
+                            Some('"') => match self.request.peek() {
                                 Some('"') => {
-                                    unimplemented!()
+                                    let _ = self.request.next().unwrap();
+                                    let mut raw_chars: Vec<char> = _d();
+                                    loop {
+                                        match self.request.next() {
+                                            None => {
+                                                return Some(Err(
+                                                    self.error("expected closing triple-quote")
+                                                ))
+                                            }
+                                            Some('"') if self.request.peek() == Some(&'"') => {
+                                                let _ = self.request.next().unwrap();
+                                                if self.request.peek() != Some(&'"') {
+                                                    raw_chars.extend(['"', '"']);
+                                                    continue;
+                                                }
+                                                let _ = self.request.next().unwrap();
+                                                // `\"""` is the only escape sequence in block
+                                                // strings
+                                                if raw_chars.last() == Some(&'\\') {
+                                                    raw_chars.pop();
+                                                    raw_chars.extend(['"', '"', '"']);
+                                                    continue;
+                                                }
+                                                break Some(Token::String(block_string_value(
+                                                    &raw_chars.into_iter().collect::<String>(),
+                                                )));
+                                            }
+                                            Some(ch) => raw_chars.push(ch),
+                                        }
+                                    }
                                 }
                                 _ => Some(Token::String("".to_owned())),
                             },
@@ -406,6 +441,41 @@ where
     })
 }
 
+/// Per spec, strips the common indentation and any leading/trailing blank
+/// lines from a block string's raw contents
+fn block_string_value(raw_value: &str) -> String {
+    let mut lines = raw_value
+        .split("\r\n")
+        .flat_map(|line| line.split(['\r', '\n']))
+        .collect::<Vec<_>>();
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter_map(|line| {
+            let indent = line
+                .chars()
+                .take_while(|ch| matches!(ch, ' ' | '\t'))
+                .count();
+            (indent < line.len()).then_some(indent)
+        })
+        .min();
+    if let Some(common_indent) = common_indent {
+        for line in lines.iter_mut().skip(1) {
+            *line = line.get(common_indent..).unwrap_or_default();
+        }
+    }
+    let is_blank = |line: &&str| line.chars().all(|ch| matches!(ch, ' ' | '\t'));
+    let start = lines
+        .iter()
+        .position(|line| !is_blank(line))
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !is_blank(line))
+        .map_or(start, |index| index + 1);
+    lines[start..end.max(start)].join("\n")
+}
+
 pub fn parse(request: impl IntoIterator<Item = char>) -> ParseResult<Request> {
     parse_tokens(lex(request))
 }
@@ -422,93 +492,53 @@ pub fn parse_tokens(tokens: impl IntoIterator<Item = LexResult<Token>>) -> Parse
                             &token,
                             Token::Name(name) if matches!(
                                 &**name,
-                                "query" // | "mutation" | "subscription"
+                                "query" | "mutation" | "subscription"
                             )
                         ) =>
                 {
                     PositionsTracker::emit_operation();
                     definitions.push({
                         let mut builder = OperationDefinitionBuilder::default();
-                        builder = builder.operation_type(OperationType::Query);
-                        match token {
-                            Token::Name(_parsed_operation_type) => match tokens
-                                .next()
-                                .transpose()?
-                            {
-                                Some(Token::LeftCurlyBracket) => ExecutableDefinition::Operation(
-                                    builder
-                                        .selection_set(parse_selection_set(&mut tokens)?)
-                                        .build()
-                                        .unwrap(),
-                                ),
-                                Some(Token::AtSymbol) => {
+                        builder = builder.operation_type(match &token {
+                            Token::Name(name) if name == "mutation" => OperationType::Mutation,
+                            Token::Name(name) if name == "subscription" => {
+                                OperationType::Subscription
+                            }
+                            _ => OperationType::Query,
+                        });
+                        if matches!(token, Token::Name(_)) {
+                            match tokens.peek() {
+                                Some(Ok(Token::Name(_))) => {
                                     builder =
-                                        builder.directives(parse_directives(&mut tokens, false)?);
-                                    match tokens.next().transpose()? {
-                                        Some(Token::LeftCurlyBracket) => {
-                                            ExecutableDefinition::Operation(
-                                                builder
-                                                    .selection_set(parse_selection_set(
-                                                        &mut tokens,
-                                                    )?)
-                                                    .build()
-                                                    .unwrap(),
-                                            )
-                                        }
-                                        _ => {
-                                            return Err(parse_error("Expected selection set").into())
-                                        }
-                                    }
+                                        builder.name(tokens.next().unwrap().unwrap().into_name());
                                 }
-                                Some(Token::Name(name)) => {
-                                    builder = builder.name(name);
-                                    match tokens.next().transpose()? {
-                                        Some(Token::AtSymbol) => {
-                                            builder = builder
-                                                .directives(parse_directives(&mut tokens, false)?);
-                                            match tokens.next().transpose()? {
-                                                Some(Token::LeftCurlyBracket) => {
-                                                    ExecutableDefinition::Operation(
-                                                        builder
-                                                            .selection_set(parse_selection_set(
-                                                                &mut tokens,
-                                                            )?)
-                                                            .build()
-                                                            .unwrap(),
-                                                    )
-                                                }
-                                                _ => {
-                                                    return Err(parse_error(
-                                                        "Expected selection set",
-                                                    )
-                                                    .into())
-                                                }
-                                            }
-                                        }
-                                        Some(Token::LeftCurlyBracket) => {
-                                            ExecutableDefinition::Operation(
-                                                builder
-                                                    .selection_set(parse_selection_set(
-                                                        &mut tokens,
-                                                    )?)
-                                                    .build()
-                                                    .unwrap(),
-                                            )
-                                        }
-                                        _ => {
-                                            return Err(parse_error("Expected selection set").into())
-                                        }
-                                    }
+                                Some(Ok(
+                                    Token::LeftParen | Token::AtSymbol | Token::LeftCurlyBracket,
+                                )) => {}
+                                _ => {
+                                    let _ = tokens.next().transpose()?;
+                                    return Err(parse_error("Expected query").into());
                                 }
-                                _ => return Err(parse_error("Expected query").into()),
-                            },
-                            _ => ExecutableDefinition::Operation(
-                                builder
-                                    .selection_set(parse_selection_set(&mut tokens)?)
-                                    .build()
-                                    .unwrap(),
-                            ),
+                            }
+                            if matches!(tokens.peek(), Some(Ok(Token::LeftParen))) {
+                                builder = builder
+                                    .variable_definitions(parse_variable_definitions(&mut tokens)?);
+                            }
+                            if matches!(tokens.peek(), Some(Ok(Token::AtSymbol))) {
+                                let _ = tokens.next().unwrap().unwrap();
+                                builder = builder.directives(parse_directives(&mut tokens, false)?);
+                            }
+                            if !matches!(tokens.next().transpose()?, Some(Token::LeftCurlyBracket))
+                            {
+                                return Err(parse_error("Expected selection set").into());
+                            }
                         }
+                        ExecutableDefinition::Operation(
+                            builder
+                                .selection_set(parse_selection_set(&mut tokens)?)
+                                .build()
+                                .unwrap(),
+                        )
                     });
                 }
                 Some(Token::Name(name)) if name == "fragment" => {
@@ -669,16 +699,125 @@ where
     }
 }
 
+fn parse_variable_definitions<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+) -> ParseResult<Vec<VariableDefinition>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    let _ = tokens.next().unwrap().unwrap();
+    let mut ret: Vec<VariableDefinition> = _d();
+    loop {
+        match tokens.next().transpose()? {
+            Some(Token::DollarSign) => {
+                PositionsTracker::emit_variable_definition();
+                ret.push(VariableDefinition::new(
+                    parse_variable_name(tokens)?,
+                    {
+                        if !matches!(tokens.next().transpose()?, Some(Token::Colon)) {
+                            return Err(parse_error("Expected colon").into());
+                        }
+                        parse_type(tokens)?
+                    },
+                    match tokens.peek() {
+                        Some(Ok(Token::Equals)) => {
+                            let _ = tokens.next().unwrap().unwrap();
+                            Some(parse_value(tokens, true)?)
+                        }
+                        _ => None,
+                    },
+                ));
+            }
+            Some(Token::RightParen) => {
+                if ret.is_empty() {
+                    return Err(parse_error("Empty variable definitions").into());
+                }
+                return Ok(ret);
+            }
+            _ => return Err(parse_error("Expected variable definition").into()),
+        }
+    }
+}
+
+fn parse_variable_name<TIterator>(tokens: &mut Peekable<TIterator>) -> ParseResult<String>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    match tokens.next().transpose()? {
+        Some(Token::Name(name)) => Ok(name),
+        _ => Err(parse_error("Expected variable name").into()),
+    }
+}
+
+fn parse_type<TIterator>(tokens: &mut Peekable<TIterator>) -> ParseResult<TypeFull>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    let type_ = match tokens.next().transpose()? {
+        Some(Token::Name(name)) => TypeFull::Type(name),
+        Some(Token::LeftSquareBracket) => {
+            let type_ = parse_type(tokens)?;
+            if !matches!(tokens.next().transpose()?, Some(Token::RightSquareBracket)) {
+                return Err(parse_error("Expected closing square bracket").into());
+            }
+            TypeFull::List(Box::new(type_))
+        }
+        _ => return Err(parse_error("Expected type").into()),
+    };
+    Ok(match tokens.peek() {
+        Some(Ok(Token::ExclamationPoint)) => {
+            let _ = tokens.next().unwrap().unwrap();
+            TypeFull::NonNull(Box::new(type_))
+        }
+        _ => type_,
+    })
+}
+
 fn parse_value<TIterator>(tokens: &mut Peekable<TIterator>, is_const: bool) -> ParseResult<Value>
 where
     TIterator: Iterator<Item = LexResult<Token>>,
 {
     Ok(match tokens.next().transpose()? {
+        Some(Token::DollarSign) => {
+            if is_const {
+                return Err(parse_error("Variables aren't allowed in constant values").into());
+            }
+            Value::Variable(parse_variable_name(tokens)?)
+        }
         Some(Token::Int(int)) => Value::Int(int),
+        Some(Token::Float(float)) => Value::Float(float),
         Some(Token::String(string)) => Value::String(string),
         Some(Token::Name(name)) if name == "null" => Value::Null,
         Some(Token::Name(name)) if name == "true" => Value::Bool(true),
         Some(Token::Name(name)) if name == "false" => Value::Bool(false),
+        Some(Token::Name(name)) => Value::Enum(name),
+        Some(Token::LeftSquareBracket) => {
+            let mut values: Vec<Value> = _d();
+            loop {
+                match tokens.peek() {
+                    Some(Ok(Token::RightSquareBracket)) => {
+                        let _ = tokens.next().unwrap().unwrap();
+                        break Value::List(values);
+                    }
+                    _ => values.push(parse_value(tokens, is_const)?),
+                }
+            }
+        }
+        Some(Token::LeftCurlyBracket) => {
+            let mut fields: Vec<(String, Value)> = _d();
+            loop {
+                match tokens.next().transpose()? {
+                    Some(Token::Name(name)) => {
+                        if !matches!(tokens.next().transpose()?, Some(Token::Colon)) {
+                            return Err(parse_error("Expected colon").into());
+                        }
+                        fields.push((name, parse_value(tokens, is_const)?));
+                    }
+                    Some(Token::RightCurlyBracket) => break Value::Object(fields),
+                    _ => return Err(parse_error("Expected object field").into()),
+                }
+            }
+        }
         _ => return Err(parse_error("Expected value").into()),
     })
 }
@@ -854,6 +993,33 @@ mod tests {
         lex_test(r#""abc""#, [Token::String("abc".to_owned())]);
     }
 
+    #[test]
+    fn test_block_string() {
+        lex_test(r#""""""""#, [Token::String("".to_owned())]);
+        lex_test(r#""""abc""""#, [Token::String("abc".to_owned())]);
+        lex_test(
+            "\"\"\"\n    Hello,\n      World!\n\n    Yours,\n      GraphQL.\n  \"\"\"",
+            [Token::String(
+                "Hello,\n  World!\n\nYours,\n  GraphQL.".to_owned(),
+            )],
+        );
+        lex_test(
+            r#""""a \""" b "" c \n""""#,
+            [Token::String(r#"a """ b "" c \n"#.to_owned())],
+        );
+        lex_test(
+            "\"\"\"a\r\n  b\"\"\" c",
+            [
+                Token::String("a\nb".to_owned()),
+                Token::Name("c".to_owned()),
+            ],
+        );
+        lex_test(
+            r#"""a"#,
+            [Token::String("".to_owned()), Token::Name("a".to_owned())],
+        );
+    }
+
     #[test]
     fn test_name() {
         lex_test("Foo", [Token::Name("Foo".to_owned())]);
@@ -891,6 +1057,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lex_unterminated_block_string() {
+        lex_error_test(
+            r#""""abc"#,
+            "expected closing triple-quote",
+            Location::new(1, 6),
+        );
+        lex_error_test(
+            "\"\"\"abc\n  def\"\"",
+            "expected closing triple-quote",
+            Location::new(2, 7),
+        );
+    }
+
     #[test]
     fn test_lex_unicode_escape_non_hex_digit() {
         lex_error_test(r#""\u123z""#, "Unexpected hex digit", Location::new(1, 7));
@@ -1107,6 +1287,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_variable_definitions() {
+        parse_error_test(
+            r#"query Foo($id Int) { actor(id: $id) { name } }"#,
+            "Expected colon",
+            Location::new(1, 15),
+        );
+        parse_error_test(
+            r#"query Foo() { actor(id: 1) { name } }"#,
+            "Empty variable definitions",
+            Location::new(1, 11),
+        );
+        parse_error_test(
+            r#"query Foo($id: [Int) { actor(id: $id) { name } }"#,
+            "Expected closing square bracket",
+            Location::new(1, 20),
+        );
+        parse_error_test(
+            r#"query Foo($id: Int = $other) { actor(id: $id) { name } }"#,
+            "Variables aren't allowed in constant values",
+            Location::new(1, 22),
+        );
+    }
+
+    fn parse_argument_value_test(value: &str, expected_value: Value) {
+        let request = parse(format!("{{ actor(id: {value}) {{ name }} }}").chars()).unwrap();
+        let Selection::Field(field) = &request.chosen_operation().selection_set[0] else {
+            panic!("Expected field");
+        };
+        assert_eq!(field.arguments.as_ref().unwrap()[0].value, expected_value);
+    }
+
+    #[test]
+    fn test_parse_values() {
+        parse_argument_value_test("1.5", Value::Float(1.5));
+        parse_argument_value_test("NAME", Value::Enum("NAME".to_owned()));
+        parse_argument_value_test("[]", Value::List(vec![]));
+        parse_argument_value_test(
+            "[1, [true], $id]",
+            Value::List(vec![
+                Value::Int(1),
+                Value::List(vec![Value::Bool(true)]),
+                Value::Variable("id".to_owned()),
+            ]),
+        );
+        parse_argument_value_test(
+            r#"{name: "Katie", ids: [1, 2], nested: {a: null}}"#,
+            Value::Object(vec![
+                ("name".to_owned(), Value::String("Katie".to_owned())),
+                (
+                    "ids".to_owned(),
+                    Value::List(vec![Value::Int(1), Value::Int(2)]),
+                ),
+                (
+                    "nested".to_owned(),
+                    Value::Object(vec![("a".to_owned(), Value::Null)]),
+                ),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_parse_object_value() {
+        parse_error_test(
+            indoc!(
+                r#"
+              {
+                actor(id: {a 1}) {
+                  name
+                }
+              }
+            "#
+            ),
+            "Expected colon",
+            Location::new(2, 16),
+        );
+        parse_error_test(
+            indoc!(
+                r#"
+              {
+                actor(id: {1: 1}) {
+                  name
+                }
+              }
+            "#
+            ),
+            "Expected object field",
+            Location::new(2, 14),
+        );
+    }
+
+    #[test]
+    fn test_parse_block_string_positions() {
+        parse_error_test(
+            indoc!(
+                r#"
+              {
+                actor(id: """
+                  multi-line
+                """ 1) {
+                  name
+                }
+              }
+            "#
+            ),
+            "Expected argument",
+            Location::new(4, 7),
+        );
+    }
+
     #[test]
     fn test_parse_directive() {
         parse_error_test(
@@ -0,0 +1,374 @@
+use std::iter::Peekable;
+
+use squalid::_d;
+
+use super::{
+    lex, parse_directives, parse_error, parse_type, parse_value, LexResult, ParseResult, Token,
+};
+use crate::{
+    EnumType, EnumTypeBuilder, EnumValue, EnumValueBuilder, InputField, InputFieldBuilder,
+    InputObjectType, InputObjectTypeBuilder, Interface, InterfaceBuilder, InterfaceField,
+    OperationType, Param, TypeFull, Union, Value, DEFAULT_DEPRECATION_REASON,
+};
+
+/// A parsed SDL document (type definitions only). Object types' fields don't
+/// have `FieldResolver`s yet, see `Schema::try_from_sdl()`
+pub struct TypeSystemDocument {
+    pub definitions: Vec<TypeSystemDefinition>,
+}
+
+pub enum TypeSystemDefinition {
+    Schema(Vec<(OperationType, String)>),
+    Scalar(String),
+    Object(ObjectTypeDefinition),
+    Interface(Interface),
+    Union(Union),
+    Enum(EnumType),
+    InputObject(InputObjectType),
+}
+
+pub struct ObjectTypeDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    pub implements: Vec<String>,
+    pub fields: Vec<FieldDefinition>,
+}
+
+pub struct FieldDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    /// Deprecated iff set
+    pub deprecation_reason: Option<String>,
+    pub type_: TypeFull,
+    pub params: Vec<Param>,
+}
+
+pub fn parse_sdl(sdl: impl IntoIterator<Item = char>) -> ParseResult<TypeSystemDocument> {
+    let mut tokens = lex(sdl).peekable();
+    let mut definitions: Vec<TypeSystemDefinition> = _d();
+    loop {
+        let description = parse_description(&mut tokens)?;
+        definitions.push(match tokens.next().transpose()? {
+            None if description.is_none() => break,
+            Some(Token::Name(keyword)) => match &*keyword {
+                "schema" => TypeSystemDefinition::Schema(parse_root_operation_types(&mut tokens)?),
+                "scalar" => TypeSystemDefinition::Scalar(parse_name(&mut tokens, "scalar")?),
+                "type" => TypeSystemDefinition::Object(ObjectTypeDefinition {
+                    name: parse_name(&mut tokens, "type")?,
+                    description,
+                    implements: parse_implements(&mut tokens)?,
+                    fields: parse_fields_definition(&mut tokens)?,
+                }),
+                "interface" => {
+                    let mut builder = InterfaceBuilder::default()
+                        .name(parse_name(&mut tokens, "interface")?)
+                        .implements(parse_implements(&mut tokens)?);
+                    if let Some(description) = description {
+                        builder = builder.description(description);
+                    }
+                    TypeSystemDefinition::Interface(
+                        builder
+                            .fields(
+                                parse_fields_definition(&mut tokens)?
+                                    .into_iter()
+                                    .map(|field| {
+                                        let mut ret = InterfaceField::new(
+                                            field.name,
+                                            field.type_,
+                                            field.params,
+                                        );
+                                        ret.description = field.description;
+                                        ret.deprecation_reason = field.deprecation_reason;
+                                        ret
+                                    })
+                                    .collect(),
+                            )
+                            .build()
+                            .unwrap(),
+                    )
+                }
+                "union" => {
+                    let mut union = Union::new(
+                        parse_name(&mut tokens, "union")?,
+                        parse_union_member_types(&mut tokens)?,
+                    );
+                    union.description = description;
+                    TypeSystemDefinition::Union(union)
+                }
+                "enum" => {
+                    let mut builder =
+                        EnumTypeBuilder::default().name(parse_name(&mut tokens, "enum")?);
+                    if let Some(description) = description {
+                        builder = builder.description(description);
+                    }
+                    TypeSystemDefinition::Enum(
+                        builder
+                            .values(parse_enum_values_definition(&mut tokens)?)
+                            .build()
+                            .unwrap(),
+                    )
+                }
+                "input" => {
+                    let mut builder =
+                        InputObjectTypeBuilder::default().name(parse_name(&mut tokens, "input")?);
+                    if let Some(description) = description {
+                        builder = builder.description(description);
+                    }
+                    TypeSystemDefinition::InputObject(
+                        builder
+                            .fields(parse_input_fields_definition(&mut tokens)?)
+                            .build()
+                            .unwrap(),
+                    )
+                }
+                _ => return Err(parse_error("Expected type system definition").into()),
+            },
+            _ => return Err(parse_error("Expected type system definition").into()),
+        });
+    }
+    Ok(TypeSystemDocument { definitions })
+}
+
+fn parse_description<TIterator>(tokens: &mut Peekable<TIterator>) -> ParseResult<Option<String>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    Ok(match tokens.peek() {
+        Some(Ok(Token::String(_))) => match tokens.next().transpose()? {
+            Some(Token::String(description)) => Some(description),
+            _ => unreachable!(),
+        },
+        _ => None,
+    })
+}
+
+fn parse_name<TIterator>(tokens: &mut Peekable<TIterator>, kind: &str) -> ParseResult<String>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    match tokens.next().transpose()? {
+        Some(Token::Name(name)) => Ok(name),
+        _ => Err(parse_error(&format!("Expected {kind} name")).into()),
+    }
+}
+
+fn expect<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+    expected: Token,
+    message: &str,
+) -> ParseResult<()>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    match tokens.next().transpose()? {
+        Some(token) if token == expected => Ok(()),
+        _ => Err(parse_error(message).into()),
+    }
+}
+
+fn next_is<TIterator>(tokens: &mut Peekable<TIterator>, expected: &Token) -> bool
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    if !matches!(tokens.peek(), Some(Ok(token)) if token == expected) {
+        return false;
+    }
+    let _ = tokens.next().unwrap().unwrap();
+    true
+}
+
+fn parse_root_operation_types<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+) -> ParseResult<Vec<(OperationType, String)>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    expect(
+        tokens,
+        Token::LeftCurlyBracket,
+        "Expected root operation types",
+    )?;
+    let mut ret: Vec<(OperationType, String)> = _d();
+    loop {
+        let operation_type = match tokens.next().transpose()? {
+            Some(Token::Name(name)) if name == "query" => OperationType::Query,
+            Some(Token::Name(name)) if name == "mutation" => OperationType::Mutation,
+            Some(Token::Name(name)) if name == "subscription" => OperationType::Subscription,
+            Some(Token::RightCurlyBracket) if !ret.is_empty() => return Ok(ret),
+            _ => return Err(parse_error("Expected root operation type").into()),
+        };
+        expect(tokens, Token::Colon, "Expected colon")?;
+        ret.push((operation_type, parse_name(tokens, "type")?));
+    }
+}
+
+fn parse_implements<TIterator>(tokens: &mut Peekable<TIterator>) -> ParseResult<Vec<String>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    if !next_is(tokens, &Token::Name("implements".to_owned())) {
+        return Ok(_d());
+    }
+    next_is(tokens, &Token::Ampersand);
+    let mut ret = vec![parse_name(tokens, "interface")?];
+    while next_is(tokens, &Token::Ampersand) {
+        ret.push(parse_name(tokens, "interface")?);
+    }
+    Ok(ret)
+}
+
+fn parse_union_member_types<TIterator>(tokens: &mut Peekable<TIterator>) -> ParseResult<Vec<String>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    expect(tokens, Token::Equals, "Expected union member types")?;
+    next_is(tokens, &Token::Pipe);
+    let mut ret = vec![parse_name(tokens, "union member type")?];
+    while next_is(tokens, &Token::Pipe) {
+        ret.push(parse_name(tokens, "union member type")?);
+    }
+    Ok(ret)
+}
+
+fn parse_fields_definition<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+) -> ParseResult<Vec<FieldDefinition>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    expect(tokens, Token::LeftCurlyBracket, "Expected fields")?;
+    let mut ret: Vec<FieldDefinition> = _d();
+    while !next_is(tokens, &Token::RightCurlyBracket) {
+        let description = parse_description(tokens)?;
+        let name = parse_name(tokens, "field")?;
+        let params = match tokens.peek() {
+            Some(Ok(Token::LeftParen)) => parse_arguments_definition(tokens)?,
+            _ => _d(),
+        };
+        expect(tokens, Token::Colon, "Expected colon")?;
+        ret.push(FieldDefinition {
+            name,
+            description,
+            type_: parse_type(tokens)?,
+            deprecation_reason: parse_deprecation_reason(tokens)?,
+            params,
+        });
+    }
+    if ret.is_empty() {
+        return Err(parse_error("Empty fields").into());
+    }
+    Ok(ret)
+}
+
+fn parse_arguments_definition<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+) -> ParseResult<Vec<Param>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    let _ = tokens.next().unwrap().unwrap();
+    let mut ret: Vec<Param> = _d();
+    while !next_is(tokens, &Token::RightParen) {
+        let description = parse_description(tokens)?;
+        let name = parse_name(tokens, "argument")?;
+        expect(tokens, Token::Colon, "Expected colon")?;
+        let mut param = Param::new(name, parse_type(tokens)?);
+        if next_is(tokens, &Token::Equals) {
+            param.default_value = Some(parse_value(tokens, true)?);
+        }
+        param.description = description;
+        param.deprecation_reason = parse_deprecation_reason(tokens)?;
+        ret.push(param);
+    }
+    if ret.is_empty() {
+        return Err(parse_error("Empty arguments").into());
+    }
+    Ok(ret)
+}
+
+fn parse_input_fields_definition<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+) -> ParseResult<Vec<InputField>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    expect(tokens, Token::LeftCurlyBracket, "Expected input fields")?;
+    let mut ret: Vec<InputField> = _d();
+    while !next_is(tokens, &Token::RightCurlyBracket) {
+        let description = parse_description(tokens)?;
+        let mut builder = InputFieldBuilder::default().name(parse_name(tokens, "input field")?);
+        if let Some(description) = description {
+            builder = builder.description(description);
+        }
+        expect(tokens, Token::Colon, "Expected colon")?;
+        builder = builder.type_(parse_type(tokens)?);
+        if next_is(tokens, &Token::Equals) {
+            builder = builder.default_value(parse_value(tokens, true)?);
+        }
+        ret.push(builder.build().unwrap());
+    }
+    if ret.is_empty() {
+        return Err(parse_error("Empty input fields").into());
+    }
+    Ok(ret)
+}
+
+fn parse_enum_values_definition<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+) -> ParseResult<Vec<EnumValue>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    expect(tokens, Token::LeftCurlyBracket, "Expected enum values")?;
+    let mut ret: Vec<EnumValue> = _d();
+    while !next_is(tokens, &Token::RightCurlyBracket) {
+        let description = parse_description(tokens)?;
+        let mut builder = EnumValueBuilder::default().name(parse_name(tokens, "enum value")?);
+        if let Some(description) = description {
+            builder = builder.description(description);
+        }
+        if let Some(deprecation_reason) = parse_deprecation_reason(tokens)? {
+            builder = builder.deprecation_reason(deprecation_reason);
+        }
+        ret.push(builder.build().unwrap());
+    }
+    if ret.is_empty() {
+        return Err(parse_error("Empty enum values").into());
+    }
+    Ok(ret)
+}
+
+/// `@deprecated` is the only directive that's supported in SDL
+fn parse_deprecation_reason<TIterator>(
+    tokens: &mut Peekable<TIterator>,
+) -> ParseResult<Option<String>>
+where
+    TIterator: Iterator<Item = LexResult<Token>>,
+{
+    if !next_is(tokens, &Token::AtSymbol) {
+        return Ok(None);
+    }
+    let mut ret: Option<String> = _d();
+    for directive in parse_directives(tokens, true)? {
+        if directive.name != "deprecated" {
+            return Err(
+                parse_error(&format!("Unsupported directive `@{}`", directive.name)).into(),
+            );
+        }
+        ret = Some(
+            match directive
+                .arguments
+                .unwrap_or_default()
+                .into_iter()
+                .find(|argument| argument.name == "reason")
+            {
+                None => DEFAULT_DEPRECATION_REASON.to_owned(),
+                Some(argument) => match argument.value {
+                    Value::String(reason) => reason,
+                    _ => return Err(parse_error("Expected deprecation reason string").into()),
+                },
+            },
+        );
+    }
+    Ok(ret)
+}
@@ -3,33 +3,48 @@ use std::collections::{HashMap, HashSet};
 use squalid::{OptionExt, _d};
 
 use crate::{
-    fields_in_progress_new, request, types, Argument, Directive, IndexMap, OperationType, Request,
-    ResponseInProgress, Schema, Selection, Value,
+    fields_in_progress_new, request, types, Argument, DependencyValue, Directive,
+    ExternalDependencyValues, IndexMap, OperationType, Request, ResponseInProgress, Schema,
+    Selection, Value,
 };
 
 pub struct QueryPlan<'a> {
+    pub operation_type: OperationType,
     field_plans: IndexMap<String, FieldPlan<'a>>,
 }
 
 impl<'a> QueryPlan<'a> {
     pub fn new(request: &'a Request, schema: &'a Schema) -> Self {
         let chosen_operation = request.chosen_operation();
-        assert_eq!(chosen_operation.operation_type, OperationType::Query);
+        let operation_type_name =
+            schema.type_name_for_operation_type(chosen_operation.operation_type);
 
         Self {
+            operation_type: chosen_operation.operation_type,
             field_plans: create_field_plans(
                 &chosen_operation.selection_set,
-                &[schema.query_type_name.clone()].into_iter().collect(),
+                &[operation_type_name.to_owned()].into_iter().collect(),
                 schema,
                 request,
             )
-            .remove(&schema.query_type_name)
+            .remove(operation_type_name)
             .unwrap(),
         }
     }
 
-    pub fn initial_response_in_progress(&self) -> ResponseInProgress<'_> {
-        ResponseInProgress::new(fields_in_progress_new(&self.field_plans, &_d()))
+    pub fn initial_response_in_progress(
+        &self,
+        external_dependency_values: &ExternalDependencyValues,
+    ) -> ResponseInProgress<'_> {
+        ResponseInProgress::new(fields_in_progress_new(
+            &self.field_plans,
+            external_dependency_values,
+        ))
+    }
+
+    pub fn subscription_field_plan(&self) -> Option<&FieldPlan<'a>> {
+        assert_eq!(self.operation_type, OperationType::Subscription);
+        self.field_plans.values().next()
     }
 }
 
@@ -37,7 +52,10 @@ pub struct FieldPlan<'a> {
     pub name: String,
     pub field_type: &'a types::Field,
     pub selection_set_by_type: Option<HashMap<String, IndexMap<String, FieldPlan<'a>>>>,
+    /// Any variables in the argument values get resolved (against
+    /// `variable_values`) when coercing them
     pub arguments: Option<IndexMap<String, Argument>>,
+    pub variable_values: &'a HashMap<String, DependencyValue>,
 }
 
 impl<'a> FieldPlan<'a> {
@@ -63,9 +81,21 @@ impl<'a> FieldPlan<'a> {
             arguments: request_field.arguments.as_ref().map(|arguments| {
                 arguments
                     .into_iter()
-                    .map(|argument| (argument.name.clone(), argument.clone()))
+                    // per spec, an argument whose variable wasn't provided
+                    // (and has no default value) is treated as absent
+                    .filter(|argument| match &argument.value {
+                        Value::Variable(name) => request.variable_values.contains_key(name),
+                        _ => true,
+                    })
+                    .map(|argument| {
+                        (
+                            argument.name.clone(),
+                            Argument::new(argument.name.clone(), argument.value.clone()),
+                        )
+                    })
                     .collect()
             }),
+            variable_values: &request.variable_values,
         }
     }
 }
@@ -80,12 +110,12 @@ fn create_field_plans<'a>(
         selection_set
             .iter()
             .filter(|selection| !match selection {
-                Selection::Field(field) => should_skip(&field.directives),
+                Selection::Field(field) => should_skip(&field.directives, request),
                 Selection::InlineFragment(inline_fragment) => {
-                    should_skip(&inline_fragment.directives)
+                    should_skip(&inline_fragment.directives, request)
                 }
                 Selection::FragmentSpread(fragment_spread) => {
-                    should_skip(&fragment_spread.directives)
+                    should_skip(&fragment_spread.directives, request)
                 }
             })
             .map(|selection| match selection {
@@ -137,22 +167,32 @@ fn create_field_plans<'a>(
     )
 }
 
-fn should_skip(directives: &[Directive]) -> bool {
+fn should_skip(directives: &[Directive], request: &Request) -> bool {
     if directives.into_iter().any(|directive| {
-        directive.name == "skip"
-            && directive.arguments.as_ref().unwrap()[0].value == Value::Bool(true)
+        directive.name == "skip" && directive_condition(directive, request) == Some(true)
     }) {
         return true;
     }
     if directives.into_iter().any(|directive| {
-        directive.name == "include"
-            && directive.arguments.as_ref().unwrap()[0].value == Value::Bool(false)
+        directive.name == "include" && directive_condition(directive, request) == Some(false)
     }) {
         return true;
     }
     false
 }
 
+/// The value of `@skip`/`@include`'s `if` argument
+fn directive_condition(directive: &Directive, request: &Request) -> Option<bool> {
+    match &directive.arguments.as_ref().unwrap()[0].value {
+        Value::Bool(value) => Some(*value),
+        Value::Variable(name) => match request.variable_values.get(name)? {
+            DependencyValue::Boolean(value) => Some(*value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn get_overlapping_fragment_types<'a>(
     all_current_concrete_type_names: &HashSet<String>,
     fragment_selection_set: &'a [Selection],
@@ -90,6 +90,15 @@ impl PositionsTracker {
             )));
     }
 
+    pub fn receive_variable_definition(&self) {
+        match self.document.borrow_mut().definitions.last_mut().unwrap() {
+            OperationOrFragment::Operation(operation) => {
+                operation.variable_definitions.push(self.last_token());
+            }
+            _ => unreachable!(),
+        }
+    }
+
     pub fn receive_fragment_definition(&self) {
         self.document
             .borrow_mut()
@@ -260,6 +269,21 @@ impl PositionsTracker {
             .location
     }
 
+    pub fn nth_operation_nth_variable_definition_location(
+        &self,
+        operation_index: usize,
+        index: usize,
+    ) -> Location {
+        self.document
+            .borrow()
+            .definitions
+            .iter()
+            .filter_map(|definition| definition.maybe_as_operation())
+            .nth(operation_index)
+            .unwrap()
+            .variable_definitions[index]
+    }
+
     pub fn nth_fragment_location(&self, index: usize) -> Location {
         self.document
             .borrow()
@@ -491,6 +515,12 @@ impl PositionsTracker {
         }
     }
 
+    pub fn emit_variable_definition() {
+        if let Some(positions_tracker) = Self::current() {
+            positions_tracker.receive_variable_definition();
+        }
+    }
+
     pub fn emit_fragment_definition() {
         if let Some(positions_tracker) = Self::current() {
             positions_tracker.receive_fragment_definition();
@@ -670,6 +700,7 @@ impl FindCurrentlyActiveSelectionSet for OperationOrFragment {
 #[derive(Debug)]
 struct Operation {
     pub location: Location,
+    pub variable_definitions: Vec<Location>,
     pub selection_set: SelectionSet,
     pub directives: Vec<Directive>,
 }
@@ -678,6 +709,7 @@ impl Operation {
     pub fn new(location: Location) -> Self {
         Self {
             location,
+            variable_definitions: _d(),
             selection_set: _d(),
             directives: _d(),
         }
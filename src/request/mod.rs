@@ -1,22 +1,41 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use derive_builder::Builder;
+use itertools::Itertools;
 use rkyv::{Archive, Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use squalid::_d;
 
-use crate::OperationType;
+use crate::{DependencyValue, OperationType, TypeFull};
 
 #[derive(Debug)]
 pub struct Request {
     pub document: Document,
+    /// Which of the document's operations to execute (only optional if the
+    /// document contains a single operation)
+    pub operation_name: Option<String>,
+    /// The chosen operation's coerced variable values (a variable that
+    /// wasn't provided and has no default value is absent)
+    pub variable_values: HashMap<String, DependencyValue>,
 }
 
 impl Request {
     pub fn new(document: Document) -> Self {
-        Self { document }
+        Self {
+            document,
+            operation_name: _d(),
+            variable_values: _d(),
+        }
+    }
+
+    pub fn maybe_chosen_operation(&self) -> Option<&OperationDefinition> {
+        self.document
+            .maybe_chosen_operation(self.operation_name.as_deref())
     }
 
     pub fn chosen_operation(&self) -> &OperationDefinition {
-        self.document.chosen_operation()
+        self.maybe_chosen_operation()
+            .expect("Couldn't choose operation")
     }
 
     pub fn fragment(&self, name: &str) -> &FragmentDefinition {
@@ -24,6 +43,20 @@ impl Request {
     }
 }
 
+/// The non-document parts of a GraphQL request (as eg sent alongside the
+/// `query` in GraphQL-over-HTTP)
+#[derive(Builder, Default)]
+#[builder(pattern = "owned")]
+pub struct RequestOptions {
+    #[builder(setter(into, strip_option), default)]
+    pub operation_name: Option<String>,
+    #[builder(default)]
+    pub variables: Map<String, JsonValue>,
+    /// Report selections of deprecated fields as `Response::warnings`
+    #[builder(default)]
+    pub warn_on_deprecated_fields: bool,
+}
+
 #[derive(Debug, Archive, Serialize, Deserialize)]
 pub struct Document {
     pub definitions: Vec<ExecutableDefinition>,
@@ -46,16 +79,24 @@ impl Document {
         }
     }
 
-    pub fn chosen_operation(&self) -> &OperationDefinition {
-        for definition in self.definitions.iter() {
-            match definition {
-                ExecutableDefinition::Operation(operation_definition) => {
-                    return operation_definition;
-                }
-                _ => continue,
-            }
+    pub fn operations(&self) -> impl Iterator<Item = &OperationDefinition> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| definition.maybe_as_operation_definition())
+    }
+
+    /// Per spec, the operation named `operation_name` (or else the lone
+    /// operation)
+    pub fn maybe_chosen_operation(
+        &self,
+        operation_name: Option<&str>,
+    ) -> Option<&OperationDefinition> {
+        match operation_name {
+            Some(operation_name) => self.operations().find(|operation_definition| {
+                operation_definition.name.as_deref() == Some(operation_name)
+            }),
+            None => self.operations().exactly_one().ok(),
         }
-        panic!()
     }
 
     pub fn fragment(&self, name: &str) -> &FragmentDefinition {
@@ -96,11 +137,30 @@ pub struct OperationDefinition {
     pub operation_type: OperationType,
     #[builder(setter(into), default)]
     pub name: Option<String>,
+    #[builder(default)]
+    pub variable_definitions: Vec<VariableDefinition>,
     pub selection_set: Vec<Selection>,
     #[builder(default)]
     pub directives: Vec<Directive>,
 }
 
+#[derive(Debug, Archive, Serialize, Deserialize)]
+pub struct VariableDefinition {
+    pub name: String,
+    pub type_: TypeFull,
+    pub default_value: Option<Value>,
+}
+
+impl VariableDefinition {
+    pub fn new(name: String, type_: TypeFull, default_value: Option<Value>) -> Self {
+        Self {
+            name,
+            type_,
+            default_value,
+        }
+    }
+}
+
 #[derive(Debug, Archive, Serialize, Deserialize)]
 pub struct FragmentDefinition {
     pub name: String,
@@ -230,12 +290,56 @@ impl Argument {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Archive, Serialize, Deserialize)]
+#[rkyv(serialize_bounds(
+    __S: rkyv::ser::Writer,
+    __S: rkyv::ser::Allocator,
+    <__S as rkyv::rancor::Fallible>::Error: rkyv::rancor::Source,
+))]
+#[rkyv(deserialize_bounds(
+    <__D as rkyv::rancor::Fallible>::Error: rkyv::rancor::Source,
+))]
+#[rkyv(bytecheck(
+    bounds(
+        __C: rkyv::validation::ArchiveContext,
+        <__C as rkyv::rancor::Fallible>::Error: rkyv::rancor::Source,
+    )
+))]
 pub enum Value {
     Int(i32),
+    Float(f64),
     String(String),
     Null,
     Bool(bool),
+    Enum(String),
+    List(#[rkyv(omit_bounds)] Vec<Value>),
+    /// (field name, value) pairs, in document order
+    Object(#[rkyv(omit_bounds)] Vec<(String, Value)>),
+    Variable(String),
+}
+
+/// Prints the value as a GraphQL input value literal
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value:?}"),
+            Self::String(value) => write!(f, "{}", JsonValue::String(value.clone())),
+            Self::Null => write!(f, "null"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Enum(value) => write!(f, "{value}"),
+            Self::List(values) => write!(f, "[{}]", values.iter().join(", ")),
+            Self::Object(fields) => write!(
+                f,
+                "{{{}}}",
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .join(", ")
+            ),
+            Self::Variable(name) => write!(f, "${name}"),
+        }
+    }
 }
 
 #[derive(Debug, Archive, Serialize, Deserialize)]
@@ -3,8 +3,8 @@ use std::collections::HashMap;
 use inflector::Inflector;
 
 use crate::{
-    ExternalDependency, ExternalDependencyValues, InternalDependency, InternalDependencyValues,
-    ResponseValue,
+    CustomScalar, DependencyValue, ExternalDependency, ExternalDependencyValues, FieldError,
+    FieldResult, InternalDependency, InternalDependencyValues, ResponseValue,
 };
 
 pub struct FieldResolver {
@@ -32,7 +32,14 @@ pub trait Carver {
         &self,
         external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> ResponseValue;
+    ) -> FieldResult<ResponseValue>;
+
+    /// The names of the (external or internal) dependencies that this
+    /// reads, which get checked against the field resolver's when the schema
+    /// gets constructed. Empty if not known up front
+    fn dependency_names(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 pub struct StringCarver {
@@ -50,16 +57,239 @@ impl Carver for StringCarver {
         &self,
         external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> ResponseValue {
-        ResponseValue::String(
-            internal_dependencies
-                .get(&self.name)
-                .or_else(|| external_dependencies.get(&self.name))
-                .unwrap()
-                .as_string()
-                .clone(),
+    ) -> FieldResult<ResponseValue> {
+        carve_nullable(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?,
+            |value| match value {
+                DependencyValue::String(value) => Ok(ResponseValue::String(value.clone())),
+                _ => Err(FieldError::new("Expected string")),
+            },
+        )
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+pub struct IntCarver {
+    pub name: String,
+}
+
+impl IntCarver {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl Carver for IntCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        carve_nullable(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?,
+            |value| {
+                Ok(ResponseValue::Int(match value {
+                    DependencyValue::Int(int) | DependencyValue::Id(int) => *int,
+                    _ => return Err(FieldError::new("Expected int")),
+                }))
+            },
         )
     }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+pub struct FloatCarver {
+    pub name: String,
+}
+
+impl FloatCarver {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl Carver for FloatCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        carve_nullable(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?,
+            |value| {
+                Ok(ResponseValue::Float(match value {
+                    DependencyValue::Float(float) => *float,
+                    DependencyValue::Int(int) | DependencyValue::Id(int) => f64::from(*int),
+                    _ => return Err(FieldError::new("Expected float")),
+                }))
+            },
+        )
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+pub struct BooleanCarver {
+    pub name: String,
+}
+
+impl BooleanCarver {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl Carver for BooleanCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        carve_nullable(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?,
+            |value| match value {
+                DependencyValue::Boolean(value) => Ok(ResponseValue::Boolean(*value)),
+                _ => Err(FieldError::new("Expected boolean")),
+            },
+        )
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+/// Per spec, `ID`s get serialized as strings
+pub struct IdCarver {
+    pub name: String,
+}
+
+impl IdCarver {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl Carver for IdCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        carve_nullable(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?,
+            |value| {
+                Ok(ResponseValue::String(match value {
+                    DependencyValue::Id(id) | DependencyValue::Int(id) => id.to_string(),
+                    DependencyValue::String(id) => id.clone(),
+                    _ => return Err(FieldError::new("Expected id")),
+                }))
+            },
+        )
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+/// Expects the (string) dependency value to be the name of one of the enum
+/// type's values (which gets checked against the field's enum type when
+/// executing)
+pub struct EnumCarver {
+    pub name: String,
+}
+
+impl EnumCarver {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl Carver for EnumCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        carve_nullable(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?,
+            |value| match value {
+                DependencyValue::String(value) => Ok(ResponseValue::EnumValue(value.clone())),
+                _ => Err(FieldError::new("Expected enum value")),
+            },
+        )
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+/// Serializes the dependency value via the custom scalar's
+/// `CustomScalar::serialize()`
+pub struct CustomScalarCarver {
+    pub name: String,
+    pub custom_scalar: Box<dyn CustomScalar>,
+}
+
+impl CustomScalarCarver {
+    pub fn new(name: String, custom_scalar: impl CustomScalar + 'static) -> Self {
+        Self {
+            name,
+            custom_scalar: Box::new(custom_scalar),
+        }
+    }
+}
+
+impl Carver for CustomScalarCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        carve_nullable(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?,
+            |value| self.custom_scalar.serialize(value).map_err(FieldError::new),
+        )
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+pub(crate) fn get_dependency_value<'a>(
+    name: &str,
+    external_dependencies: &'a ExternalDependencyValues,
+    internal_dependencies: &'a InternalDependencyValues,
+) -> FieldResult<&'a DependencyValue> {
+    internal_dependencies
+        .get(name)
+        .or_else(|| external_dependencies.get(name))
+        .ok_or_else(|| missing_dependency_error(name))
+}
+
+pub(crate) fn missing_dependency_error(name: &str) -> FieldError {
+    FieldError::new(format!("Missing dependency `{name}`"))
+}
+
+fn carve_nullable(
+    value: &DependencyValue,
+    carve: impl FnOnce(&DependencyValue) -> FieldResult<ResponseValue>,
+) -> FieldResult<ResponseValue> {
+    match value {
+        DependencyValue::Null => Ok(ResponseValue::Null),
+        value => carve(value),
+    }
 }
 
 pub enum CarverOrPopulator {
@@ -71,6 +301,31 @@ pub enum CarverOrPopulator {
         Box<dyn UnionOrInterfaceTypePopulatorList>,
         Box<dyn PopulatorList>,
     ),
+    OptionalPopulator(Box<dyn OptionalPopulator>),
+    OptionalPopulatorList(Box<dyn OptionalPopulatorList>),
+}
+
+impl CarverOrPopulator {
+    /// See `Carver::dependency_names()`
+    pub fn dependency_names(&self) -> Vec<String> {
+        match self {
+            Self::Carver(carver) => carver.dependency_names(),
+            Self::Populator(populator) => populator.dependency_names(),
+            Self::PopulatorList(populator) => populator.dependency_names(),
+            Self::UnionOrInterfaceTypePopulator(type_populator, populator) => type_populator
+                .dependency_names()
+                .into_iter()
+                .chain(populator.dependency_names())
+                .collect(),
+            Self::UnionOrInterfaceTypePopulatorList(type_populator, populator) => type_populator
+                .dependency_names()
+                .into_iter()
+                .chain(populator.dependency_names())
+                .collect(),
+            Self::OptionalPopulator(populator) => populator.dependency_names(),
+            Self::OptionalPopulatorList(populator) => populator.dependency_names(),
+        }
+    }
 }
 
 pub trait Populator {
@@ -78,7 +333,27 @@ pub trait Populator {
         &self,
         external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> ExternalDependencyValues;
+    ) -> FieldResult<ExternalDependencyValues>;
+
+    /// See `Carver::dependency_names()`
+    fn dependency_names(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// For nullable object-typed fields, where `None` resolves the field to
+/// `null`
+pub trait OptionalPopulator {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Option<ExternalDependencyValues>>;
+
+    /// See `Carver::dependency_names()`
+    fn dependency_names(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 pub struct ValuePopulator {
@@ -94,16 +369,20 @@ impl ValuePopulator {
 impl Populator for ValuePopulator {
     fn populate(
         &self,
-        _external_dependencies: &ExternalDependencyValues,
+        external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> ExternalDependencyValues {
+    ) -> FieldResult<ExternalDependencyValues> {
         let mut ret = ExternalDependencyValues::default();
         ret.insert(
             self.key.clone(),
-            internal_dependencies.get(&self.key).unwrap().clone(),
+            get_dependency_value(&self.key, external_dependencies, internal_dependencies)?.clone(),
         )
         .unwrap();
-        ret
+        Ok(ret)
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.key.clone()]
     }
 }
 
@@ -124,19 +403,23 @@ impl Populator for ValuesPopulator {
         &self,
         _external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> ExternalDependencyValues {
+    ) -> FieldResult<ExternalDependencyValues> {
         let mut ret = ExternalDependencyValues::default();
         for (internal_dependency_key, populated_key) in &self.keys {
             ret.insert(
                 populated_key.clone(),
                 internal_dependencies
                     .get(internal_dependency_key)
-                    .unwrap()
+                    .ok_or_else(|| missing_dependency_error(internal_dependency_key))?
                     .clone(),
             )
             .unwrap();
         }
-        ret
+        Ok(ret)
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        self.keys.keys().cloned().collect()
     }
 }
 
@@ -145,7 +428,27 @@ pub trait PopulatorList {
         &self,
         external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> Vec<ExternalDependencyValues>;
+    ) -> FieldResult<Vec<ExternalDependencyValues>>;
+
+    /// See `Carver::dependency_names()`
+    fn dependency_names(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// For nullable list-of-object-typed fields, where `None` resolves the field
+/// to `null`
+pub trait OptionalPopulatorList {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Option<Vec<ExternalDependencyValues>>>;
+
+    /// See `Carver::dependency_names()`
+    fn dependency_names(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 pub struct ValuePopulatorList {
@@ -163,10 +466,10 @@ impl PopulatorList for ValuePopulatorList {
         &self,
         _external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> Vec<ExternalDependencyValues> {
-        internal_dependencies
+    ) -> FieldResult<Vec<ExternalDependencyValues>> {
+        Ok(internal_dependencies
             .get(&self.singular.to_plural())
-            .unwrap()
+            .ok_or_else(|| missing_dependency_error(&self.singular.to_plural()))?
             .as_list()
             .into_iter()
             .map(|value| {
@@ -174,7 +477,11 @@ impl PopulatorList for ValuePopulatorList {
                 ret.insert(self.singular.clone(), value.clone()).unwrap();
                 ret
             })
-            .collect()
+            .collect())
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec![self.singular.to_plural()]
     }
 }
 
@@ -183,7 +490,12 @@ pub trait UnionOrInterfaceTypePopulator {
         &self,
         external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> String;
+    ) -> FieldResult<String>;
+
+    /// See `Carver::dependency_names()`
+    fn dependency_names(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 pub struct TypeDepluralizer {}
@@ -199,13 +511,17 @@ impl UnionOrInterfaceTypePopulator for TypeDepluralizer {
         &self,
         _external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> String {
-        internal_dependencies
+    ) -> FieldResult<String> {
+        Ok(internal_dependencies
             .get("type")
-            .unwrap()
+            .ok_or_else(|| missing_dependency_error("type"))?
             .as_string()
             .to_singular()
-            .to_pascal_case()
+            .to_pascal_case())
+    }
+
+    fn dependency_names(&self) -> Vec<String> {
+        vec!["type".to_owned()]
     }
 }
 
@@ -214,5 +530,10 @@ pub trait UnionOrInterfaceTypePopulatorList {
         &self,
         external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> Vec<String>;
+    ) -> FieldResult<Vec<String>>;
+
+    /// See `Carver::dependency_names()`
+    fn dependency_names(&self) -> Vec<String> {
+        vec![]
+    }
 }
@@ -1,4 +1,5 @@
-use serde::Serialize;
+use serde::{Serialize, Serializer};
+use serde_json::Value as JsonValue;
 use squalid::_d;
 
 use crate::{
@@ -11,32 +12,50 @@ pub struct Response {
     pub errors: Vec<ResponseError>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<ResponseValue>,
+    /// Non-fatal diagnostics (eg selections of deprecated fields), which get
+    /// serialized as `extensions.warnings`
+    #[serde(
+        rename = "extensions",
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_warnings"
+    )]
+    pub warnings: Vec<ResponseError>,
 }
 
 impl Response {
     pub fn new(data: Option<ResponseValue>, errors: Vec<ResponseError>) -> Self {
-        Self { data, errors }
+        Self {
+            data,
+            errors,
+            warnings: _d(),
+        }
     }
 }
 
 impl From<ResponseValue> for Response {
     fn from(value: ResponseValue) -> Self {
-        Self {
-            data: Some(value),
-            errors: _d(),
-        }
+        Self::new(Some(value), _d())
     }
 }
 
 impl From<Vec<ResponseError>> for Response {
     fn from(value: Vec<ResponseError>) -> Self {
-        Self {
-            data: _d(),
-            errors: value,
-        }
+        Self::new(_d(), value)
     }
 }
 
+fn serialize_warnings<S: Serializer>(
+    warnings: &[ResponseError],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Extensions<'a> {
+        warnings: &'a [ResponseError],
+    }
+
+    Extensions { warnings }.serialize(serializer)
+}
+
 #[derive(Serialize)]
 #[serde(untagged)]
 pub enum ResponseValue {
@@ -50,8 +69,38 @@ pub enum ResponseValue {
     EnumValue(String),
 }
 
+impl From<JsonValue> for ResponseValue {
+    fn from(value: JsonValue) -> Self {
+        match value {
+            JsonValue::Null => Self::Null,
+            JsonValue::Bool(value) => Self::Boolean(value),
+            JsonValue::Number(number) => number
+                .as_i64()
+                .and_then(|value| i32::try_from(value).ok())
+                .map(Self::Int)
+                .unwrap_or_else(|| Self::Float(number.as_f64().unwrap())),
+            JsonValue::String(value) => Self::String(value),
+            JsonValue::Array(values) => Self::List(values.into_iter().map(Into::into).collect()),
+            JsonValue::Object(fields) => Self::Map(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
 impl From<FieldsInProgress<'_>> for ResponseValue {
     fn from(fields_in_progress: FieldsInProgress) -> Self {
+        // per spec, a non-null field that resolved to `null` makes its
+        // enclosing object `null`
+        if fields_in_progress
+            .values()
+            .any(|field| matches!(field, ResponseValueOrInProgress::PropagatedNull))
+        {
+            return Self::Null;
+        }
         Self::Map(
             fields_in_progress
                 .into_iter()
@@ -113,6 +162,15 @@ pub enum ResponseValueOrInProgress<'a> {
     InProgress(InProgress<'a>),
     InProgressRecursing(InProgressRecursing<'a>),
     InProgressRecursingList(InProgressRecursingList<'a>),
+    /// A `null` in a non-null position, which gets propagated to the
+    /// enclosing object
+    PropagatedNull,
+}
+
+impl ResponseValueOrInProgress<'_> {
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::ResponseValue(_) | Self::PropagatedNull)
+    }
 }
 
 pub struct InProgress<'a> {
@@ -172,17 +230,40 @@ impl<'a> InProgressRecursingList<'a> {
     }
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct ResponseError {
     pub message: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<PathSegment>,
 }
 
 impl ResponseError {
     pub fn new(message: String, locations: Vec<Location>) -> Self {
-        Self { message, locations }
+        Self {
+            message,
+            locations,
+            path: _d(),
+        }
     }
+
+    pub fn new_with_path(message: String, path: Vec<PathSegment>) -> Self {
+        Self {
+            message,
+            locations: _d(),
+            path,
+        }
+    }
+}
+
+/// Per spec, an error's `path` is made up of response keys and (0-based)
+/// list indices
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
 }
 
 impl From<ValidationError> for ResponseError {
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::{
+    Argument, DependencyValue, FieldError, FieldResult, Id, InputObjectType, Param, ScalarType,
+    Schema, Type, TypeFull, Value,
+};
+
+/// Coerces a field's argument, falling back to the param's default value when
+/// the argument was omitted. `None` means there's no value for it at all
+pub fn coerce_field_argument(
+    arguments: Option<&IndexMap<String, Argument>>,
+    param: &Param,
+    schema: &Schema,
+    variable_values: &HashMap<String, DependencyValue>,
+) -> FieldResult<Option<DependencyValue>> {
+    arguments
+        .and_then(|arguments| arguments.get(&param.name))
+        .map(|argument| &argument.value)
+        // per spec, an argument whose variable wasn't provided is treated as
+        // omitted
+        .filter(|value| match value {
+            Value::Variable(name) => variable_values.contains_key(name),
+            _ => true,
+        })
+        .or(param.default_value.as_ref())
+        .map(|value| coerce_argument_value(value, &param.type_, schema, variable_values))
+        .transpose()
+}
+
+/// Coerces a (validated) argument value into the dependency value that
+/// resolvers see. Variables get replaced by their already-coerced values
+pub fn coerce_argument_value(
+    value: &Value,
+    type_: &TypeFull,
+    schema: &Schema,
+    variable_values: &HashMap<String, DependencyValue>,
+) -> FieldResult<DependencyValue> {
+    Ok(match (value, type_) {
+        // per spec, a variable that wasn't provided (and has no default
+        // value) is `null` here (ie within a list)
+        (Value::Variable(name), _) => variable_values
+            .get(name)
+            .cloned()
+            .unwrap_or(DependencyValue::Null),
+        (Value::Null, _) => DependencyValue::Null,
+        (value, TypeFull::NonNull(type_)) => {
+            coerce_argument_value(value, type_, schema, variable_values)?
+        }
+        (Value::List(values), TypeFull::List(item_type)) => DependencyValue::List(
+            values
+                .iter()
+                .map(|value| coerce_argument_value(value, item_type, schema, variable_values))
+                .collect::<FieldResult<_>>()?,
+        ),
+        // per spec, a non-list value gets coerced to a list of one
+        (value, TypeFull::List(item_type)) => DependencyValue::List(vec![coerce_argument_value(
+            value,
+            item_type,
+            schema,
+            variable_values,
+        )?]),
+        (value, TypeFull::Type(type_name)) => match schema.maybe_type(type_name) {
+            // literals were checked when validating too, but a custom
+            // scalar's `parse_value()` may still reject eg a default value
+            Some(Type::Scalar(ScalarType::Custom(custom_scalar))) => {
+                custom_scalar.parse_value(value).map_err(FieldError::new)?
+            }
+            Some(Type::InputObject(input_object_type)) => {
+                coerce_input_object_value(value, input_object_type, schema, variable_values)?
+            }
+            _ => coerce_scalar_value(value, type_name)?,
+        },
+    })
+}
+
+/// `ID`s are numeric row ids, so a string `ID` (which is also valid input per
+/// spec) has to parse as one
+pub fn coerce_id(value: &str) -> FieldResult<DependencyValue> {
+    value
+        .parse::<Id>()
+        .map(DependencyValue::Id)
+        .map_err(|_| FieldError::new(format!("Expected numeric ID, got `{value}`")))
+}
+
+fn coerce_input_object_value(
+    value: &Value,
+    input_object_type: &InputObjectType,
+    schema: &Schema,
+    variable_values: &HashMap<String, DependencyValue>,
+) -> FieldResult<DependencyValue> {
+    let Value::Object(fields) = value else {
+        unreachable!("validated as an object");
+    };
+    Ok(DependencyValue::Object(
+        input_object_type
+            .fields
+            .values()
+            .filter_map(|input_field| {
+                let value = fields
+                    .iter()
+                    .find(|(name, _)| *name == input_field.name)
+                    .map(|(_, value)| value)
+                    // per spec, a field whose variable wasn't provided is
+                    // treated as absent
+                    .filter(|value| match value {
+                        Value::Variable(name) => variable_values.contains_key(name),
+                        _ => true,
+                    })
+                    .or(input_field.default_value.as_ref())?;
+                Some(
+                    coerce_argument_value(value, &input_field.type_, schema, variable_values)
+                        .map(|value| (input_field.name.clone(), value)),
+                )
+            })
+            .collect::<FieldResult<_>>()?,
+    ))
+}
+
+fn coerce_scalar_value(value: &Value, type_name: &str) -> FieldResult<DependencyValue> {
+    Ok(match (value, type_name) {
+        // per spec, Int input values are accepted for Float
+        (Value::Int(value), "Float") => DependencyValue::Float(f64::from(*value)),
+        (Value::Int(value), "ID") => DependencyValue::Id(*value),
+        (Value::String(value), "ID") => coerce_id(value)?,
+        (Value::Int(value), _) => DependencyValue::Int(*value),
+        (Value::Float(value), _) => DependencyValue::Float(*value),
+        (Value::String(value) | Value::Enum(value), _) => DependencyValue::String(value.clone()),
+        (Value::Bool(value), _) => DependencyValue::Boolean(*value),
+        // ruled out when validating
+        (Value::List(_) | Value::Object(_) | Value::Null, _) => unreachable!(),
+        // resolved above
+        (Value::Variable(_), _) => unreachable!(),
+    })
+}
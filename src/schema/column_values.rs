@@ -0,0 +1,185 @@
+use std::collections::{HashMap, HashSet};
+
+use squalid::_d;
+
+use crate::{
+    ColumnGetter, DataSource, DependencyType, DependencyValue, FieldResult, FieldsInProgress,
+    HasManyViaForeignKey, Id, InternalDependencyResolver, ResponseValueOrInProgress,
+};
+
+type LoadedRow = HashMap<(String, DependencyType), DependencyValue>;
+
+/// Child ids by parent id
+type LoadedChildIds = HashMap<Id, Vec<Id>>;
+
+/// The column values requested by all of the `ColumnGetter`s (and the child
+/// ids requested by all of the `HasManyViaForeignKey`s) that are pending
+/// within a single `progress_fields()` tick, loaded with one query per table
+/// (or per foreign key) rather than one query per object per field
+#[derive(Default)]
+pub struct LoadedColumnValues {
+    // a failed query gets reported as a field error for each field that
+    // depended on it
+    rows_by_table: HashMap<String, FieldResult<HashMap<Id, LoadedRow>>>,
+    child_ids_by_foreign_key: HashMap<(String, String), FieldResult<LoadedChildIds>>,
+}
+
+#[derive(Default)]
+struct TableRequest {
+    columns: HashSet<(String, DependencyType)>,
+    row_ids: HashSet<Id>,
+}
+
+#[derive(Default)]
+struct Requests {
+    tables: HashMap<String, TableRequest>,
+    /// Parent ids by (table name, foreign key column name)
+    foreign_keys: HashMap<(String, String), HashSet<Id>>,
+}
+
+impl LoadedColumnValues {
+    pub async fn load(
+        fields_in_progress: &FieldsInProgress<'_>,
+        data_source: &dyn DataSource,
+    ) -> Self {
+        let mut requests: Requests = _d();
+        collect_requests(fields_in_progress, &mut requests);
+
+        let mut rows_by_table: HashMap<_, _> = _d();
+        for (table_name, request) in requests.tables {
+            let rows = load_rows(&table_name, request, data_source).await;
+            rows_by_table.insert(table_name, rows);
+        }
+        let mut child_ids_by_foreign_key: HashMap<_, _> = _d();
+        for ((table_name, foreign_key_column_name), parent_ids) in requests.foreign_keys {
+            let parent_ids = parent_ids.into_iter().collect::<Vec<_>>();
+            let child_ids = data_source
+                .get_ids_by_foreign_key(&table_name, &foreign_key_column_name, &parent_ids)
+                .await;
+            child_ids_by_foreign_key.insert((table_name, foreign_key_column_name), child_ids);
+        }
+        Self {
+            rows_by_table,
+            child_ids_by_foreign_key,
+        }
+    }
+
+    /// Resolves to `DependencyValue::Null` if the row doesn't exist or the
+    /// column is `NULL`
+    pub fn get(
+        &self,
+        column_getter: &ColumnGetter,
+        type_: DependencyType,
+        row_id: Id,
+    ) -> FieldResult<DependencyValue> {
+        let rows = self
+            .rows_by_table
+            .get(&column_getter.table_name)
+            .expect("Should've loaded every pending column getter's table")
+            .as_ref()
+            .map_err(Clone::clone)?;
+        Ok(rows
+            .get(&row_id)
+            .and_then(|row| row.get(&(column_getter.column_name.clone(), type_)))
+            .cloned()
+            .unwrap_or(DependencyValue::Null))
+    }
+
+    /// Resolves to a `DependencyValue::List` of ids
+    pub fn get_child_ids(
+        &self,
+        has_many_via_foreign_key: &HasManyViaForeignKey,
+        parent_id: Id,
+    ) -> FieldResult<DependencyValue> {
+        let child_ids = self
+            .child_ids_by_foreign_key
+            .get(&(
+                has_many_via_foreign_key.table_name.clone(),
+                has_many_via_foreign_key.foreign_key_column_name.clone(),
+            ))
+            .expect("Should've loaded every pending has-many's child ids")
+            .as_ref()
+            .map_err(Clone::clone)?;
+        Ok(DependencyValue::List(
+            child_ids
+                .get(&parent_id)
+                .into_iter()
+                .flatten()
+                .map(|child_id| DependencyValue::Id(*child_id))
+                .collect(),
+        ))
+    }
+}
+
+fn collect_requests(fields_in_progress: &FieldsInProgress<'_>, requests: &mut Requests) {
+    for field in fields_in_progress.values() {
+        match field {
+            ResponseValueOrInProgress::InProgress(in_progress) => {
+                let Some(DependencyValue::Id(row_id)) =
+                    in_progress.external_dependency_values.get("id")
+                else {
+                    continue;
+                };
+                for internal_dependency in &in_progress
+                    .field_plan
+                    .field_type
+                    .resolver
+                    .internal_dependencies
+                {
+                    match &internal_dependency.resolver {
+                        InternalDependencyResolver::ColumnGetter(column_getter) => {
+                            let request = requests
+                                .tables
+                                .entry(column_getter.table_name.clone())
+                                .or_default();
+                            request.columns.insert((
+                                column_getter.column_name.clone(),
+                                internal_dependency.type_,
+                            ));
+                            request.row_ids.insert(*row_id);
+                        }
+                        InternalDependencyResolver::HasManyViaForeignKey(
+                            has_many_via_foreign_key,
+                        ) => {
+                            requests
+                                .foreign_keys
+                                .entry((
+                                    has_many_via_foreign_key.table_name.clone(),
+                                    has_many_via_foreign_key.foreign_key_column_name.clone(),
+                                ))
+                                .or_default()
+                                .insert(*row_id);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            ResponseValueOrInProgress::InProgressRecursing(in_progress_recursing) => {
+                collect_requests(&in_progress_recursing.selection, requests);
+            }
+            ResponseValueOrInProgress::InProgressRecursingList(in_progress_recursing_list) => {
+                for selection in &in_progress_recursing_list.selections {
+                    collect_requests(selection, requests);
+                }
+            }
+            ResponseValueOrInProgress::ResponseValue(_)
+            | ResponseValueOrInProgress::PropagatedNull => {}
+        }
+    }
+}
+
+async fn load_rows(
+    table_name: &str,
+    request: TableRequest,
+    data_source: &dyn DataSource,
+) -> FieldResult<HashMap<Id, LoadedRow>> {
+    let columns = request.columns.into_iter().collect::<Vec<_>>();
+    let row_ids = request.row_ids.into_iter().collect::<Vec<_>>();
+    let rows = data_source
+        .get_column_values_by_id(table_name, &columns, &row_ids)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, values)| (id, columns.iter().cloned().zip(values).collect()))
+        .collect())
+}
@@ -0,0 +1,351 @@
+use itertools::Itertools;
+
+use crate::{
+    DependencyValue, ExternalDependencyValues, FieldInterface, FieldPlan, IndexMap, InputField,
+    InternalDependencyResolver, InternalDependencyValues, Param, Schema, Type,
+    TypeOrInterfaceField, TypeOrUnionOrInterface, Value, DEFAULT_DEPRECATION_REASON,
+};
+
+/// Resolves the `InternalDependencyResolver::Introspection*` variants. Type
+/// references (eg `[Actor!]!`) get passed around as their printed form and
+/// the objects backing `__Field`/`__InputValue`/etc get resolved eagerly as
+/// `DependencyValue::Object`s keyed by their external dependency names
+pub(super) fn resolve_introspection(
+    resolver: &InternalDependencyResolver,
+    field_plan: &FieldPlan<'_>,
+    external_dependency_values: &ExternalDependencyValues,
+    internal_dependency_values: &InternalDependencyValues,
+    schema: &Schema,
+) -> DependencyValue {
+    match resolver {
+        InternalDependencyResolver::IntrospectionSchema => introspection_schema(schema),
+        InternalDependencyResolver::IntrospectionTypeByName => {
+            let name = internal_dependency_values.get("name").unwrap().as_string();
+            match schema.maybe_type_or_union_or_interface(name) {
+                Some(_) => DependencyValue::String(name.clone()),
+                None => DependencyValue::Null,
+            }
+        }
+        resolver => {
+            let type_reference = external_dependency_values.get("type").unwrap().as_string();
+            let named_type = (!is_wrapping_type_reference(type_reference))
+                .then(|| schema.maybe_type_or_union_or_interface(type_reference))
+                .flatten();
+            match resolver {
+                InternalDependencyResolver::IntrospectionTypeKind => {
+                    match type_kind(type_reference, named_type) {
+                        Some(kind) => DependencyValue::String(kind.to_owned()),
+                        None => DependencyValue::Null,
+                    }
+                }
+                InternalDependencyResolver::IntrospectionTypeFields => {
+                    let fields: Vec<TypeOrInterfaceField<'_>> = match named_type {
+                        Some(TypeOrUnionOrInterface::Type(Type::Object(object_type))) => {
+                            object_type.fields.values().map(Into::into).collect()
+                        }
+                        Some(TypeOrUnionOrInterface::Interface(interface)) => {
+                            interface.fields.values().map(Into::into).collect()
+                        }
+                        _ => return DependencyValue::Null,
+                    };
+                    let include_deprecated = include_deprecated(field_plan);
+                    DependencyValue::List(
+                        fields
+                            .into_iter()
+                            .filter(|field| {
+                                include_deprecated || field.deprecation_reason().is_none()
+                            })
+                            .map(introspection_field)
+                            .collect(),
+                    )
+                }
+                InternalDependencyResolver::IntrospectionTypeDescription => {
+                    optional_string(match named_type {
+                        Some(TypeOrUnionOrInterface::Type(Type::Object(object_type))) => {
+                            object_type.description.as_deref()
+                        }
+                        Some(TypeOrUnionOrInterface::Type(Type::Enum(enum_type))) => {
+                            enum_type.description.as_deref()
+                        }
+                        Some(TypeOrUnionOrInterface::Type(Type::InputObject(
+                            input_object_type,
+                        ))) => input_object_type.description.as_deref(),
+                        Some(TypeOrUnionOrInterface::Union(union)) => union.description.as_deref(),
+                        Some(TypeOrUnionOrInterface::Interface(interface)) => {
+                            interface.description.as_deref()
+                        }
+                        _ => None,
+                    })
+                }
+                InternalDependencyResolver::IntrospectionTypeInterfaces => match named_type {
+                    Some(TypeOrUnionOrInterface::Type(Type::Object(object_type))) => {
+                        string_list(&object_type.implements)
+                    }
+                    Some(TypeOrUnionOrInterface::Interface(interface)) => {
+                        string_list(&interface.implements)
+                    }
+                    _ => DependencyValue::Null,
+                },
+                InternalDependencyResolver::IntrospectionTypePossibleTypes => match named_type {
+                    Some(TypeOrUnionOrInterface::Union(union)) => string_list(&union.types),
+                    Some(TypeOrUnionOrInterface::Interface(interface)) => string_list(
+                        schema.interface_all_concrete_types[&interface.name]
+                            .iter()
+                            .sorted(),
+                    ),
+                    _ => DependencyValue::Null,
+                },
+                InternalDependencyResolver::IntrospectionTypeEnumValues => match named_type {
+                    Some(TypeOrUnionOrInterface::Type(Type::Enum(enum_type))) => {
+                        let include_deprecated = include_deprecated(field_plan);
+                        DependencyValue::List(
+                            enum_type
+                                .values
+                                .values()
+                                .filter(|value| include_deprecated || !value.is_deprecated())
+                                .map(|value| {
+                                    object([
+                                        ("name", DependencyValue::String(value.name.clone())),
+                                        (
+                                            "description",
+                                            optional_string(value.description.as_deref()),
+                                        ),
+                                        (
+                                            "is_deprecated",
+                                            DependencyValue::Boolean(value.is_deprecated()),
+                                        ),
+                                        (
+                                            "deprecation_reason",
+                                            optional_string(value.deprecation_reason.as_deref()),
+                                        ),
+                                    ])
+                                })
+                                .collect(),
+                        )
+                    }
+                    _ => DependencyValue::Null,
+                },
+                InternalDependencyResolver::IntrospectionTypeInputFields => match named_type {
+                    Some(TypeOrUnionOrInterface::Type(Type::InputObject(input_object_type))) => {
+                        DependencyValue::List(
+                            input_object_type
+                                .fields
+                                .values()
+                                .map(introspection_input_field)
+                                .collect(),
+                        )
+                    }
+                    _ => DependencyValue::Null,
+                },
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+fn introspection_schema(schema: &Schema) -> DependencyValue {
+    object([
+        ("description", DependencyValue::Null),
+        (
+            "types",
+            string_list(
+                schema
+                    .types
+                    .keys()
+                    .chain(schema.builtin_types.keys())
+                    .chain(schema.unions.keys())
+                    .chain(schema.interfaces.keys())
+                    .sorted(),
+            ),
+        ),
+        (
+            "query_type",
+            DependencyValue::String(schema.query_type_name.clone()),
+        ),
+        (
+            "mutation_type",
+            optional_string(schema.mutation_type_name.as_deref()),
+        ),
+        (
+            "subscription_type",
+            optional_string(schema.subscription_type_name.as_deref()),
+        ),
+        ("directives", introspection_directives()),
+    ])
+}
+
+fn introspection_directives() -> DependencyValue {
+    DependencyValue::List(vec![
+        introspection_directive(
+            "skip",
+            "Directs the executor to skip this field or fragment when the `if` argument is true.",
+            &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+            introspection_input_value("if", Some("Skipped when true."), "Boolean!", None, None),
+        ),
+        introspection_directive(
+            "include",
+            "Directs the executor to include this field or fragment only when the `if` argument is true.",
+            &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+            introspection_input_value("if", Some("Included when true."), "Boolean!", None, None),
+        ),
+        introspection_directive(
+            "deprecated",
+            "Marks an element of a GraphQL schema as no longer supported.",
+            &[
+                "FIELD_DEFINITION",
+                "ARGUMENT_DEFINITION",
+                "INPUT_FIELD_DEFINITION",
+                "ENUM_VALUE",
+            ],
+            introspection_input_value(
+                "reason",
+                Some("Explains why this element was deprecated."),
+                "String",
+                Some(&Value::String(DEFAULT_DEPRECATION_REASON.to_owned())),
+                None,
+            ),
+        ),
+    ])
+}
+
+fn introspection_directive(
+    name: &str,
+    description: &str,
+    locations: &[&str],
+    arg: DependencyValue,
+) -> DependencyValue {
+    object([
+        ("name", DependencyValue::String(name.to_owned())),
+        (
+            "description",
+            DependencyValue::String(description.to_owned()),
+        ),
+        ("locations", string_list(locations)),
+        ("args", DependencyValue::List(vec![arg])),
+        ("is_repeatable", DependencyValue::Boolean(false)),
+    ])
+}
+
+fn introspection_field(field: TypeOrInterfaceField<'_>) -> DependencyValue {
+    object([
+        ("name", DependencyValue::String(field.name().to_owned())),
+        ("description", optional_string(field.description())),
+        (
+            "args",
+            DependencyValue::List(field.params().values().map(introspection_param).collect()),
+        ),
+        ("type", DependencyValue::String(field.type_().to_string())),
+        (
+            "is_deprecated",
+            DependencyValue::Boolean(field.deprecation_reason().is_some()),
+        ),
+        (
+            "deprecation_reason",
+            optional_string(field.deprecation_reason()),
+        ),
+    ])
+}
+
+fn introspection_param(param: &Param) -> DependencyValue {
+    introspection_input_value(
+        &param.name,
+        param.description.as_deref(),
+        &param.type_.to_string(),
+        param.default_value.as_ref(),
+        param.deprecation_reason.as_deref(),
+    )
+}
+
+fn introspection_input_field(input_field: &InputField) -> DependencyValue {
+    introspection_input_value(
+        &input_field.name,
+        input_field.description.as_deref(),
+        &input_field.type_.to_string(),
+        input_field.default_value.as_ref(),
+        None,
+    )
+}
+
+fn introspection_input_value(
+    name: &str,
+    description: Option<&str>,
+    type_reference: &str,
+    default_value: Option<&Value>,
+    deprecation_reason: Option<&str>,
+) -> DependencyValue {
+    object([
+        ("name", DependencyValue::String(name.to_owned())),
+        ("description", optional_string(description)),
+        ("type", DependencyValue::String(type_reference.to_owned())),
+        (
+            "default_value",
+            optional_string(default_value.map(ToString::to_string).as_deref()),
+        ),
+        (
+            "is_deprecated",
+            DependencyValue::Boolean(deprecation_reason.is_some()),
+        ),
+        ("deprecation_reason", optional_string(deprecation_reason)),
+    ])
+}
+
+fn type_kind(
+    type_reference: &str,
+    named_type: Option<TypeOrUnionOrInterface<'_>>,
+) -> Option<&'static str> {
+    if type_reference.ends_with('!') {
+        return Some("NON_NULL");
+    }
+    if type_reference.starts_with('[') {
+        return Some("LIST");
+    }
+    Some(match named_type? {
+        TypeOrUnionOrInterface::Type(Type::Object(_)) => "OBJECT",
+        TypeOrUnionOrInterface::Type(Type::Scalar(_)) => "SCALAR",
+        TypeOrUnionOrInterface::Type(Type::Enum(_)) => "ENUM",
+        TypeOrUnionOrInterface::Type(Type::InputObject(_)) => "INPUT_OBJECT",
+        TypeOrUnionOrInterface::Union(_) => "UNION",
+        TypeOrUnionOrInterface::Interface(_) => "INTERFACE",
+    })
+}
+
+fn is_wrapping_type_reference(type_reference: &str) -> bool {
+    type_reference.ends_with('!') || type_reference.starts_with('[')
+}
+
+fn include_deprecated(field_plan: &FieldPlan<'_>) -> bool {
+    field_plan
+        .arguments
+        .as_ref()
+        .and_then(|arguments| arguments.get("includeDeprecated"))
+        .is_some_and(|argument| match &argument.value {
+            Value::Variable(name) => {
+                field_plan.variable_values.get(name) == Some(&DependencyValue::Boolean(true))
+            }
+            value => *value == Value::Bool(true),
+        })
+}
+
+fn object<'a>(fields: impl IntoIterator<Item = (&'a str, DependencyValue)>) -> DependencyValue {
+    DependencyValue::Object(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value))
+            .collect::<IndexMap<_, _>>(),
+    )
+}
+
+fn string_list(values: impl IntoIterator<Item = impl AsRef<str>>) -> DependencyValue {
+    DependencyValue::List(
+        values
+            .into_iter()
+            .map(|value| DependencyValue::String(value.as_ref().to_owned()))
+            .collect(),
+    )
+}
+
+fn optional_string(value: Option<&str>) -> DependencyValue {
+    value.map_or(DependencyValue::Null, |value| {
+        DependencyValue::String(value.to_owned())
+    })
+}
@@ -2,26 +2,46 @@ use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
 use std::sync::RwLock;
 
+use futures::{
+    future,
+    stream::{self, LocalBoxStream, StreamExt},
+};
 use rkyv::{rancor, util::AlignedVec};
-use sqlx::{Pool, Postgres};
 use squalid::{OptionExt, _d};
 
 use crate::{
-    builtin_types, fields_in_progress_new, get_hash, parse, CarverOrPopulator, DependencyType,
-    DependencyValue, Document, DummyUnionTypenameField, Error, ExternalDependencyValues, FieldPlan,
-    FieldsInProgress, Id, InProgress, InProgressRecursing, InProgressRecursingList, IndexMap,
-    Interface, InternalDependencyResolver, InternalDependencyValues, OperationType, Populator,
-    PositionsTracker, QueryPlan, Request, Response, ResponseValue, ResponseValueOrInProgress,
-    Result as SauvignonResult, Type, TypeInterface, Union, Value,
+    builtin_types, fields_in_progress_new, get_hash, parse, CarverOrPopulator,
+    ColumnGetterListOrderBy, ColumnGetterListValue, CustomScalar, DataSource, DependencyType,
+    DependencyValue, Document, DummyUnionTypenameField, EnumType, Error, ExternalDependencyValues,
+    FieldError, FieldPlan, FieldResult, FieldsInProgress, Id, InProgress, InProgressRecursing,
+    InProgressRecursingList, IndexMap, Interface, InternalDependencyResolver,
+    InternalDependencyValues, ListQuery, OperationType, PathSegment, Populator, PositionsTracker,
+    QueryPlan, Request, RequestOptions, Response, ResponseError, ResponseValue,
+    ResponseValueOrInProgress, Type, TypeFull, TypeInterface, Union,
 };
 
+mod arguments;
+mod column_values;
+mod introspection;
+mod sdl;
+mod type_system;
 mod validation;
+mod variables;
+use crate::data_source::is_valid_sql_identifier;
+use arguments::coerce_field_argument;
+use column_values::LoadedColumnValues;
+use introspection::resolve_introspection;
+pub use sdl::SdlResolvers;
+use type_system::validate_type_system;
 pub use validation::ValidationError;
 use validation::ValidationRequestOrErrors;
+use variables::coerce_variable_values;
 
 pub struct Schema {
     pub types: HashMap<String, Type>,
     pub query_type_name: String,
+    pub mutation_type_name: Option<String>,
+    pub subscription_type_name: Option<String>,
     builtin_types: HashMap<String, Type>,
     pub unions: HashMap<String, Union>,
     pub interfaces: HashMap<String, Interface>,
@@ -31,16 +51,49 @@ pub struct Schema {
 }
 
 impl Schema {
+    /// Reports every problem found with the schema (rather than only the
+    /// first one)
     pub fn try_new(
         types: Vec<Type>,
         unions: Vec<Union>,
         interfaces: Vec<Interface>,
-    ) -> SauvignonResult<Self> {
-        let query_type_index = types
+    ) -> Result<Self, Vec<Error>> {
+        let builtin_types = builtin_types();
+        let mut errors: Vec<Error> = _d();
+        let query_type_name = types
+            .iter()
+            .find(|type_| type_.is_query_type())
+            .map(|type_| type_.name().to_owned());
+        if query_type_name.is_none() {
+            errors.push(Error::NoQueryTypeSpecified);
+        }
+        errors.extend(
+            types
+                .iter()
+                .filter_map(|type_| type_.maybe_as_object())
+                .flat_map(|object_type| object_type.fields.values())
+                .flat_map(|field| &field.resolver.internal_dependencies)
+                .flat_map(|internal_dependency| internal_dependency.resolver.sql_identifiers())
+                .filter(|identifier| !is_valid_sql_identifier(identifier))
+                .map(|identifier| Error::InvalidSqlIdentifier(identifier.to_owned())),
+        );
+        errors.extend(validate_type_system(
+            &types,
+            &unions,
+            &interfaces,
+            &builtin_types,
+        ));
+        let Some(query_type_name) = query_type_name.filter(|_| errors.is_empty()) else {
+            return Err(errors);
+        };
+        let mutation_type_name = types
+            .iter()
+            .find(|type_| type_.is_mutation_type())
+            .map(|type_| type_.name().to_owned());
+        let subscription_type_name = types
             .iter()
-            .position(|type_| type_.is_query_type())
-            .ok_or_else(|| Error::NoQueryTypeSpecified)?;
-        let query_type_name = types[query_type_index].name().to_owned();
+            .find(|type_| type_.is_subscription_type())
+            .map(|type_| type_.name().to_owned());
 
         let interface_all_concrete_types = interfaces
             .iter()
@@ -71,7 +124,9 @@ impl Schema {
                 .map(|type_| (type_.name().to_owned(), type_))
                 .collect(),
             query_type_name,
-            builtin_types: builtin_types(),
+            mutation_type_name,
+            subscription_type_name,
+            builtin_types,
             unions: unions
                 .into_iter()
                 .map(|union| (union.name.clone(), union))
@@ -86,7 +141,122 @@ impl Schema {
         })
     }
 
-    pub async fn request(&self, document_str: &str, db_pool: &Pool<Postgres>) -> Response {
+    pub async fn request(&self, document_str: &str, data_source: &dyn DataSource) -> Response {
+        self.request_with_options(document_str, &_d(), data_source)
+            .await
+    }
+
+    pub async fn request_with_options(
+        &self,
+        document_str: &str,
+        options: &RequestOptions,
+        data_source: &dyn DataSource,
+    ) -> Response {
+        let request = match self.prepare_request(document_str, options) {
+            Ok(request) => request,
+            Err(response) => return response,
+        };
+        if request.chosen_operation().operation_type == OperationType::Subscription {
+            return vec![ResponseError::new(
+                "Subscriptions must be executed via `Schema::subscribe()`".to_owned(),
+                _d(),
+            )]
+            .into();
+        }
+        let mut response = compute_response(self, &request, &_d(), data_source).await;
+        if options.warn_on_deprecated_fields {
+            response.warnings = self.deprecated_field_warnings(document_str, &request);
+        }
+        response
+    }
+
+    /// Yields a response (computed by running the subscription root field's
+    /// selection set) for each event emitted by that field's
+    /// `SubscriptionSource`
+    pub fn subscribe<'a>(
+        &'a self,
+        document_str: &str,
+        data_source: &'a dyn DataSource,
+    ) -> LocalBoxStream<'a, Response> {
+        self.subscribe_with_options(document_str, &_d(), data_source)
+    }
+
+    pub fn subscribe_with_options<'a>(
+        &'a self,
+        document_str: &str,
+        options: &RequestOptions,
+        data_source: &'a dyn DataSource,
+    ) -> LocalBoxStream<'a, Response> {
+        let request = match self.prepare_request(document_str, options) {
+            Ok(request) => request,
+            Err(response) => return stream::once(future::ready(response)).boxed_local(),
+        };
+        if request.chosen_operation().operation_type != OperationType::Subscription {
+            return stream::once(future::ready(
+                vec![ResponseError::new(
+                    "Expected subscription operation".to_owned(),
+                    _d(),
+                )]
+                .into(),
+            ))
+            .boxed_local();
+        }
+        let events = {
+            let query_plan = QueryPlan::new(&request, self);
+            match query_plan.subscription_field_plan() {
+                Some(field_plan) => field_plan
+                    .field_type
+                    .subscription_source
+                    .as_ref()
+                    .expect_else(|| {
+                        format!("No subscription source for field `{}`", field_plan.name)
+                    })
+                    .subscribe(),
+                // the root field was eg `@skip`'d
+                None => return stream::empty().boxed_local(),
+            }
+        };
+        let warnings = if options.warn_on_deprecated_fields {
+            self.deprecated_field_warnings(document_str, &request)
+        } else {
+            _d()
+        };
+        stream::unfold((events, request), move |(mut events, request)| {
+            let warnings = warnings.clone();
+            async move {
+                let event = events.next().await?;
+                let mut response = compute_response(self, &request, &event, data_source).await;
+                response.warnings = warnings;
+                Some((response, (events, request)))
+            }
+        })
+        .boxed_local()
+    }
+
+    fn deprecated_field_warnings(
+        &self,
+        document_str: &str,
+        request: &Request,
+    ) -> Vec<ResponseError> {
+        if self.validate_deprecated_fields(request).is_empty() {
+            return _d();
+        }
+        illicit::Layer::new()
+            .offer(PositionsTracker::default())
+            .enter(|| {
+                let request = parse(document_str.chars()).unwrap();
+                self.validate_deprecated_fields(&request)
+            })
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    fn prepare_request(
+        &self,
+        document_str: &str,
+        options: &RequestOptions,
+    ) -> Result<Request, Response> {
         let document_str_hash = get_hash(document_str);
         let cached_validated_document = self
             .cached_validated_documents
@@ -97,7 +267,7 @@ impl Schema {
                 rkyv::from_bytes_unchecked::<Document, rancor::Error>(cached_validated_document)
                     .unwrap()
             });
-        let request = match cached_validated_document {
+        let mut request = match cached_validated_document {
             Some(cached_validated_document) => Request::new(cached_validated_document),
             None => {
                 let request = match parse(document_str.chars()) {
@@ -106,7 +276,7 @@ impl Schema {
                         let parse_error = illicit::Layer::new()
                             .offer(PositionsTracker::default())
                             .enter(|| parse(document_str.chars()).unwrap_err());
-                        return vec![parse_error.into()].into();
+                        return Err(vec![parse_error.into()].into());
                     }
                 };
                 let validation_request_or_errors = self.validate(&request);
@@ -118,11 +288,11 @@ impl Schema {
                             self.validate(&request).into_errors()
                         });
                     assert!(!validation_errors.is_empty());
-                    return validation_errors
+                    return Err(validation_errors
                         .into_iter()
                         .map(Into::into)
                         .collect::<Vec<_>>()
-                        .into();
+                        .into());
                 }
                 self.cached_validated_documents.write().unwrap().insert(
                     document_str_hash,
@@ -131,20 +301,47 @@ impl Schema {
                 request
             }
         };
-        compute_response(self, &request, db_pool).await.into()
+        request.operation_name = options.operation_name.clone();
+        let Some(chosen_operation) = request.maybe_chosen_operation() else {
+            return Err(vec![ResponseError::new(
+                match options.operation_name.as_ref() {
+                    Some(operation_name) => format!("Unknown operation: `{operation_name}`"),
+                    None if request.document.operations().next().is_none() => {
+                        "Document doesn't contain any operations".to_owned()
+                    }
+                    None => "Must provide operation name if document contains multiple operations"
+                        .to_owned(),
+                },
+                _d(),
+            )]
+            .into());
+        };
+        request.variable_values =
+            coerce_variable_values(chosen_operation, &options.variables, self)
+                .map_err(Response::from)?;
+        Ok(request)
     }
 
     pub fn query_type(&self) -> &Type {
         &self.types[&self.query_type_name]
     }
 
-    pub fn type_name_for_operation_type(&self, operation_type: OperationType) -> &str {
+    pub fn maybe_type_name_for_operation_type(
+        &self,
+        operation_type: OperationType,
+    ) -> Option<&str> {
         match operation_type {
-            OperationType::Query => &self.query_type_name,
-            _ => unimplemented!(),
+            OperationType::Query => Some(&self.query_type_name),
+            OperationType::Mutation => self.mutation_type_name.as_deref(),
+            OperationType::Subscription => self.subscription_type_name.as_deref(),
         }
     }
 
+    pub fn type_name_for_operation_type(&self, operation_type: OperationType) -> &str {
+        self.maybe_type_name_for_operation_type(operation_type)
+            .expect_else(|| format!("No type for operation type: {operation_type:?}"))
+    }
+
     pub fn maybe_type(&self, name: &str) -> Option<&Type> {
         self.types
             .get(name)
@@ -155,6 +352,11 @@ impl Schema {
         self.maybe_type(name).unwrap()
     }
 
+    pub fn maybe_custom_scalar(&self, name: &str) -> Option<&dyn CustomScalar> {
+        self.maybe_type(name)
+            .and_then(|type_| type_.maybe_as_custom_scalar())
+    }
+
     pub fn maybe_type_or_union_or_interface<'a>(
         &'a self,
         name: &str,
@@ -200,314 +402,573 @@ impl Schema {
 async fn compute_response(
     schema: &Schema,
     request: &Request,
-    db_pool: &Pool<Postgres>,
-) -> ResponseValue {
+    external_dependency_values: &ExternalDependencyValues,
+    data_source: &dyn DataSource,
+) -> Response {
     let query_plan = QueryPlan::new(&request, schema);
-    let response_in_progress = query_plan.initial_response_in_progress();
-    let mut fields_in_progress = response_in_progress.fields;
+    let response_in_progress = query_plan.initial_response_in_progress(external_dependency_values);
+    let mut errors: Vec<ResponseError> = _d();
+    let data: ResponseValue = match query_plan.operation_type {
+        // per spec, top-level mutation fields get executed serially (in
+        // document order)
+        OperationType::Mutation => {
+            let mut fields_done: FieldsInProgress = _d();
+            for (field_name, field_in_progress) in response_in_progress.fields {
+                fields_done.extend(
+                    progress_fields_until_done(
+                        [(field_name, field_in_progress)].into_iter().collect(),
+                        data_source,
+                        schema,
+                        &mut errors,
+                    )
+                    .await,
+                );
+            }
+            fields_done.into()
+        }
+        _ => progress_fields_until_done(
+            response_in_progress.fields,
+            data_source,
+            schema,
+            &mut errors,
+        )
+        .await
+        .into(),
+    };
+    Response::new(Some(data), errors)
+}
+
+async fn progress_fields_until_done<'a>(
+    fields_in_progress: FieldsInProgress<'a>,
+    data_source: &'a dyn DataSource,
+    schema: &'a Schema,
+    errors: &mut Vec<ResponseError>,
+) -> FieldsInProgress<'a> {
+    let mut fields_in_progress = fields_in_progress;
     loop {
-        let ret = progress_fields(fields_in_progress, db_pool, schema).await;
+        // every pending `ColumnGetter` of this tick gets loaded up front
+        let column_values = LoadedColumnValues::load(&fields_in_progress, data_source).await;
+        let ret = progress_fields(
+            fields_in_progress,
+            data_source,
+            schema,
+            &column_values,
+            &[],
+            errors,
+        )
+        .await;
         let is_done = ret.0;
         fields_in_progress = ret.1;
         if is_done {
-            return fields_in_progress.into();
+            return fields_in_progress;
         }
     }
 }
 
-fn progress_fields<'a>(
+fn progress_fields<'a: 'b, 'b>(
     fields_in_progress: FieldsInProgress<'a>,
-    db_pool: &'a Pool<Postgres>,
+    data_source: &'a dyn DataSource,
     schema: &'a Schema,
-) -> Pin<Box<dyn Future<Output = (bool, FieldsInProgress<'a>)> + 'a>> {
+    column_values: &'b LoadedColumnValues,
+    path: &'b [PathSegment],
+    errors: &'b mut Vec<ResponseError>,
+) -> Pin<Box<dyn Future<Output = (bool, FieldsInProgress<'a>)> + 'b>> {
     Box::pin(async move {
         let is_done = fields_in_progress
             .values()
-            .all(|field| matches!(field, ResponseValueOrInProgress::ResponseValue(_)));
+            .all(ResponseValueOrInProgress::is_done);
         if is_done {
             return (true, fields_in_progress);
         }
 
         let mut progressed = IndexMap::new();
         for (field_name, response_value_or_in_progress) in fields_in_progress {
-            progressed.insert(
-                field_name,
-                match response_value_or_in_progress {
-                    ResponseValueOrInProgress::ResponseValue(response_value) => {
-                        ResponseValueOrInProgress::ResponseValue(response_value)
+            let field_path = [path, &[PathSegment::Field(field_name.clone())]].concat();
+            let progressed_field = match response_value_or_in_progress {
+                ResponseValueOrInProgress::ResponseValue(response_value) => {
+                    ResponseValueOrInProgress::ResponseValue(response_value)
+                }
+                ResponseValueOrInProgress::PropagatedNull => {
+                    ResponseValueOrInProgress::PropagatedNull
+                }
+                ResponseValueOrInProgress::InProgress(InProgress {
+                    field_plan,
+                    external_dependency_values,
+                }) => match start_field(
+                    field_plan,
+                    &external_dependency_values,
+                    data_source,
+                    schema,
+                    column_values,
+                )
+                .await
+                {
+                    Ok(ResponseValueOrInProgress::ResponseValue(response_value)) => {
+                        let mut has_null_violation = false;
+                        let completed = complete_value(
+                            response_value,
+                            &field_plan.field_type.type_,
+                            &mut has_null_violation,
+                        );
+                        if has_null_violation {
+                            errors.push(ResponseError::new_with_path(
+                                format!(
+                                    "Cannot return null for non-nullable field `{}`",
+                                    field_plan.name
+                                ),
+                                field_path,
+                            ));
+                        }
+                        to_completed(completed)
                     }
-                    ResponseValueOrInProgress::InProgress(InProgress {
-                        field_plan,
-                        external_dependency_values,
-                    }) => {
-                        let internal_dependency_values = populate_internal_dependencies(
+                    Ok(response_value_or_in_progress) => response_value_or_in_progress,
+                    // per spec, a field error makes the field `null` (which
+                    // may then propagate)
+                    Err(error) => {
+                        errors.push(ResponseError::new_with_path(error.message, field_path));
+                        to_completed(complete_value(
+                            ResponseValue::Null,
+                            &field_plan.field_type.type_,
+                            &mut false,
+                        ))
+                    }
+                },
+                ResponseValueOrInProgress::InProgressRecursing(InProgressRecursing {
+                    field_plan,
+                    populated,
+                    selection,
+                }) => {
+                    let (is_done, fields_in_progress) = progress_fields(
+                        selection,
+                        data_source,
+                        schema,
+                        column_values,
+                        &field_path,
+                        errors,
+                    )
+                    .await;
+
+                    if is_done {
+                        // any null violation got reported by the field where
+                        // it happened
+                        to_completed(complete_value(
+                            fields_in_progress.into(),
+                            &field_plan.field_type.type_,
+                            &mut false,
+                        ))
+                    } else {
+                        ResponseValueOrInProgress::InProgressRecursing(InProgressRecursing {
                             field_plan,
-                            &external_dependency_values,
-                            db_pool,
+                            populated,
+                            selection: fields_in_progress,
+                        })
+                    }
+                }
+                ResponseValueOrInProgress::InProgressRecursingList(InProgressRecursingList {
+                    field_plan,
+                    populated,
+                    selections,
+                }) => {
+                    let mut progressed = vec![];
+                    let mut are_all_done = true;
+                    for (index, selection) in selections.into_iter().enumerate() {
+                        let item_path = [&field_path[..], &[PathSegment::Index(index)]].concat();
+                        let (is_done, fields_in_progress) = progress_fields(
+                            selection,
+                            data_source,
                             schema,
+                            column_values,
+                            &item_path,
+                            errors,
                         )
                         .await;
-                        match &field_plan.field_type.resolver.carver_or_populator {
-                            CarverOrPopulator::Populator(populator) => {
-                                to_recursing_after_populating(
-                                    &external_dependency_values,
-                                    &internal_dependency_values,
-                                    populator,
-                                    field_plan.field_type.type_.name(),
-                                    field_plan,
-                                )
-                            }
-                            CarverOrPopulator::PopulatorList(populator) => {
-                                let populated = populator.populate(
-                                    &external_dependency_values,
-                                    &internal_dependency_values,
-                                );
-                                let type_name = field_plan.field_type.type_.name();
-                                let fields_in_progress = populated
-                                    .iter()
-                                    .map(|populated| {
-                                        fields_in_progress_new(
-                                            &field_plan.selection_set_by_type.as_ref().unwrap()
-                                                [type_name],
-                                            &populated,
-                                        )
-                                    })
-                                    .collect();
-                                ResponseValueOrInProgress::InProgressRecursingList(
-                                    InProgressRecursingList::new(
-                                        field_plan,
-                                        populated,
-                                        fields_in_progress,
-                                    ),
-                                )
-                            }
-                            CarverOrPopulator::Carver(carver) => {
-                                ResponseValueOrInProgress::ResponseValue(carver.carve(
-                                    &external_dependency_values,
-                                    &internal_dependency_values,
-                                ))
-                            }
-                            CarverOrPopulator::UnionOrInterfaceTypePopulator(
-                                type_populator,
-                                populator,
-                            ) => {
-                                let type_name = type_populator.populate(
-                                    &external_dependency_values,
-                                    &internal_dependency_values,
-                                );
-                                to_recursing_after_populating(
-                                    &external_dependency_values,
-                                    &internal_dependency_values,
-                                    populator,
-                                    &type_name,
-                                    field_plan,
-                                )
-                            }
-                            CarverOrPopulator::UnionOrInterfaceTypePopulatorList(
-                                type_populator,
-                                populator,
-                            ) => {
-                                let type_names = type_populator.populate(
-                                    &external_dependency_values,
-                                    &internal_dependency_values,
-                                );
-                                let populated = populator.populate(
-                                    &external_dependency_values,
-                                    &internal_dependency_values,
-                                );
-                                assert!(type_names.len() == populated.len());
-                                let fields_in_progress = populated
-                                    .iter()
-                                    .zip(type_names)
-                                    .map(|(populated, type_name)| {
-                                        fields_in_progress_new(
-                                            &field_plan.selection_set_by_type.as_ref().unwrap()
-                                                [&type_name],
-                                            populated,
-                                        )
-                                    })
-                                    .collect();
-                                ResponseValueOrInProgress::InProgressRecursingList(
-                                    InProgressRecursingList::new(
-                                        field_plan,
-                                        populated,
-                                        fields_in_progress,
-                                    ),
-                                )
-                            }
+                        if !is_done {
+                            are_all_done = false;
                         }
+                        progressed.push(fields_in_progress);
                     }
-                    ResponseValueOrInProgress::InProgressRecursing(InProgressRecursing {
-                        field_plan,
-                        populated,
-                        selection,
-                    }) => {
-                        let (is_done, fields_in_progress) =
-                            progress_fields(selection, db_pool, schema).await;
-
-                        if is_done {
-                            ResponseValueOrInProgress::ResponseValue(fields_in_progress.into())
-                        } else {
-                            ResponseValueOrInProgress::InProgressRecursing(InProgressRecursing {
+                    if are_all_done {
+                        to_completed(complete_value(
+                            progressed.into(),
+                            &field_plan.field_type.type_,
+                            &mut false,
+                        ))
+                    } else {
+                        ResponseValueOrInProgress::InProgressRecursingList(
+                            InProgressRecursingList {
                                 field_plan,
                                 populated,
-                                selection: fields_in_progress,
-                            })
-                        }
-                    }
-                    ResponseValueOrInProgress::InProgressRecursingList(
-                        InProgressRecursingList {
-                            field_plan,
-                            populated,
-                            selections,
-                        },
-                    ) => {
-                        let mut progressed = vec![];
-                        let mut are_all_done = true;
-                        for selection in selections {
-                            let (is_done, fields_in_progress) =
-                                progress_fields(selection, db_pool, schema).await;
-                            if !is_done {
-                                are_all_done = false;
-                            }
-                            progressed.push(fields_in_progress);
-                        }
-                        if are_all_done {
-                            ResponseValueOrInProgress::ResponseValue(progressed.into())
-                        } else {
-                            ResponseValueOrInProgress::InProgressRecursingList(
-                                InProgressRecursingList {
-                                    field_plan,
-                                    populated,
-                                    selections: progressed,
-                                },
-                            )
-                        }
+                                selections: progressed,
+                            },
+                        )
                     }
-                },
-            );
+                }
+            };
+            progressed.insert(field_name, progressed_field);
         }
 
         (false, progressed)
     })
 }
 
+/// Resolves the field's internal dependencies and then either carves its
+/// value or populates its selection set's external dependencies
+async fn start_field<'a>(
+    field_plan: &'a FieldPlan<'a>,
+    external_dependency_values: &ExternalDependencyValues,
+    data_source: &dyn DataSource,
+    schema: &Schema,
+    column_values: &LoadedColumnValues,
+) -> FieldResult<ResponseValueOrInProgress<'a>> {
+    let internal_dependency_values = populate_internal_dependencies(
+        field_plan,
+        external_dependency_values,
+        data_source,
+        schema,
+        column_values,
+    )
+    .await?;
+    Ok(match &field_plan.field_type.resolver.carver_or_populator {
+        CarverOrPopulator::Populator(populator) => to_recursing_after_populating(
+            external_dependency_values,
+            &internal_dependency_values,
+            populator,
+            field_plan.field_type.type_.name(),
+            field_plan,
+        )?,
+        CarverOrPopulator::PopulatorList(populator) => to_recursing_list(
+            populator.populate(external_dependency_values, &internal_dependency_values)?,
+            field_plan,
+        ),
+        CarverOrPopulator::OptionalPopulator(populator) => {
+            match populator.populate(external_dependency_values, &internal_dependency_values)? {
+                Some(populated) => {
+                    to_recursing(populated, field_plan.field_type.type_.name(), field_plan)
+                }
+                None => ResponseValueOrInProgress::ResponseValue(ResponseValue::Null),
+            }
+        }
+        CarverOrPopulator::OptionalPopulatorList(populator) => {
+            match populator.populate(external_dependency_values, &internal_dependency_values)? {
+                Some(populated) => to_recursing_list(populated, field_plan),
+                None => ResponseValueOrInProgress::ResponseValue(ResponseValue::Null),
+            }
+        }
+        CarverOrPopulator::Carver(carver) => {
+            let carved = carver.carve(external_dependency_values, &internal_dependency_values)?;
+            if let Some(enum_type) = schema
+                .maybe_type(field_plan.field_type.type_.name())
+                .and_then(|type_| type_.maybe_as_enum())
+            {
+                validate_enum_value(&carved, enum_type)?;
+            }
+            ResponseValueOrInProgress::ResponseValue(carved)
+        }
+        CarverOrPopulator::UnionOrInterfaceTypePopulator(type_populator, populator) => {
+            let type_name =
+                type_populator.populate(external_dependency_values, &internal_dependency_values)?;
+            to_recursing_after_populating(
+                external_dependency_values,
+                &internal_dependency_values,
+                populator,
+                &type_name,
+                field_plan,
+            )?
+        }
+        CarverOrPopulator::UnionOrInterfaceTypePopulatorList(type_populator, populator) => {
+            let type_names =
+                type_populator.populate(external_dependency_values, &internal_dependency_values)?;
+            let populated =
+                populator.populate(external_dependency_values, &internal_dependency_values)?;
+            assert!(type_names.len() == populated.len());
+            let fields_in_progress = populated
+                .iter()
+                .zip(type_names)
+                .map(|(populated, type_name)| {
+                    fields_in_progress_new(
+                        &field_plan.selection_set_by_type.as_ref().unwrap()[&type_name],
+                        populated,
+                    )
+                })
+                .collect();
+            ResponseValueOrInProgress::InProgressRecursingList(InProgressRecursingList::new(
+                field_plan,
+                populated,
+                fields_in_progress,
+            ))
+        }
+    })
+}
+
+/// Per spec, a carved value that isn't one of the enum type's values is a
+/// field error
+fn validate_enum_value(value: &ResponseValue, enum_type: &EnumType) -> FieldResult<()> {
+    match value {
+        ResponseValue::EnumValue(value) if !enum_type.has_value(value) => Err(FieldError::new(
+            format!("Invalid value `{value}` for enum `{}`", enum_type.name),
+        )),
+        ResponseValue::List(values) => values
+            .iter()
+            .try_for_each(|value| validate_enum_value(value, enum_type)),
+        _ => Ok(()),
+    }
+}
+
 async fn populate_internal_dependencies(
     field_plan: &FieldPlan<'_>,
     external_dependency_values: &ExternalDependencyValues,
-    db_pool: &Pool<Postgres>,
+    data_source: &dyn DataSource,
     schema: &Schema,
-) -> InternalDependencyValues {
+    column_values: &LoadedColumnValues,
+) -> FieldResult<InternalDependencyValues> {
     let mut ret = InternalDependencyValues::default();
     for internal_dependency in field_plan.field_type.resolver.internal_dependencies.iter() {
         ret.insert(
             internal_dependency.name.clone(),
             match &internal_dependency.resolver {
                 InternalDependencyResolver::ColumnGetter(column_getter) => {
-                    let row_id = match external_dependency_values.get("id").unwrap() {
-                        DependencyValue::Id(id) => id,
-                        _ => unreachable!(),
-                    };
-                    match internal_dependency.type_ {
-                        DependencyType::Id => {
-                            // TODO: should check that table names and column names can never be SQL injection?
-                            let query = format!(
-                                "SELECT {} FROM {} WHERE id = $1",
-                                column_getter.column_name, column_getter.table_name
-                            );
-                            let (column_value,): (Id,) = sqlx::query_as(&query)
-                                .bind(row_id)
-                                .fetch_one(db_pool)
-                                .await
-                                .unwrap();
-                            DependencyValue::Id(column_value)
-                        }
-                        DependencyType::String => {
-                            // TODO: should check that table names and column names can never be SQL injection?
-                            let query = format!(
-                                "SELECT {} FROM {} WHERE id = $1",
-                                column_getter.column_name, column_getter.table_name
-                            );
-                            let (column_value,): (String,) = sqlx::query_as(&query)
-                                .bind(row_id)
-                                .fetch_one(db_pool)
-                                .await
-                                .unwrap();
-                            DependencyValue::String(column_value)
-                        }
-                        _ => unimplemented!(),
-                    }
+                    let row_id = get_parent_id(external_dependency_values)?;
+                    column_values.get(column_getter, internal_dependency.type_, row_id)?
                 }
-                InternalDependencyResolver::LiteralValue(literal_value) => literal_value.0.clone(),
-                InternalDependencyResolver::ColumnGetterList(column_getter_list) => {
-                    // TODO: same as above, sql injection?
-                    let query = format!(
-                        "SELECT {} FROM {}",
-                        column_getter_list.column_name, column_getter_list.table_name
-                    );
-                    let rows = sqlx::query_as::<_, (Id,)>(&query)
-                        .fetch_all(db_pool)
-                        .await
-                        .unwrap();
+                InternalDependencyResolver::HasManyViaForeignKey(has_many_via_foreign_key) => {
+                    let parent_id = get_parent_id(external_dependency_values)?;
+                    column_values.get_child_ids(has_many_via_foreign_key, parent_id)?
+                }
+                InternalDependencyResolver::ManyToManyViaJoinTable(many_to_many) => {
+                    let parent_id = get_parent_id(external_dependency_values)?;
+                    let list_query = resolve_list_query(
+                        &many_to_many.conditions,
+                        many_to_many.order_by.as_ref(),
+                        many_to_many.limit.as_ref(),
+                        &ret,
+                    )?;
                     DependencyValue::List(
-                        rows.into_iter()
-                            .map(|(column_value,)| DependencyValue::Id(column_value))
+                        data_source
+                            .get_ids_via_join_table(
+                                &many_to_many.join_table_name,
+                                &many_to_many.parent_column_name,
+                                &many_to_many.target_column_name,
+                                &many_to_many.target_table_name,
+                                parent_id,
+                                &list_query,
+                            )
+                            .await?
+                            .into_iter()
+                            .map(DependencyValue::Id)
                             .collect(),
                     )
                 }
-                InternalDependencyResolver::IntrospectionTypeInterfaces => {
-                    let type_name = match external_dependency_values.get("name").unwrap() {
-                        DependencyValue::String(name) => name,
-                        _ => unreachable!(),
-                    };
+                InternalDependencyResolver::Custom(custom) => {
+                    custom
+                        .resolve(external_dependency_values, &ret, data_source)
+                        .await?
+                }
+                InternalDependencyResolver::LiteralValue(literal_value) => literal_value.0.clone(),
+                InternalDependencyResolver::ColumnGetterList(column_getter_list) => {
                     DependencyValue::List(
-                        schema
-                            .maybe_type(type_name)
-                            .filter(|type_| matches!(type_, Type::Object(_)))
-                            .map(|type_| {
-                                type_
-                                    .as_object()
-                                    .implements
-                                    .iter()
-                                    .map(|implement| DependencyValue::String(implement.clone()))
-                                    .collect()
-                            })
-                            .or_else(|| {
-                                schema.interfaces.get(type_name).map(|interface| {
-                                    interface
-                                        .implements
-                                        .iter()
-                                        .map(|implement| DependencyValue::String(implement.clone()))
-                                        .collect()
-                                })
-                            })
-                            .unwrap_or_default(),
+                        data_source
+                            .get_column_values(
+                                &column_getter_list.table_name,
+                                &column_getter_list.column_name,
+                                match internal_dependency.type_ {
+                                    DependencyType::ListOfIds => DependencyType::Id,
+                                    DependencyType::ListOfStrings => DependencyType::String,
+                                    _ => {
+                                        return Err(FieldError::new(format!(
+                                            "Expected `{}` to be a list of ids or strings",
+                                            internal_dependency.name
+                                        )))
+                                    }
+                                },
+                                &resolve_list_query(
+                                    &column_getter_list.conditions,
+                                    column_getter_list.order_by.as_ref(),
+                                    column_getter_list.limit.as_ref(),
+                                    &ret,
+                                )?,
+                            )
+                            .await?,
+                    )
+                }
+                resolver @ (InternalDependencyResolver::IntrospectionSchema
+                | InternalDependencyResolver::IntrospectionTypeByName
+                | InternalDependencyResolver::IntrospectionTypeKind
+                | InternalDependencyResolver::IntrospectionTypeDescription
+                | InternalDependencyResolver::IntrospectionTypeFields
+                | InternalDependencyResolver::IntrospectionTypeInterfaces
+                | InternalDependencyResolver::IntrospectionTypePossibleTypes
+                | InternalDependencyResolver::IntrospectionTypeEnumValues
+                | InternalDependencyResolver::IntrospectionTypeInputFields) => {
+                    resolve_introspection(
+                        resolver,
+                        field_plan,
+                        external_dependency_values,
+                        &ret,
+                        schema,
                     )
                 }
                 InternalDependencyResolver::Argument(argument_resolver) => {
-                    let argument = field_plan
-                        .arguments
-                        .as_ref()
-                        .unwrap()
-                        .get(&argument_resolver.name)
-                        .unwrap();
-                    match (internal_dependency.type_, &argument.value) {
-                        (DependencyType::Id, Value::Int(argument_value)) => {
-                            DependencyValue::Id(*argument_value)
-                        }
-                        (DependencyType::String, Value::String(argument_value)) => {
-                            DependencyValue::String(argument_value.clone())
+                    coerce_field_argument(
+                        field_plan.arguments.as_ref(),
+                        &field_plan.field_type.params[&argument_resolver.name],
+                        schema,
+                        field_plan.variable_values,
+                    )?
+                    // an omitted nullable argument
+                    .unwrap_or(DependencyValue::Null)
+                }
+                InternalDependencyResolver::RowInserter(row_inserter) => {
+                    let coerce_argument = |argument_name: &str| {
+                        coerce_field_argument(
+                            field_plan.arguments.as_ref(),
+                            &field_plan.field_type.params[argument_name],
+                            schema,
+                            field_plan.variable_values,
+                        )
+                    };
+                    let column_values = match row_inserter.input_object_argument_name.as_ref() {
+                        None => row_inserter
+                            .columns
+                            .iter()
+                            .filter_map(|(column_name, argument_name)| {
+                                coerce_argument(argument_name)
+                                    .transpose()
+                                    .map(|value| Ok((column_name.clone(), value?)))
+                            })
+                            .collect::<FieldResult<Vec<_>>>()?,
+                        Some(input_object_argument_name) => {
+                            match coerce_argument(input_object_argument_name)? {
+                                Some(DependencyValue::Object(mut fields)) => row_inserter
+                                    .columns
+                                    .iter()
+                                    .filter_map(|(column_name, field_name)| {
+                                        Some((column_name.clone(), fields.swap_remove(field_name)?))
+                                    })
+                                    .collect(),
+                                None | Some(DependencyValue::Null) => vec![],
+                                Some(_) => {
+                                    return Err(FieldError::new(format!(
+                                    "Expected input object argument `{input_object_argument_name}`"
+                                )))
+                                }
+                            }
                         }
-                        // TODO: truly unreachable?
-                        _ => unreachable!(),
-                    }
+                    };
+                    DependencyValue::Id(
+                        data_source
+                            .insert_row(&row_inserter.table_name, column_values)
+                            .await?,
+                    )
                 }
             },
         )
         .unwrap();
     }
 
-    ret
+    Ok(ret)
+}
+
+/// The `id` external dependency that `ColumnGetter`s, `HasManyViaForeignKey`s
+/// and `ManyToManyViaJoinTable`s look up by
+fn get_parent_id(external_dependency_values: &ExternalDependencyValues) -> FieldResult<Id> {
+    match external_dependency_values.get("id") {
+        Some(DependencyValue::Id(id)) => Ok(*id),
+        _ => Err(FieldError::new("Expected `id` external dependency")),
+    }
+}
+
+/// Resolves a `ColumnGetterList`'s (or `ManyToManyViaJoinTable`'s)
+/// conditions, ordering and limit
+pub(crate) fn resolve_list_query(
+    conditions: &[(String, ColumnGetterListValue)],
+    order_by: Option<&ColumnGetterListOrderBy>,
+    limit: Option<&ColumnGetterListValue>,
+    internal_dependency_values: &InternalDependencyValues,
+) -> FieldResult<ListQuery> {
+    let get_internal_dependency = |name: &str| {
+        internal_dependency_values
+            .get(name)
+            .ok_or_else(|| FieldError::new(format!("Missing dependency `{name}`")))
+    };
+    let resolve_value = |value: &ColumnGetterListValue| match value {
+        ColumnGetterListValue::Literal(value) => Ok(value.clone()),
+        ColumnGetterListValue::InternalDependency(name) => get_internal_dependency(name).cloned(),
+    };
+    let conditions = conditions
+        .iter()
+        .map(|(column_name, value)| Ok((column_name.clone(), resolve_value(value)?)))
+        .filter(|condition| !matches!(condition, Ok((_, DependencyValue::Null))))
+        .collect::<FieldResult<_>>()?;
+    let order_by = match order_by {
+        None => None,
+        Some(ColumnGetterListOrderBy::Fixed(order)) => Some(order.clone()),
+        Some(ColumnGetterListOrderBy::InternalDependency(name, orders)) => {
+            match get_internal_dependency(name)? {
+                DependencyValue::Null => None,
+                DependencyValue::String(value) => Some(
+                    orders
+                        .get(value)
+                        .ok_or_else(|| FieldError::new(format!("Unknown ordering `{value}`")))?
+                        .clone(),
+                ),
+                _ => {
+                    return Err(FieldError::new(format!(
+                        "Expected string ordering `{name}`"
+                    )))
+                }
+            }
+        }
+    };
+    let limit = match limit.map(resolve_value).transpose()? {
+        None | Some(DependencyValue::Null) => None,
+        Some(DependencyValue::Int(limit) | DependencyValue::Id(limit)) if limit >= 0 => {
+            Some(i64::from(limit))
+        }
+        Some(DependencyValue::Int(limit) | DependencyValue::Id(limit)) => {
+            return Err(FieldError::new(format!(
+                "Limit must not be negative, got `{limit}`"
+            )));
+        }
+        _ => return Err(FieldError::new("Expected integer limit")),
+    };
+    Ok(ListQuery {
+        conditions,
+        order_by,
+        limit,
+        .._d()
+    })
+}
+
+/// Per spec, a `null` in a non-null position makes the nearest nullable
+/// enclosing position `null` instead. Returns `None` if there's no such
+/// position within `type_`, ie the `null` propagates to the enclosing object
+fn complete_value(
+    value: ResponseValue,
+    type_: &TypeFull,
+    has_null_violation: &mut bool,
+) -> Option<ResponseValue> {
+    match (type_, value) {
+        (TypeFull::NonNull(_), ResponseValue::Null) => {
+            *has_null_violation = true;
+            None
+        }
+        (TypeFull::NonNull(type_), value) => complete_value(value, type_, has_null_violation),
+        (TypeFull::List(item_type), ResponseValue::List(items)) => Some(
+            items
+                .into_iter()
+                .map(|item| complete_value(item, item_type, has_null_violation))
+                .collect::<Option<Vec<_>>>()
+                .map_or(ResponseValue::Null, ResponseValue::List),
+        ),
+        (_, value) => Some(value),
+    }
+}
+
+fn to_completed<'a>(completed: Option<ResponseValue>) -> ResponseValueOrInProgress<'a> {
+    match completed {
+        Some(value) => ResponseValueOrInProgress::ResponseValue(value),
+        None => ResponseValueOrInProgress::PropagatedNull,
+    }
 }
 
 fn to_recursing_after_populating<'a>(
@@ -516,8 +977,20 @@ fn to_recursing_after_populating<'a>(
     populator: &Box<dyn Populator>,
     resolved_concrete_type_name: &str,
     field_plan: &'a FieldPlan<'a>,
+) -> FieldResult<ResponseValueOrInProgress<'a>> {
+    let populated = populator.populate(&external_dependency_values, &internal_dependency_values)?;
+    Ok(to_recursing(
+        populated,
+        resolved_concrete_type_name,
+        field_plan,
+    ))
+}
+
+fn to_recursing<'a>(
+    populated: ExternalDependencyValues,
+    resolved_concrete_type_name: &str,
+    field_plan: &'a FieldPlan<'a>,
 ) -> ResponseValueOrInProgress<'a> {
-    let populated = populator.populate(&external_dependency_values, &internal_dependency_values);
     let fields_in_progress = fields_in_progress_new(
         &field_plan.selection_set_by_type.as_ref().unwrap()[resolved_concrete_type_name],
         &populated,
@@ -529,6 +1002,27 @@ fn to_recursing_after_populating<'a>(
     ))
 }
 
+fn to_recursing_list<'a>(
+    populated: Vec<ExternalDependencyValues>,
+    field_plan: &'a FieldPlan<'a>,
+) -> ResponseValueOrInProgress<'a> {
+    let type_name = field_plan.field_type.type_.name();
+    let fields_in_progress = populated
+        .iter()
+        .map(|populated| {
+            fields_in_progress_new(
+                &field_plan.selection_set_by_type.as_ref().unwrap()[type_name],
+                populated,
+            )
+        })
+        .collect();
+    ResponseValueOrInProgress::InProgressRecursingList(InProgressRecursingList::new(
+        field_plan,
+        populated,
+        fields_in_progress,
+    ))
+}
+
 #[derive(Copy, Clone)]
 pub enum TypeOrUnionOrInterface<'a> {
     Type(&'a Type),
@@ -0,0 +1,483 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use serde_json::Value as JsonValue;
+
+use crate::{
+    parse_sdl, Carver, CarverOrPopulator, CustomScalar, DependencyValue, EnumType, Error,
+    ExternalDependencyValues, FieldError, FieldInterface, FieldResolver, FieldResult, IndexMap,
+    InputField, InputObjectType, Interface, InternalDependencyValues, ObjectType,
+    ObjectTypeBuilder, ObjectTypeDefinition, OperationType, Param, ResponseValue, ScalarType,
+    Schema, SubscriptionSource, Type, TypeFieldBuilder, TypeFull, TypeInterface,
+    TypeOrInterfaceField, TypeSystemDefinition, Union, Value, DEFAULT_DEPRECATION_REASON,
+};
+
+/// What gets attached to the (resolver-less) types parsed from SDL by
+/// `Schema::try_from_sdl()`. Resolvers and subscription sources are keyed by
+/// `Type.field` coordinate (eg `Query.actors`)
+#[derive(Default)]
+pub struct SdlResolvers {
+    resolvers: IndexMap<String, FieldResolver>,
+    subscription_sources: IndexMap<String, Box<dyn SubscriptionSource>>,
+    custom_scalars: IndexMap<String, Box<dyn CustomScalar>>,
+}
+
+impl SdlResolvers {
+    pub fn resolver(self, coordinate: impl Into<String>, resolver: FieldResolver) -> Self {
+        let mut new = self;
+        new.resolvers.insert(coordinate.into(), resolver);
+        new
+    }
+
+    pub fn subscription_source(
+        self,
+        coordinate: impl Into<String>,
+        subscription_source: Box<dyn SubscriptionSource>,
+    ) -> Self {
+        let mut new = self;
+        new.subscription_sources
+            .insert(coordinate.into(), subscription_source);
+        new
+    }
+
+    pub fn custom_scalar(self, custom_scalar: Box<dyn CustomScalar>) -> Self {
+        let mut new = self;
+        new.custom_scalars
+            .insert(custom_scalar.name().to_owned(), custom_scalar);
+        new
+    }
+}
+
+impl Schema {
+    /// Builds a schema from an SDL document (type definitions only). Every
+    /// field of an object type needs a resolver (and every subscription field
+    /// a subscription source), and every resolver/subscription source/custom
+    /// scalar has to correspond to something in the SDL. Any such mismatches
+    /// get reported along with `Schema::try_new()`'s errors
+    pub fn try_from_sdl(sdl: &str, resolvers: SdlResolvers) -> Result<Self, Vec<Error>> {
+        let document = parse_sdl(sdl.chars()).map_err(|error| vec![Error::InvalidSdl(error)])?;
+        let SdlResolvers {
+            mut resolvers,
+            mut subscription_sources,
+            mut custom_scalars,
+        } = resolvers;
+        let root_operation_types = document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                TypeSystemDefinition::Schema(root_operation_types) => {
+                    Some(root_operation_types.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| {
+                vec![
+                    (OperationType::Query, "Query".to_owned()),
+                    (OperationType::Mutation, "Mutation".to_owned()),
+                    (OperationType::Subscription, "Subscription".to_owned()),
+                ]
+            });
+
+        let mut errors: Vec<Error> = vec![];
+        let mut types: Vec<Type> = vec![];
+        let mut unions: Vec<Union> = vec![];
+        let mut interfaces: Vec<Interface> = vec![];
+        for definition in document.definitions {
+            match definition {
+                TypeSystemDefinition::Schema(_) => {}
+                TypeSystemDefinition::Scalar(name) => {
+                    let custom_scalar = custom_scalars.shift_remove(&name).unwrap_or_else(|| {
+                        errors.push(Error::MissingCustomScalar(name.clone()));
+                        Box::new(MissingCustomScalar { name })
+                    });
+                    types.push(Type::Scalar(ScalarType::Custom(custom_scalar)));
+                }
+                TypeSystemDefinition::Object(object_type) => {
+                    let operation_type = root_operation_types
+                        .iter()
+                        .find(|(_, name)| *name == object_type.name)
+                        .map(|(operation_type, _)| *operation_type);
+                    types.push(Type::Object(object_type_from_definition(
+                        object_type,
+                        operation_type,
+                        &mut resolvers,
+                        &mut subscription_sources,
+                        &mut errors,
+                    )));
+                }
+                TypeSystemDefinition::Interface(interface) => interfaces.push(interface),
+                TypeSystemDefinition::Union(union) => unions.push(union),
+                TypeSystemDefinition::Enum(enum_type) => types.push(Type::Enum(enum_type)),
+                TypeSystemDefinition::InputObject(input_object_type) => {
+                    types.push(Type::InputObject(input_object_type))
+                }
+            }
+        }
+        errors.extend(resolvers.into_keys().map(Error::ExtraResolver));
+        // (a subscription source for a known non-subscription field gets
+        // reported by `Schema::try_new()`)
+        errors.extend(
+            subscription_sources
+                .into_keys()
+                .map(Error::ExtraSubscriptionSource),
+        );
+        errors.extend(custom_scalars.into_keys().map(Error::ExtraCustomScalar));
+
+        match Self::try_new(types, unions, interfaces) {
+            Ok(schema) if errors.is_empty() => Ok(schema),
+            Ok(_) => Err(errors),
+            Err(type_system_errors) => {
+                errors.extend(type_system_errors);
+                Err(errors)
+            }
+        }
+    }
+
+    /// Prints the schema as GraphQL SDL. Named types get printed sorted by
+    /// name (and fields etc in their declared order) so that the output is
+    /// stable enough to commit and diff
+    pub fn sdl(&self) -> String {
+        let mut definitions: Vec<String> = vec![];
+        if let Some(schema_definition) = self.sdl_schema_definition() {
+            definitions.push(schema_definition);
+        }
+        definitions.extend(
+            self.types
+                .values()
+                .filter_map(|type_| Some((type_.name(), sdl_type(type_)?)))
+                .chain(
+                    self.interfaces
+                        .values()
+                        .map(|interface| (&*interface.name, sdl_interface(interface))),
+                )
+                .chain(
+                    self.unions
+                        .values()
+                        .map(|union| (&*union.name, sdl_union(union))),
+                )
+                .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                .map(|(_, definition)| definition),
+        );
+        let mut ret = definitions.join("\n\n");
+        ret.push('\n');
+        ret
+    }
+
+    /// Per spec, the `schema` definition can be omitted when the root
+    /// operation types use their default names
+    fn sdl_schema_definition(&self) -> Option<String> {
+        let root_operation_types = [
+            ("query", Some(&self.query_type_name), "Query"),
+            ("mutation", self.mutation_type_name.as_ref(), "Mutation"),
+            (
+                "subscription",
+                self.subscription_type_name.as_ref(),
+                "Subscription",
+            ),
+        ];
+        if root_operation_types
+            .iter()
+            .all(|(_, name, default_name)| name.is_none_or(|name| name == default_name))
+        {
+            return None;
+        }
+        let mut ret = "schema {\n".to_owned();
+        for (operation_type, name, _) in root_operation_types {
+            if let Some(name) = name {
+                writeln!(ret, "  {operation_type}: {name}").unwrap();
+            }
+        }
+        ret.push('}');
+        Some(ret)
+    }
+}
+
+fn sdl_type(type_: &Type) -> Option<String> {
+    match type_ {
+        Type::Object(object_type) => Some(sdl_object_type(object_type)),
+        Type::Scalar(ScalarType::BuiltIn(_)) => None,
+        Type::Scalar(ScalarType::Custom(custom_scalar)) => {
+            Some(format!("scalar {}", custom_scalar.name()))
+        }
+        Type::Enum(enum_type) => Some(sdl_enum_type(enum_type)),
+        Type::InputObject(input_object_type) => Some(sdl_input_object_type(input_object_type)),
+    }
+}
+
+fn sdl_object_type(object_type: &ObjectType) -> String {
+    let mut ret = sdl_description(object_type.description.as_deref(), "");
+    write!(
+        ret,
+        "type {}{}",
+        object_type.name,
+        sdl_implements(&object_type.implements)
+    )
+    .unwrap();
+    ret.push_str(&sdl_block(
+        object_type
+            .fields
+            .values()
+            .map(|field| sdl_field(field.into())),
+    ));
+    ret
+}
+
+fn sdl_interface(interface: &Interface) -> String {
+    let mut ret = sdl_description(interface.description.as_deref(), "");
+    write!(
+        ret,
+        "interface {}{}",
+        interface.name,
+        sdl_implements(&interface.implements)
+    )
+    .unwrap();
+    ret.push_str(&sdl_block(
+        interface
+            .fields
+            .values()
+            .map(|field| sdl_field(field.into())),
+    ));
+    ret
+}
+
+fn sdl_union(union: &Union) -> String {
+    let mut ret = sdl_description(union.description.as_deref(), "");
+    write!(
+        ret,
+        "union {} = {}",
+        union.name,
+        union.types.iter().join(" | ")
+    )
+    .unwrap();
+    ret
+}
+
+fn sdl_enum_type(enum_type: &EnumType) -> String {
+    let mut ret = sdl_description(enum_type.description.as_deref(), "");
+    write!(ret, "enum {}", enum_type.name).unwrap();
+    ret.push_str(&sdl_block(enum_type.values.values().map(|value| {
+        format!(
+            "{}  {}{}",
+            sdl_description(value.description.as_deref(), "  "),
+            value.name,
+            sdl_deprecated(value.deprecation_reason.as_deref()),
+        )
+    })));
+    ret
+}
+
+fn sdl_input_object_type(input_object_type: &InputObjectType) -> String {
+    let mut ret = sdl_description(input_object_type.description.as_deref(), "");
+    write!(ret, "input {}", input_object_type.name).unwrap();
+    ret.push_str(&sdl_block(
+        input_object_type.fields.values().map(sdl_input_field),
+    ));
+    ret
+}
+
+fn sdl_field(field: TypeOrInterfaceField<'_>) -> String {
+    format!(
+        "{}  {}{}: {}{}",
+        sdl_description(field.description(), "  "),
+        field.name(),
+        sdl_params(field.params()),
+        field.type_(),
+        sdl_deprecated(field.deprecation_reason()),
+    )
+}
+
+/// Params get printed on one line unless any of them has a description
+fn sdl_params(params: &IndexMap<String, Param>) -> String {
+    if params.is_empty() {
+        return "".to_owned();
+    }
+    if params.values().all(|param| param.description.is_none()) {
+        return format!(
+            "({})",
+            params
+                .values()
+                .map(|param| sdl_input_value(
+                    &param.name,
+                    &param.type_,
+                    param.default_value.as_ref(),
+                    param.deprecation_reason.as_deref()
+                ))
+                .join(", ")
+        );
+    }
+    format!(
+        "(\n{}\n  )",
+        params
+            .values()
+            .map(|param| format!(
+                "{}    {}",
+                sdl_description(param.description.as_deref(), "    "),
+                sdl_input_value(
+                    &param.name,
+                    &param.type_,
+                    param.default_value.as_ref(),
+                    param.deprecation_reason.as_deref()
+                ),
+            ))
+            .join("\n")
+    )
+}
+
+fn sdl_input_field(input_field: &InputField) -> String {
+    format!(
+        "{}  {}",
+        sdl_description(input_field.description.as_deref(), "  "),
+        sdl_input_value(
+            &input_field.name,
+            &input_field.type_,
+            input_field.default_value.as_ref(),
+            None,
+        ),
+    )
+}
+
+fn sdl_input_value(
+    name: &str,
+    type_: &TypeFull,
+    default_value: Option<&Value>,
+    deprecation_reason: Option<&str>,
+) -> String {
+    let mut ret = format!("{name}: {type_}");
+    if let Some(default_value) = default_value {
+        write!(ret, " = {default_value}").unwrap();
+    }
+    ret.push_str(&sdl_deprecated(deprecation_reason));
+    ret
+}
+
+fn sdl_implements(implements: &[String]) -> String {
+    if implements.is_empty() {
+        return "".to_owned();
+    }
+    format!(" implements {}", implements.iter().join(" & "))
+}
+
+fn sdl_block(lines: impl Iterator<Item = String>) -> String {
+    format!(" {{\n{}\n}}", lines.collect::<Vec<_>>().join("\n"))
+}
+
+fn sdl_deprecated(deprecation_reason: Option<&str>) -> String {
+    match deprecation_reason {
+        None => "".to_owned(),
+        Some(DEFAULT_DEPRECATION_REASON) => " @deprecated".to_owned(),
+        Some(reason) => format!(" @deprecated(reason: {})", sdl_string(reason)),
+    }
+}
+
+/// Single-line descriptions get printed as (quoted) strings, multi-line
+/// ones as block strings
+fn sdl_description(description: Option<&str>, indent: &str) -> String {
+    let Some(description) = description else {
+        return "".to_owned();
+    };
+    if !description.contains('\n') {
+        return format!("{indent}{}\n", sdl_string(description));
+    }
+    let mut ret = format!("{indent}\"\"\"\n");
+    for line in description.replace("\"\"\"", "\\\"\"\"").lines() {
+        if line.is_empty() {
+            ret.push('\n');
+        } else {
+            writeln!(ret, "{indent}{line}").unwrap();
+        }
+    }
+    writeln!(ret, "{indent}\"\"\"").unwrap();
+    ret
+}
+
+fn sdl_string(value: &str) -> String {
+    serde_json::Value::String(value.to_owned()).to_string()
+}
+
+fn object_type_from_definition(
+    object_type: ObjectTypeDefinition,
+    operation_type: Option<OperationType>,
+    resolvers: &mut IndexMap<String, FieldResolver>,
+    subscription_sources: &mut IndexMap<String, Box<dyn SubscriptionSource>>,
+    errors: &mut Vec<Error>,
+) -> ObjectType {
+    let mut builder = ObjectTypeBuilder::default()
+        .name(object_type.name.clone())
+        .implements(object_type.implements);
+    if let Some(description) = object_type.description {
+        builder = builder.description(description);
+    }
+    if let Some(operation_type) = operation_type {
+        builder = builder.is_top_level_type(operation_type);
+    }
+    let fields = object_type
+        .fields
+        .into_iter()
+        .map(|field| {
+            let coordinate = format!("{}.{}", object_type.name, field.name);
+            let resolver = resolvers.shift_remove(&coordinate).unwrap_or_else(|| {
+                errors.push(Error::MissingResolver(coordinate.clone()));
+                FieldResolver::new(
+                    vec![],
+                    vec![],
+                    CarverOrPopulator::Carver(Box::new(MissingResolverCarver {})),
+                )
+            });
+            let mut builder = TypeFieldBuilder::default()
+                .name(field.name)
+                .type_(field.type_)
+                .params(field.params)
+                .resolver(resolver);
+            if let Some(description) = field.description {
+                builder = builder.description(description);
+            }
+            if let Some(deprecation_reason) = field.deprecation_reason {
+                builder = builder.deprecation_reason(deprecation_reason);
+            }
+            // whether it's missing or extra gets checked by `Schema::try_new()`
+            if let Some(subscription_source) = subscription_sources.shift_remove(&coordinate) {
+                builder = builder.subscription_source(subscription_source);
+            }
+            builder.build().unwrap()
+        })
+        .collect::<Vec<_>>();
+    builder.fields(fields).build().unwrap()
+}
+
+/// Stands in for a missing resolver, so that the rest of the schema still
+/// gets checked by `Schema::try_new()` (the schema doesn't get built anyway)
+struct MissingResolverCarver {}
+
+impl Carver for MissingResolverCarver {
+    fn carve(
+        &self,
+        _external_dependencies: &ExternalDependencyValues,
+        _internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        Err(FieldError::new("Missing resolver"))
+    }
+}
+
+/// Like `MissingResolverCarver`, for a missing custom scalar
+struct MissingCustomScalar {
+    name: String,
+}
+
+impl CustomScalar for MissingCustomScalar {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn serialize(&self, _value: &DependencyValue) -> Result<ResponseValue, String> {
+        Err(format!("Missing custom scalar `{}`", self.name))
+    }
+
+    fn parse_value(&self, _value: &Value) -> Result<DependencyValue, String> {
+        Err(format!("Missing custom scalar `{}`", self.name))
+    }
+
+    fn parse_variable_value(&self, _value: &JsonValue) -> Result<DependencyValue, String> {
+        Err(format!("Missing custom scalar `{}`", self.name))
+    }
+}
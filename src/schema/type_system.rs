@@ -0,0 +1,423 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    BuiltInScalarType, DependencyType, Error, FieldInterface, Interface,
+    InternalDependencyResolver, ObjectType, ScalarType, Type, TypeField, TypeFull, TypeInterface,
+    TypeOrUnionOrInterface, Union,
+};
+
+/// Checks the type system rules from the spec (that
+/// `Schema::type_()`/`Schema::field()`/etc rely on at request time) along with
+/// that field resolvers' dependencies line up
+pub(super) fn validate_type_system(
+    types: &[Type],
+    unions: &[Union],
+    interfaces: &[Interface],
+    builtin_types: &HashMap<String, Type>,
+) -> Vec<Error> {
+    let mut errors: Vec<Error> = vec![];
+
+    let mut named_types: HashMap<&str, TypeOrUnionOrInterface<'_>> = builtin_types
+        .values()
+        .map(|type_| (type_.name(), TypeOrUnionOrInterface::Type(type_)))
+        .collect();
+    for (name, named_type) in types
+        .iter()
+        .map(|type_| (type_.name(), TypeOrUnionOrInterface::Type(type_)))
+        .chain(
+            unions
+                .iter()
+                .map(|union| (&*union.name, TypeOrUnionOrInterface::Union(union))),
+        )
+        .chain(interfaces.iter().map(|interface| {
+            (
+                &*interface.name,
+                TypeOrUnionOrInterface::Interface(interface),
+            )
+        }))
+    {
+        if named_types.insert(name, named_type).is_some() {
+            errors.push(Error::DuplicateTypeName(name.to_owned()));
+        }
+    }
+    let validator = TypeSystemValidator { named_types };
+
+    for type_ in types {
+        match type_ {
+            Type::Object(object_type) => {
+                validator.validate_object_type(object_type, &mut errors);
+            }
+            Type::InputObject(input_object_type) => {
+                for input_field in input_object_type.fields.values() {
+                    validator.validate_input_type(
+                        &input_field.type_,
+                        || format!("{}.{}", input_object_type.name, input_field.name),
+                        &mut errors,
+                    );
+                }
+            }
+            Type::Scalar(_) | Type::Enum(_) => {}
+        }
+    }
+    for union in unions {
+        validator.validate_union(union, &mut errors);
+    }
+    for interface in interfaces {
+        for field in interface.fields.values() {
+            validator.validate_field_types(&interface.name, field, &mut errors);
+        }
+        validator.validate_implements(
+            &interface.name,
+            &interface.implements,
+            &mut errors,
+            |name| interface.fields.get(name),
+        );
+    }
+
+    errors
+}
+
+struct TypeSystemValidator<'a> {
+    named_types: HashMap<&'a str, TypeOrUnionOrInterface<'a>>,
+}
+
+impl TypeSystemValidator<'_> {
+    fn validate_object_type(&self, object_type: &ObjectType, errors: &mut Vec<Error>) {
+        for field in object_type.fields.values() {
+            self.validate_field_types(&object_type.name, field, errors);
+            validate_field_dependencies(&object_type.name, field, errors);
+            self.validate_argument_dependency_types(&object_type.name, field, errors);
+            if object_type.is_subscription_type() && field.subscription_source.is_none() {
+                errors.push(Error::MissingSubscriptionSource(format!(
+                    "{}.{}",
+                    object_type.name, field.name
+                )));
+            } else if !object_type.is_subscription_type() && field.subscription_source.is_some() {
+                errors.push(Error::ExtraSubscriptionSource(format!(
+                    "{}.{}",
+                    object_type.name, field.name
+                )));
+            }
+        }
+        self.validate_implements(&object_type.name, &object_type.implements, errors, |name| {
+            object_type.fields.get(name)
+        });
+    }
+
+    fn validate_field_types(
+        &self,
+        type_name: &str,
+        field: &impl FieldInterface,
+        errors: &mut Vec<Error>,
+    ) {
+        let coordinate = || format!("{type_name}.{}", field.name());
+        if self
+            .named_type(field.type_(), coordinate, errors)
+            .is_some_and(|named_type| !is_output_type(named_type))
+        {
+            errors.push(Error::NonOutputType {
+                coordinate: coordinate(),
+                type_name: field.type_().name().to_owned(),
+            });
+        }
+        for param in field.params().values() {
+            self.validate_input_type(
+                &param.type_,
+                || format!("{}({}:)", coordinate(), param.name),
+                errors,
+            );
+        }
+    }
+
+    fn validate_input_type(
+        &self,
+        type_: &TypeFull,
+        coordinate: impl Fn() -> String,
+        errors: &mut Vec<Error>,
+    ) {
+        if self
+            .named_type(type_, &coordinate, errors)
+            .is_some_and(|named_type| !is_input_type(named_type))
+        {
+            errors.push(Error::NonInputType {
+                coordinate: coordinate(),
+                type_name: type_.name().to_owned(),
+            });
+        }
+    }
+
+    fn named_type(
+        &self,
+        type_: &TypeFull,
+        coordinate: impl Fn() -> String,
+        errors: &mut Vec<Error>,
+    ) -> Option<TypeOrUnionOrInterface<'_>> {
+        let named_type = self.named_types.get(type_.name()).copied();
+        if named_type.is_none() {
+            errors.push(Error::UnknownType {
+                coordinate: coordinate(),
+                type_name: type_.name().to_owned(),
+            });
+        }
+        named_type
+    }
+
+    fn validate_argument_dependency_types(
+        &self,
+        type_name: &str,
+        field: &TypeField,
+        errors: &mut Vec<Error>,
+    ) {
+        for internal_dependency in &field.resolver.internal_dependencies {
+            let InternalDependencyResolver::Argument(argument_resolver) =
+                &internal_dependency.resolver
+            else {
+                continue;
+            };
+            // an unknown argument gets reported by `validate_field_dependencies()`
+            let Some(param) = field.params.get(&argument_resolver.name) else {
+                continue;
+            };
+            if !self.is_argument_dependency_type_compatible(internal_dependency.type_, &param.type_)
+            {
+                errors.push(Error::IncompatibleArgumentDependency {
+                    coordinate: format!("{type_name}.{}", field.name),
+                    argument: argument_resolver.name.clone(),
+                });
+            }
+        }
+    }
+
+    /// Whether the argument's coerced values fit the dependency type. Custom
+    /// scalars can coerce to any dependency value so aren't checked
+    fn is_argument_dependency_type_compatible(
+        &self,
+        dependency_type: DependencyType,
+        param_type: &TypeFull,
+    ) -> bool {
+        match (param_type, dependency_type) {
+            (TypeFull::NonNull(param_type), dependency_type) => {
+                self.is_argument_dependency_type_compatible(dependency_type, param_type)
+            }
+            (TypeFull::List(item_type), DependencyType::ListOfIds) => {
+                self.is_argument_dependency_type_compatible(DependencyType::Id, item_type)
+            }
+            (TypeFull::List(item_type), DependencyType::ListOfStrings) => {
+                self.is_argument_dependency_type_compatible(DependencyType::String, item_type)
+            }
+            (TypeFull::List(_), _) => false,
+            (TypeFull::Type(type_name), dependency_type) => {
+                match self.named_types.get(&**type_name) {
+                    Some(TypeOrUnionOrInterface::Type(Type::Scalar(ScalarType::BuiltIn(
+                        scalar_type,
+                    )))) => matches!(
+                        (scalar_type, dependency_type),
+                        (BuiltInScalarType::String(_), DependencyType::String)
+                            | (BuiltInScalarType::Int(_), DependencyType::Int)
+                            | (BuiltInScalarType::Float(_), DependencyType::Float)
+                            | (BuiltInScalarType::Boolean(_), DependencyType::Boolean)
+                            | (BuiltInScalarType::Id(_), DependencyType::Id)
+                    ),
+                    Some(TypeOrUnionOrInterface::Type(Type::Enum(_))) => {
+                        dependency_type == DependencyType::String
+                    }
+                    Some(TypeOrUnionOrInterface::Type(Type::InputObject(_))) => {
+                        dependency_type == DependencyType::Object
+                    }
+                    // unknown/non-input types get reported by
+                    // `validate_field_types()`
+                    _ => true,
+                }
+            }
+        }
+    }
+
+    fn validate_union(&self, union: &Union, errors: &mut Vec<Error>) {
+        for member in &union.types {
+            match self.named_types.get(&**member) {
+                None => errors.push(Error::UnknownUnionMember {
+                    union: union.name.clone(),
+                    type_name: member.clone(),
+                }),
+                Some(TypeOrUnionOrInterface::Type(Type::Object(_))) => {}
+                Some(_) => errors.push(Error::NonObjectUnionMember {
+                    union: union.name.clone(),
+                    type_name: member.clone(),
+                }),
+            }
+        }
+    }
+
+    /// Per spec's `IsValidImplementation()`
+    fn validate_implements<'field, TField: FieldInterface + 'field>(
+        &self,
+        type_name: &str,
+        implements: &[String],
+        errors: &mut Vec<Error>,
+        get_field: impl Fn(&str) -> Option<&'field TField>,
+    ) {
+        for interface_name in implements {
+            let Some(TypeOrUnionOrInterface::Interface(interface)) =
+                self.named_types.get(&**interface_name)
+            else {
+                errors.push(Error::UnknownInterface {
+                    type_name: type_name.to_owned(),
+                    interface: interface_name.clone(),
+                });
+                continue;
+            };
+            for transitive_interface in &interface.implements {
+                if !implements.contains(transitive_interface) {
+                    errors.push(Error::MissingTransitiveInterface {
+                        type_name: type_name.to_owned(),
+                        interface: interface_name.clone(),
+                        transitive_interface: transitive_interface.clone(),
+                    });
+                }
+            }
+            for interface_field in interface.fields.values() {
+                let Some(field) = get_field(&interface_field.name) else {
+                    errors.push(Error::MissingInterfaceField {
+                        type_name: type_name.to_owned(),
+                        interface: interface_name.clone(),
+                        field: interface_field.name.clone(),
+                    });
+                    continue;
+                };
+                if !self.is_valid_implementation_field_type(field.type_(), &interface_field.type_) {
+                    errors.push(Error::IncompatibleInterfaceField {
+                        type_name: type_name.to_owned(),
+                        interface: interface_name.clone(),
+                        field: interface_field.name.clone(),
+                    });
+                }
+                for interface_param in interface_field.params.values() {
+                    if field
+                        .params()
+                        .get(&interface_param.name)
+                        .is_none_or(|param| param.type_ != interface_param.type_)
+                    {
+                        errors.push(Error::IncompatibleInterfaceFieldArgument {
+                            type_name: type_name.to_owned(),
+                            interface: interface_name.clone(),
+                            field: interface_field.name.clone(),
+                            argument: interface_param.name.clone(),
+                        });
+                    }
+                }
+                for param in field.params().values() {
+                    if param.is_required() && !interface_field.params.contains_key(&param.name) {
+                        errors.push(Error::RequiredExtraFieldArgument {
+                            type_name: type_name.to_owned(),
+                            interface: interface_name.clone(),
+                            field: interface_field.name.clone(),
+                            argument: param.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Per spec's `IsValidImplementationFieldType()`
+    fn is_valid_implementation_field_type(
+        &self,
+        field_type: &TypeFull,
+        implemented_field_type: &TypeFull,
+    ) -> bool {
+        match (field_type, implemented_field_type) {
+            (TypeFull::NonNull(field_type), TypeFull::NonNull(implemented_field_type)) => {
+                self.is_valid_implementation_field_type(field_type, implemented_field_type)
+            }
+            (TypeFull::NonNull(field_type), implemented_field_type) => {
+                self.is_valid_implementation_field_type(field_type, implemented_field_type)
+            }
+            (TypeFull::List(field_type), TypeFull::List(implemented_field_type)) => {
+                self.is_valid_implementation_field_type(field_type, implemented_field_type)
+            }
+            (TypeFull::Type(field_type), TypeFull::Type(implemented_field_type)) => {
+                field_type == implemented_field_type
+                    || self.is_subtype(field_type, implemented_field_type)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_subtype(&self, type_name: &str, supertype_name: &str) -> bool {
+        match (
+            self.named_types.get(type_name),
+            self.named_types.get(supertype_name),
+        ) {
+            (
+                Some(TypeOrUnionOrInterface::Type(Type::Object(_))),
+                Some(TypeOrUnionOrInterface::Union(union)),
+            ) => union.types.iter().any(|member| member == type_name),
+            (
+                Some(TypeOrUnionOrInterface::Type(Type::Object(ObjectType { implements, .. })))
+                | Some(TypeOrUnionOrInterface::Interface(Interface { implements, .. })),
+                Some(TypeOrUnionOrInterface::Interface(_)),
+            ) => implements
+                .iter()
+                .any(|implement| implement == supertype_name),
+            _ => false,
+        }
+    }
+}
+
+fn is_output_type(named_type: TypeOrUnionOrInterface<'_>) -> bool {
+    !matches!(
+        named_type,
+        TypeOrUnionOrInterface::Type(Type::InputObject(_))
+    )
+}
+
+fn is_input_type(named_type: TypeOrUnionOrInterface<'_>) -> bool {
+    matches!(
+        named_type,
+        TypeOrUnionOrInterface::Type(Type::Scalar(_) | Type::Enum(_) | Type::InputObject(_))
+    )
+}
+
+fn validate_field_dependencies(type_name: &str, field: &TypeField, errors: &mut Vec<Error>) {
+    let coordinate = || format!("{type_name}.{}", field.name);
+    let resolver = &field.resolver;
+    let mut dependency_names: HashSet<&str> = resolver
+        .external_dependencies
+        .iter()
+        .map(|external_dependency| &*external_dependency.name)
+        .collect();
+    let mut internal_dependency_names: HashSet<&str> = HashSet::new();
+    for internal_dependency in &resolver.internal_dependencies {
+        for argument in internal_dependency.resolver.argument_names() {
+            if !field.params.contains_key(argument) {
+                errors.push(Error::UnknownArgument {
+                    coordinate: coordinate(),
+                    argument: argument.to_owned(),
+                });
+            }
+        }
+        for dependency in internal_dependency.resolver.internal_dependency_names() {
+            if !dependency_names.contains(dependency) {
+                errors.push(Error::UnknownDependency {
+                    coordinate: coordinate(),
+                    dependency: dependency.to_owned(),
+                });
+            }
+        }
+        dependency_names.insert(&internal_dependency.name);
+        if !internal_dependency_names.insert(&internal_dependency.name) {
+            errors.push(Error::DuplicateDependency {
+                coordinate: coordinate(),
+                dependency: internal_dependency.name.clone(),
+            });
+        }
+    }
+    for dependency in resolver.carver_or_populator.dependency_names() {
+        if !dependency_names.contains(&*dependency) {
+            errors.push(Error::UnknownDependency {
+                coordinate: coordinate(),
+                dependency,
+            });
+        }
+    }
+}
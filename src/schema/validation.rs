@@ -1,12 +1,13 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 use squalid::{OptionExt, _d};
 
 use crate::{
     Directive, ExecutableDefinition, FieldInterface, FragmentDefinition, FragmentSpread,
-    InlineFragment, Location, OperationDefinition, PositionsTracker, Request, Schema, Selection,
-    SelectionField, Type, TypeFull, TypeOrInterfaceField, TypeOrUnionOrInterface, Value,
+    InlineFragment, Location, OperationDefinition, OperationType, PositionsTracker, Request,
+    ScalarType, Schema, Selection, SelectionField, Type, TypeFull, TypeOrInterfaceField,
+    TypeOrUnionOrInterface, Value, VariableDefinition,
 };
 
 impl Schema {
@@ -17,6 +18,10 @@ impl Schema {
         if let Some(error) = validate_lone_anonymous_operation(request) {
             return vec![error].into();
         }
+        let errors = validate_operation_types_supported(request, self);
+        if !errors.is_empty() {
+            return errors.into();
+        }
         let errors = validate_type_names_exist(request, self);
         if !errors.is_empty() {
             return errors.into();
@@ -49,6 +54,10 @@ impl Schema {
         if !errors.is_empty() {
             return errors.into();
         }
+        let errors = validate_argument_values(request, self);
+        if !errors.is_empty() {
+            return errors.into();
+        }
         if let Some(error) = validate_fragment_name_uniqueness(request) {
             return vec![error].into();
         }
@@ -64,9 +73,27 @@ impl Schema {
         if !errors.is_empty() {
             return errors.into();
         }
+        let errors = validate_subscription_single_root_field(request);
+        if !errors.is_empty() {
+            return errors.into();
+        }
+        let errors = validate_variable_name_uniqueness(request);
+        if !errors.is_empty() {
+            return errors.into();
+        }
+        let errors = validate_variable_usages(request, self);
+        if !errors.is_empty() {
+            return errors.into();
+        }
 
         ValidatedRequest::new().into()
     }
+
+    /// Not part of `validate()` (selecting a deprecated field is valid), but
+    /// available as a warning via `RequestOptions::warn_on_deprecated_fields`
+    pub fn validate_deprecated_fields(&self, request: &Request) -> Vec<ValidationError> {
+        collect_typed(&DeprecatedFieldsCollector::default(), request, self)
+    }
 }
 
 fn validate_operation_name_uniqueness(request: &Request) -> Option<ValidationError> {
@@ -171,6 +198,32 @@ fn validate_lone_anonymous_operation(request: &Request) -> Option<ValidationErro
     ))
 }
 
+fn validate_operation_types_supported(request: &Request, schema: &Schema) -> Vec<ValidationError> {
+    request
+        .document
+        .definitions
+        .iter()
+        .filter_map(|definition| definition.maybe_as_operation_definition())
+        .enumerate()
+        .filter(|(_, operation_definition)| {
+            schema
+                .maybe_type_name_for_operation_type(operation_definition.operation_type)
+                .is_none()
+        })
+        .map(|(index, operation_definition)| {
+            ValidationError::new(
+                format!(
+                    "Schema doesn't support operation type: `{}`",
+                    operation_definition.operation_type
+                ),
+                PositionsTracker::current()
+                    .map(|positions_tracker| vec![positions_tracker.nth_operation_location(index)])
+                    .unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
 trait Collector<TItem, TCollection: FromIterator<TItem> + IntoIterator<Item = TItem> + Default> {
     fn visit_operation(
         &self,
@@ -443,18 +496,7 @@ fn collect_typed_selection_set<
         .into_iter()
         .flat_map(|selection| match selection {
             Selection::Field(field) => {
-                let field_type: TypeOrInterfaceField<'_> = match enclosing_type {
-                    TypeOrUnionOrInterface::Type(type_) => {
-                        type_.as_object().field(&field.name).into()
-                    }
-                    TypeOrUnionOrInterface::Interface(interface) => {
-                        interface.field(&field.name).into()
-                    }
-                    TypeOrUnionOrInterface::Union(_) => {
-                        assert!(field.name == "__typename");
-                        (&schema.dummy_union_typename_field).into()
-                    }
-                };
+                let field_type = get_type_or_interface_field(enclosing_type, &field.name, schema);
                 let (errors, should_recurse) =
                     collector.visit_field(field, field_type, schema, request);
                 if !should_recurse {
@@ -503,6 +545,21 @@ fn collect_typed_selection_set<
         .collect()
 }
 
+fn get_type_or_interface_field<'a>(
+    enclosing_type: TypeOrUnionOrInterface<'a>,
+    field_name: &str,
+    schema: &'a Schema,
+) -> TypeOrInterfaceField<'a> {
+    match enclosing_type {
+        TypeOrUnionOrInterface::Type(type_) => type_.as_object().field(field_name).into(),
+        TypeOrUnionOrInterface::Interface(interface) => interface.field(field_name).into(),
+        TypeOrUnionOrInterface::Union(_) => {
+            assert!(field_name == "__typename");
+            (&schema.dummy_union_typename_field).into()
+        }
+    }
+}
+
 fn validate_type_names_exist(request: &Request, schema: &Schema) -> Vec<ValidationError> {
     collect(&TypeNamesExistCollector::default(), request, schema)
 }
@@ -1103,7 +1160,7 @@ impl CollectorTyped<ValidationError, Vec<ValidationError>> for RequiredArgumentC
                         type_field
                             .params()
                             .into_iter()
-                            .filter(|(_, param)| matches!(param.type_, TypeFull::NonNull(_)))
+                            .filter(|(_, param)| param.is_required())
                             .filter(|(name, _)| {
                                 !field.arguments.as_ref().is_some_and(|arguments| {
                                     arguments.into_iter().any(|argument| {
@@ -1190,6 +1247,133 @@ fn required_argument_validation_error(name: &str, location: Option<Location>) ->
     )
 }
 
+fn validate_argument_values(request: &Request, schema: &Schema) -> Vec<ValidationError> {
+    collect_typed(&ArgumentValuesCollector::default(), request, schema)
+}
+
+#[derive(Default)]
+struct ArgumentValuesCollector {}
+
+impl CollectorTyped<ValidationError, Vec<ValidationError>> for ArgumentValuesCollector {
+    fn visit_field(
+        &self,
+        field: &SelectionField,
+        type_field: TypeOrInterfaceField<'_>,
+        schema: &Schema,
+        request: &Request,
+    ) -> (Vec<ValidationError>, bool) {
+        (
+            field
+                .arguments
+                .iter()
+                .flatten()
+                .enumerate()
+                .filter_map(|(index, argument)| {
+                    let message = get_value_error(
+                        &argument.value,
+                        &type_field.params()[&argument.name].type_,
+                        schema,
+                    )?;
+                    Some(ValidationError::new(
+                        format!("Invalid value for argument `{}`: {message}", argument.name),
+                        PositionsTracker::current()
+                            .map(|positions_tracker| {
+                                positions_tracker.field_nth_argument_location(
+                                    field,
+                                    index,
+                                    &request.document,
+                                )
+                            })
+                            .into_iter()
+                            .collect(),
+                    ))
+                })
+                .collect(),
+            true,
+        )
+    }
+}
+
+fn get_value_error(value: &Value, type_: &TypeFull, schema: &Schema) -> Option<String> {
+    match (value, type_) {
+        // checked against the variable's type instead
+        (Value::Variable(_), _) => None,
+        // missing required arguments get checked separately
+        (Value::Null, _) => None,
+        (value, TypeFull::NonNull(type_)) => get_value_error(value, type_, schema),
+        (Value::List(values), TypeFull::List(item_type)) => values
+            .iter()
+            .find_map(|value| get_value_error(value, item_type, schema)),
+        // per spec, a non-list value gets coerced to a list of one
+        (value, TypeFull::List(item_type)) => get_value_error(value, item_type, schema),
+        (value, TypeFull::Type(type_name)) => match schema.maybe_type(type_name) {
+            Some(Type::Enum(enum_type)) => match value {
+                Value::Enum(name) if enum_type.has_value(name) => None,
+                Value::Enum(name) => Some(format!("`{name}` isn't a value of enum `{type_name}`")),
+                _ => Some(format!("Expected a value of enum `{type_name}`")),
+            },
+            Some(Type::InputObject(input_object_type)) => {
+                let Value::Object(fields) = value else {
+                    return Some(format!("Expected an object of input type `{type_name}`"));
+                };
+                fields
+                    .iter()
+                    .map(|(name, _)| name)
+                    .duplicates()
+                    .next()
+                    .map(|name| format!("Duplicate field `{name}` of input type `{type_name}`"))
+                    .or_else(|| {
+                        fields.iter().find_map(|(name, value)| {
+                            match input_object_type.fields.get(name) {
+                                None => Some(format!(
+                                    "Non-existent field `{name}` of input type `{type_name}`"
+                                )),
+                                Some(input_field) => {
+                                    get_value_error(value, &input_field.type_, schema)
+                                }
+                            }
+                        })
+                    })
+                    .or_else(|| {
+                        input_object_type
+                            .fields
+                            .values()
+                            .filter(|input_field| input_field.is_required())
+                            .find(|input_field| {
+                                !fields.iter().any(|(name, value)| {
+                                    *name == input_field.name && *value != Value::Null
+                                })
+                            })
+                            .map(|input_field| {
+                                format!(
+                                    "Missing required field `{}` of input type `{type_name}`",
+                                    input_field.name
+                                )
+                            })
+                    })
+            }
+            Some(Type::Scalar(ScalarType::Custom(custom_scalar))) => {
+                custom_scalar.parse_value(value).err()
+            }
+            Some(_) => (!is_valid_builtin_scalar_value(value, type_name))
+                .then(|| format!("Expected a value of type `{type_name}`")),
+            None => None,
+        },
+    }
+}
+
+fn is_valid_builtin_scalar_value(value: &Value, type_name: &str) -> bool {
+    matches!(
+        (type_name, value),
+        ("String", Value::String(_))
+            | ("Int", Value::Int(_))
+            // per spec, Int input values are accepted for Float
+            | ("Float", Value::Float(_) | Value::Int(_))
+            | ("Boolean", Value::Bool(_))
+            | ("ID", Value::String(_) | Value::Int(_))
+    )
+}
+
 fn validate_fragment_name_uniqueness(request: &Request) -> Option<ValidationError> {
     let mut duplicates = request
         .document
@@ -1636,6 +1820,418 @@ fn directive_duplicate_validation_error(
     )
 }
 
+#[derive(Default)]
+struct DeprecatedFieldsCollector {}
+
+impl CollectorTyped<ValidationError, Vec<ValidationError>> for DeprecatedFieldsCollector {
+    fn visit_field(
+        &self,
+        field: &SelectionField,
+        type_field: TypeOrInterfaceField<'_>,
+        _schema: &Schema,
+        request: &Request,
+    ) -> (Vec<ValidationError>, bool) {
+        (
+            type_field
+                .deprecation_reason()
+                .map(|deprecation_reason| {
+                    ValidationError::new(
+                        format!("Field `{}` is deprecated: {deprecation_reason}", field.name),
+                        PositionsTracker::current()
+                            .map(|positions_tracker| {
+                                vec![positions_tracker.field_location(field, &request.document)]
+                            })
+                            .unwrap_or_default(),
+                    )
+                })
+                .into_iter()
+                .collect(),
+            true,
+        )
+    }
+}
+
+fn validate_subscription_single_root_field(request: &Request) -> Vec<ValidationError> {
+    request
+        .document
+        .definitions
+        .iter()
+        .filter_map(|definition| definition.maybe_as_operation_definition())
+        .enumerate()
+        .filter(|(_, operation_definition)| {
+            operation_definition.operation_type == OperationType::Subscription
+        })
+        .filter_map(|(index, operation_definition)| {
+            let root_fields = get_root_fields(&operation_definition.selection_set, request);
+            let subscription_description = match operation_definition.name.as_ref() {
+                Some(name) => format!("Subscription `{name}`"),
+                None => "Anonymous subscription".to_owned(),
+            };
+            let message = if root_fields.len() != 1 {
+                format!("{subscription_description} must select only one top-level field")
+            } else if let Some(introspection_field_name) = root_fields
+                .values()
+                .find(|root_field_name| root_field_name.starts_with("__"))
+            {
+                format!(
+                    "{subscription_description} can't select introspection top-level field `{introspection_field_name}`"
+                )
+            } else {
+                return None;
+            };
+            Some(ValidationError::new(
+                message,
+                PositionsTracker::current()
+                    .map(|positions_tracker| vec![positions_tracker.nth_operation_location(index)])
+                    .unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+/// Field names keyed by response key (per spec, it's the response keys that
+/// get counted)
+fn get_root_fields<'a>(
+    selection_set: &'a [Selection],
+    request: &'a Request,
+) -> HashMap<&'a str, &'a str> {
+    selection_set
+        .iter()
+        .flat_map(|selection| match selection {
+            Selection::Field(field) => {
+                [(field.alias.as_deref().unwrap_or(&field.name), &*field.name)].into()
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                get_root_fields(&inline_fragment.selection_set, request)
+            }
+            Selection::FragmentSpread(fragment_spread) => get_root_fields(
+                &request.fragment(&fragment_spread.name).selection_set,
+                request,
+            ),
+        })
+        .collect()
+}
+
+fn validate_variable_name_uniqueness(request: &Request) -> Vec<ValidationError> {
+    request
+        .document
+        .definitions
+        .iter()
+        .filter_map(|definition| definition.maybe_as_operation_definition())
+        .enumerate()
+        .flat_map(|(operation_index, operation_definition)| {
+            operation_definition
+                .variable_definitions
+                .iter()
+                .enumerate()
+                .into_group_map_by(|(_, variable_definition)| &variable_definition.name)
+                .into_iter()
+                .filter(|(_, variable_definitions)| variable_definitions.len() > 1)
+                .sorted_by_key(|(_, variable_definitions)| variable_definitions[0].0)
+                .map(move |(name, variable_definitions)| {
+                    ValidationError::new(
+                        format!("Non-unique variable names: `${name}`"),
+                        PositionsTracker::current()
+                            .map(|positions_tracker| {
+                                variable_definitions
+                                    .into_iter()
+                                    .map(|(index, _)| {
+                                        positions_tracker
+                                            .nth_operation_nth_variable_definition_location(
+                                                operation_index,
+                                                index,
+                                            )
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    )
+                })
+        })
+        .collect()
+}
+
+fn validate_variable_usages(request: &Request, schema: &Schema) -> Vec<ValidationError> {
+    request
+        .document
+        .definitions
+        .iter()
+        .filter_map(|definition| definition.maybe_as_operation_definition())
+        .enumerate()
+        .flat_map(|(operation_index, operation_definition)| {
+            let variable_usages = get_variable_usages(
+                &operation_definition.selection_set,
+                schema.type_or_union_or_interface(
+                    schema.type_name_for_operation_type(operation_definition.operation_type),
+                ),
+                schema,
+                request,
+                &mut _d(),
+            );
+            variable_usages
+                .iter()
+                .filter_map(|variable_usage| {
+                    let Some(variable_definition) = operation_definition
+                        .variable_definitions
+                        .iter()
+                        .find(|variable_definition| variable_definition.name == variable_usage.name)
+                    else {
+                        return Some(ValidationError::new(
+                            format!("Undefined variable: `${}`", variable_usage.name),
+                            variable_usage.location.into_iter().collect(),
+                        ));
+                    };
+                    let location_type = variable_usage.location_type.as_ref()?;
+                    (!is_variable_usage_allowed(variable_definition, location_type)).then(|| {
+                        ValidationError::new(
+                            format!(
+                                "Variable `${}` of type `{}` can't be used where type `{}` is expected",
+                                variable_usage.name, variable_definition.type_, location_type,
+                            ),
+                            variable_usage.location.into_iter().collect(),
+                        )
+                    })
+                })
+                .chain(
+                    operation_definition
+                        .variable_definitions
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, variable_definition)| {
+                            !variable_usages.iter().any(|variable_usage| {
+                                variable_usage.name == variable_definition.name
+                            })
+                        })
+                        .map(|(index, variable_definition)| {
+                            ValidationError::new(
+                                format!("Unused variable: `${}`", variable_definition.name),
+                                PositionsTracker::current()
+                                    .map(|positions_tracker| {
+                                        vec![positions_tracker
+                                            .nth_operation_nth_variable_definition_location(
+                                                operation_index,
+                                                index,
+                                            )]
+                                    })
+                                    .unwrap_or_default(),
+                            )
+                        }),
+                )
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+struct VariableUsage<'a> {
+    name: &'a str,
+    /// `None` if not (yet) known
+    location_type: Option<TypeFull>,
+    location: Option<Location>,
+}
+
+fn get_variable_usages<'a>(
+    selection_set: &'a [Selection],
+    enclosing_type: TypeOrUnionOrInterface<'a>,
+    schema: &'a Schema,
+    request: &'a Request,
+    visited_fragment_names: &mut HashSet<&'a str>,
+) -> Vec<VariableUsage<'a>> {
+    let mut ret: Vec<VariableUsage<'a>> = _d();
+    for selection in selection_set {
+        match selection {
+            Selection::Field(field) => {
+                add_directives_variable_usages(&mut ret, &field.directives, request, schema);
+                let type_field = get_type_or_interface_field(enclosing_type, &field.name, schema);
+                if let Some(arguments) = field.arguments.as_ref() {
+                    for (index, argument) in arguments.iter().enumerate() {
+                        let param = &type_field.params()[&argument.name];
+                        add_value_variable_usages(
+                            &mut ret,
+                            &argument.value,
+                            Some(match &param.type_ {
+                                // per spec, a nullable variable can be used
+                                // for a non-null argument with a default value
+                                TypeFull::NonNull(type_) if param.default_value.is_some() => {
+                                    &**type_
+                                }
+                                type_ => type_,
+                            }),
+                            PositionsTracker::current().map(|positions_tracker| {
+                                positions_tracker.field_nth_argument_location(
+                                    field,
+                                    index,
+                                    &request.document,
+                                )
+                            }),
+                            schema,
+                        );
+                    }
+                }
+                if let Some(selection_set) = field.selection_set.as_ref() {
+                    ret.extend(get_variable_usages(
+                        selection_set,
+                        schema.type_or_union_or_interface(type_field.type_().name()),
+                        schema,
+                        request,
+                        visited_fragment_names,
+                    ));
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                add_directives_variable_usages(
+                    &mut ret,
+                    &inline_fragment.directives,
+                    request,
+                    schema,
+                );
+                ret.extend(get_variable_usages(
+                    &inline_fragment.selection_set,
+                    inline_fragment
+                        .on
+                        .as_ref()
+                        .map(|on| schema.type_or_union_or_interface(on))
+                        .unwrap_or(enclosing_type),
+                    schema,
+                    request,
+                    visited_fragment_names,
+                ));
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                add_directives_variable_usages(
+                    &mut ret,
+                    &fragment_spread.directives,
+                    request,
+                    schema,
+                );
+                if !visited_fragment_names.insert(&fragment_spread.name) {
+                    continue;
+                }
+                let fragment = request.fragment(&fragment_spread.name);
+                ret.extend(get_variable_usages(
+                    &fragment.selection_set,
+                    schema.type_or_union_or_interface(&fragment.on),
+                    schema,
+                    request,
+                    visited_fragment_names,
+                ));
+            }
+        }
+    }
+    ret
+}
+
+fn add_directives_variable_usages<'a>(
+    variable_usages: &mut Vec<VariableUsage<'a>>,
+    directives: &'a [Directive],
+    request: &Request,
+    schema: &Schema,
+) {
+    for directive in directives {
+        for argument in directive.arguments.iter().flatten() {
+            // `@skip`/`@include` (the only supported directives) just
+            // have the `if: Boolean!` argument
+            add_value_variable_usages(
+                variable_usages,
+                &argument.value,
+                Some(&TypeFull::NonNull(Box::new(TypeFull::Type(
+                    "Boolean".to_owned(),
+                )))),
+                PositionsTracker::current().map(|positions_tracker| {
+                    positions_tracker.directive_location(directive, &request.document)
+                }),
+                schema,
+            );
+        }
+    }
+}
+
+fn add_value_variable_usages<'a>(
+    variable_usages: &mut Vec<VariableUsage<'a>>,
+    value: &'a Value,
+    location_type: Option<&TypeFull>,
+    location: Option<Location>,
+    schema: &Schema,
+) {
+    match value {
+        Value::Variable(name) => variable_usages.push(VariableUsage {
+            name,
+            location_type: location_type.cloned(),
+            location,
+        }),
+        Value::List(values) => {
+            let item_type = location_type.and_then(|location_type| match location_type {
+                TypeFull::NonNull(type_) => match &**type_ {
+                    TypeFull::List(item_type) => Some(&**item_type),
+                    _ => None,
+                },
+                TypeFull::List(item_type) => Some(&**item_type),
+                _ => None,
+            });
+            for value in values {
+                add_value_variable_usages(variable_usages, value, item_type, location, schema);
+            }
+        }
+        Value::Object(fields) => {
+            let input_object_type = location_type.and_then(|location_type| {
+                schema
+                    .maybe_type(location_type.name())
+                    .and_then(|type_| type_.maybe_as_input_object())
+            });
+            for (name, value) in fields {
+                let input_field = input_object_type
+                    .and_then(|input_object_type| input_object_type.fields.get(name));
+                let field_type = input_field.map(|input_field| match &input_field.type_ {
+                    // per spec, a nullable variable can be used for a
+                    // non-null field with a default value
+                    TypeFull::NonNull(type_) if input_field.default_value.is_some() => &**type_,
+                    type_ => type_,
+                });
+                add_value_variable_usages(variable_usages, value, field_type, location, schema);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_variable_usage_allowed(
+    variable_definition: &VariableDefinition,
+    location_type: &TypeFull,
+) -> bool {
+    match (&variable_definition.type_, location_type) {
+        // a nullable variable can be used in a non-null position if it has
+        // a (non-null) default value
+        (variable_type, TypeFull::NonNull(location_type))
+            if !matches!(variable_type, TypeFull::NonNull(_)) =>
+        {
+            variable_definition
+                .default_value
+                .as_ref()
+                .is_some_and(|default_value| *default_value != Value::Null)
+                && are_types_compatible(variable_type, location_type)
+        }
+        (variable_type, location_type) => are_types_compatible(variable_type, location_type),
+    }
+}
+
+fn are_types_compatible(variable_type: &TypeFull, location_type: &TypeFull) -> bool {
+    match (variable_type, location_type) {
+        (TypeFull::NonNull(variable_type), TypeFull::NonNull(location_type)) => {
+            are_types_compatible(variable_type, location_type)
+        }
+        (_, TypeFull::NonNull(_)) => false,
+        (TypeFull::NonNull(variable_type), location_type) => {
+            are_types_compatible(variable_type, location_type)
+        }
+        (TypeFull::List(variable_type), TypeFull::List(location_type)) => {
+            are_types_compatible(variable_type, location_type)
+        }
+        (TypeFull::Type(variable_type_name), TypeFull::Type(location_type_name)) => {
+            variable_type_name == location_type_name
+        }
+        _ => false,
+    }
+}
+
 #[derive(Debug)]
 pub struct ValidationError {
     pub message: String,
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value as JsonValue};
+use squalid::_d;
+
+use super::arguments::coerce_argument_value;
+use crate::{DependencyValue, IndexMap, OperationDefinition, ResponseError, Schema, TypeFull};
+
+pub fn coerce_variable_values(
+    operation: &OperationDefinition,
+    variables: &Map<String, JsonValue>,
+    schema: &Schema,
+) -> Result<HashMap<String, DependencyValue>, Vec<ResponseError>> {
+    let mut coerced: HashMap<String, DependencyValue> = _d();
+    let mut errors: Vec<ResponseError> = _d();
+    for variable_definition in &operation.variable_definitions {
+        let name = &variable_definition.name;
+        let type_ = &variable_definition.type_;
+        match variables.get(name) {
+            None => match variable_definition.default_value.as_ref() {
+                Some(default_value) => {
+                    match coerce_argument_value(default_value, type_, schema, &_d()) {
+                        Ok(default_value) => {
+                            coerced.insert(name.clone(), default_value);
+                        }
+                        Err(error) => {
+                            errors.push(ResponseError::new(
+                                format!(
+                                    "Variable `${name}` got invalid default value: {}",
+                                    error.message
+                                ),
+                                _d(),
+                            ));
+                        }
+                    }
+                }
+                None if matches!(type_, TypeFull::NonNull(_)) => {
+                    errors.push(ResponseError::new(
+                        format!("Variable `${name}` of required type `{type_}` was not provided"),
+                        _d(),
+                    ));
+                }
+                None => {}
+            },
+            Some(value) => match coerce_value(value, type_, schema) {
+                Some(value) => {
+                    coerced.insert(name.clone(), value);
+                }
+                None => {
+                    errors.push(ResponseError::new(
+                        format!(
+                            "Variable `${name}` got invalid value `{value}`; expected type `{type_}`"
+                        ),
+                        _d(),
+                    ));
+                }
+            },
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(coerced)
+}
+
+fn coerce_value(value: &JsonValue, type_: &TypeFull, schema: &Schema) -> Option<DependencyValue> {
+    match (type_, value) {
+        (TypeFull::NonNull(_), JsonValue::Null) => None,
+        (TypeFull::NonNull(type_), value) => coerce_value(value, type_, schema),
+        (_, JsonValue::Null) => Some(DependencyValue::Null),
+        (TypeFull::List(item_type), JsonValue::Array(values)) => values
+            .iter()
+            .map(|value| coerce_value(value, item_type, schema))
+            .collect::<Option<Vec<_>>>()
+            .map(DependencyValue::List),
+        // per spec, a non-list value gets coerced to a list of one
+        (TypeFull::List(item_type), value) => {
+            coerce_value(value, item_type, schema).map(|value| DependencyValue::List(vec![value]))
+        }
+        (TypeFull::Type(type_name), value) => coerce_named_type_value(value, type_name, schema),
+    }
+}
+
+fn coerce_named_type_value(
+    value: &JsonValue,
+    type_name: &str,
+    schema: &Schema,
+) -> Option<DependencyValue> {
+    if let Some(custom_scalar) = schema.maybe_custom_scalar(type_name) {
+        return custom_scalar.parse_variable_value(value).ok();
+    }
+    if let Some(input_object_type) = schema
+        .maybe_type(type_name)
+        .and_then(|type_| type_.maybe_as_input_object())
+    {
+        let JsonValue::Object(fields) = value else {
+            return None;
+        };
+        if fields
+            .keys()
+            .any(|name| !input_object_type.fields.contains_key(name))
+        {
+            return None;
+        }
+        return input_object_type
+            .fields
+            .values()
+            .filter_map(|input_field| {
+                let value = match (fields.get(&input_field.name), &input_field.default_value) {
+                    (None, None) if input_field.is_required() => return Some(None),
+                    (None, None) => return None,
+                    (None, Some(default_value)) => {
+                        coerce_argument_value(default_value, &input_field.type_, schema, &_d()).ok()
+                    }
+                    (Some(value), _) => coerce_value(value, &input_field.type_, schema),
+                };
+                Some(value.map(|value| (input_field.name.clone(), value)))
+            })
+            .collect::<Option<IndexMap<_, _>>>()
+            .map(DependencyValue::Object);
+    }
+    if let Some(enum_type) = schema
+        .maybe_type(type_name)
+        .and_then(|type_| type_.maybe_as_enum())
+    {
+        return match value {
+            JsonValue::String(value) if enum_type.has_value(value) => {
+                Some(DependencyValue::String(value.clone()))
+            }
+            _ => None,
+        };
+    }
+    match (type_name, value) {
+        ("String", JsonValue::String(value)) => Some(DependencyValue::String(value.clone())),
+        // `ID`s are numeric row ids
+        ("ID", JsonValue::String(value)) => value.parse().ok().map(DependencyValue::Id),
+        ("ID", JsonValue::Number(value)) => coerce_int_value(value).map(DependencyValue::Id),
+        ("Boolean", JsonValue::Bool(value)) => Some(DependencyValue::Boolean(*value)),
+        ("Int", JsonValue::Number(value)) => coerce_int_value(value).map(DependencyValue::Int),
+        ("Float", JsonValue::Number(value)) => value.as_f64().map(DependencyValue::Float),
+        _ => None,
+    }
+}
+
+fn coerce_int_value(value: &serde_json::Number) -> Option<i32> {
+    value.as_i64().and_then(|value| i32::try_from(value).ok())
+}
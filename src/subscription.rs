@@ -0,0 +1,36 @@
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::broadcast;
+
+use crate::ExternalDependencyValues;
+
+/// Backs a subscription root field: each emitted event becomes the external
+/// dependency values that the field's resolver gets run with
+pub trait SubscriptionSource: Send + Sync {
+    fn subscribe(&self) -> BoxStream<'static, ExternalDependencyValues>;
+}
+
+pub struct BroadcastSubscriptionSource {
+    sender: broadcast::Sender<ExternalDependencyValues>,
+}
+
+impl BroadcastSubscriptionSource {
+    pub fn new(sender: broadcast::Sender<ExternalDependencyValues>) -> Self {
+        Self { sender }
+    }
+}
+
+impl SubscriptionSource for BroadcastSubscriptionSource {
+    fn subscribe(&self) -> BoxStream<'static, ExternalDependencyValues> {
+        stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    // a subscriber that falls behind just misses those events
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+}
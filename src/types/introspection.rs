@@ -0,0 +1,683 @@
+use crate::{
+    ArgumentInternalDependencyResolver, BooleanCarver, Carver, CarverOrPopulator, DependencyType,
+    DependencyValue, EnumCarver, EnumTypeBuilder, EnumValueBuilder, ExternalDependency,
+    ExternalDependencyValues, FieldResolver, FieldResult, InternalDependency,
+    InternalDependencyResolver, InternalDependencyValues, LiteralValueInternalDependencyResolver,
+    ObjectTypeBuilder, OptionalPopulator, OptionalPopulatorList, Param, ResponseValue,
+    StringCarver, Type,
+};
+
+use super::{Field, FieldBuilder, TypeFull};
+use crate::resolve::get_dependency_value;
+
+pub(super) fn introspection_types() -> [Type; 8] {
+    [
+        introspection_schema_type(),
+        introspection_type_type(),
+        introspection_field_type(),
+        introspection_input_value_type(),
+        introspection_enum_value_type(),
+        introspection_directive_type(),
+        introspection_type_kind_type(),
+        introspection_directive_location_type(),
+    ]
+}
+
+fn introspection_schema_type() -> Type {
+    Type::Object(
+        ObjectTypeBuilder::default()
+            .name("__Schema")
+            .fields([
+                string_field("description", "description"),
+                external_field(
+                    "types",
+                    non_null(list(non_null(named("__Type")))),
+                    "types",
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionTypesPopulatorList::new("types"),
+                    )),
+                ),
+                external_field(
+                    "queryType",
+                    non_null(named("__Type")),
+                    "query_type",
+                    CarverOrPopulator::OptionalPopulator(Box::new(
+                        IntrospectionTypePopulator::new("query_type"),
+                    )),
+                ),
+                external_field(
+                    "mutationType",
+                    named("__Type"),
+                    "mutation_type",
+                    CarverOrPopulator::OptionalPopulator(Box::new(
+                        IntrospectionTypePopulator::new("mutation_type"),
+                    )),
+                ),
+                external_field(
+                    "subscriptionType",
+                    named("__Type"),
+                    "subscription_type",
+                    CarverOrPopulator::OptionalPopulator(Box::new(
+                        IntrospectionTypePopulator::new("subscription_type"),
+                    )),
+                ),
+                external_field(
+                    "directives",
+                    non_null(list(non_null(named("__Directive")))),
+                    "directives",
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionObjectsPopulatorList::new("directives"),
+                    )),
+                ),
+            ])
+            .build()
+            .unwrap(),
+    )
+}
+
+fn introspection_type_type() -> Type {
+    Type::Object(
+        ObjectTypeBuilder::default()
+            .name("__Type")
+            .fields([
+                type_field(
+                    "kind",
+                    non_null(named("__TypeKind")),
+                    "kind",
+                    InternalDependencyResolver::IntrospectionTypeKind,
+                    CarverOrPopulator::Carver(Box::new(EnumCarver::new("kind".to_owned()))),
+                )
+                .build()
+                .unwrap(),
+                external_field(
+                    "name",
+                    named("String"),
+                    "type",
+                    CarverOrPopulator::Carver(Box::new(IntrospectionTypeNameCarver {})),
+                ),
+                type_field(
+                    "description",
+                    named("String"),
+                    "description",
+                    InternalDependencyResolver::IntrospectionTypeDescription,
+                    CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                        "description".to_owned(),
+                    ))),
+                )
+                .build()
+                .unwrap(),
+                literal_null_field("specifiedByURL"),
+                type_field(
+                    "fields",
+                    list(non_null(named("__Field"))),
+                    "fields",
+                    InternalDependencyResolver::IntrospectionTypeFields,
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionObjectsPopulatorList::new("fields"),
+                    )),
+                )
+                .params([include_deprecated_param()])
+                .build()
+                .unwrap(),
+                type_field(
+                    "interfaces",
+                    list(non_null(named("__Type"))),
+                    "interfaces",
+                    InternalDependencyResolver::IntrospectionTypeInterfaces,
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionTypesPopulatorList::new("interfaces"),
+                    )),
+                )
+                .build()
+                .unwrap(),
+                type_field(
+                    "possibleTypes",
+                    list(non_null(named("__Type"))),
+                    "possible_types",
+                    InternalDependencyResolver::IntrospectionTypePossibleTypes,
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionTypesPopulatorList::new("possible_types"),
+                    )),
+                )
+                .build()
+                .unwrap(),
+                type_field(
+                    "enumValues",
+                    list(non_null(named("__EnumValue"))),
+                    "enum_values",
+                    InternalDependencyResolver::IntrospectionTypeEnumValues,
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionObjectsPopulatorList::new("enum_values"),
+                    )),
+                )
+                .params([include_deprecated_param()])
+                .build()
+                .unwrap(),
+                type_field(
+                    "inputFields",
+                    list(non_null(named("__InputValue"))),
+                    "input_fields",
+                    InternalDependencyResolver::IntrospectionTypeInputFields,
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionObjectsPopulatorList::new("input_fields"),
+                    )),
+                )
+                .build()
+                .unwrap(),
+                external_field(
+                    "ofType",
+                    named("__Type"),
+                    "type",
+                    CarverOrPopulator::OptionalPopulator(Box::new(IntrospectionOfTypePopulator {})),
+                ),
+            ])
+            .build()
+            .unwrap(),
+    )
+}
+
+fn introspection_field_type() -> Type {
+    Type::Object(
+        ObjectTypeBuilder::default()
+            .name("__Field")
+            .fields([
+                non_null_string_field("name", "name"),
+                string_field("description", "description"),
+                FieldBuilder::default()
+                    .name("args")
+                    .type_(non_null(list(non_null(named("__InputValue")))))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new(
+                            "args".to_owned(),
+                            DependencyType::String,
+                        )],
+                        vec![InternalDependency::new(
+                            "include_deprecated".to_owned(),
+                            DependencyType::Boolean,
+                            InternalDependencyResolver::Argument(
+                                ArgumentInternalDependencyResolver::new(
+                                    "includeDeprecated".to_owned(),
+                                ),
+                            ),
+                        )],
+                        CarverOrPopulator::OptionalPopulatorList(Box::new(
+                            IntrospectionObjectsPopulatorList::new_filtering_deprecated("args"),
+                        )),
+                    ))
+                    .params([include_deprecated_param()])
+                    .build()
+                    .unwrap(),
+                type_reference_field(),
+                boolean_field("isDeprecated", "is_deprecated"),
+                string_field("deprecationReason", "deprecation_reason"),
+            ])
+            .build()
+            .unwrap(),
+    )
+}
+
+fn introspection_input_value_type() -> Type {
+    Type::Object(
+        ObjectTypeBuilder::default()
+            .name("__InputValue")
+            .fields([
+                non_null_string_field("name", "name"),
+                string_field("description", "description"),
+                type_reference_field(),
+                string_field("defaultValue", "default_value"),
+                boolean_field("isDeprecated", "is_deprecated"),
+                string_field("deprecationReason", "deprecation_reason"),
+            ])
+            .build()
+            .unwrap(),
+    )
+}
+
+fn introspection_enum_value_type() -> Type {
+    Type::Object(
+        ObjectTypeBuilder::default()
+            .name("__EnumValue")
+            .fields([
+                non_null_string_field("name", "name"),
+                string_field("description", "description"),
+                boolean_field("isDeprecated", "is_deprecated"),
+                string_field("deprecationReason", "deprecation_reason"),
+            ])
+            .build()
+            .unwrap(),
+    )
+}
+
+fn introspection_directive_type() -> Type {
+    Type::Object(
+        ObjectTypeBuilder::default()
+            .name("__Directive")
+            .fields([
+                non_null_string_field("name", "name"),
+                string_field("description", "description"),
+                external_field(
+                    "locations",
+                    non_null(list(non_null(named("__DirectiveLocation")))),
+                    "locations",
+                    CarverOrPopulator::Carver(Box::new(EnumListCarver {
+                        name: "locations".to_owned(),
+                    })),
+                ),
+                external_field(
+                    "args",
+                    non_null(list(non_null(named("__InputValue")))),
+                    "args",
+                    CarverOrPopulator::OptionalPopulatorList(Box::new(
+                        IntrospectionObjectsPopulatorList::new("args"),
+                    )),
+                ),
+                boolean_field("isRepeatable", "is_repeatable"),
+            ])
+            .build()
+            .unwrap(),
+    )
+}
+
+fn introspection_type_kind_type() -> Type {
+    enum_type(
+        "__TypeKind",
+        [
+            "SCALAR",
+            "OBJECT",
+            "INTERFACE",
+            "UNION",
+            "ENUM",
+            "INPUT_OBJECT",
+            "LIST",
+            "NON_NULL",
+        ],
+    )
+}
+
+fn introspection_directive_location_type() -> Type {
+    enum_type(
+        "__DirectiveLocation",
+        [
+            "QUERY",
+            "MUTATION",
+            "SUBSCRIPTION",
+            "FIELD",
+            "FRAGMENT_DEFINITION",
+            "FRAGMENT_SPREAD",
+            "INLINE_FRAGMENT",
+            "VARIABLE_DEFINITION",
+            "SCHEMA",
+            "SCALAR",
+            "OBJECT",
+            "FIELD_DEFINITION",
+            "ARGUMENT_DEFINITION",
+            "INTERFACE",
+            "UNION",
+            "ENUM",
+            "ENUM_VALUE",
+            "INPUT_OBJECT",
+            "INPUT_FIELD_DEFINITION",
+        ],
+    )
+}
+
+fn enum_type<const N: usize>(name: &str, values: [&str; N]) -> Type {
+    Type::Enum(
+        EnumTypeBuilder::default()
+            .name(name)
+            .values(values.map(|value| EnumValueBuilder::default().name(value).build().unwrap()))
+            .build()
+            .unwrap(),
+    )
+}
+
+fn named(name: &str) -> TypeFull {
+    TypeFull::Type(name.to_owned())
+}
+
+fn list(type_: TypeFull) -> TypeFull {
+    TypeFull::List(Box::new(type_))
+}
+
+fn non_null(type_: TypeFull) -> TypeFull {
+    TypeFull::NonNull(Box::new(type_))
+}
+
+fn include_deprecated_param() -> Param {
+    Param::new("includeDeprecated".to_owned(), named("Boolean"))
+}
+
+/// A field resolved purely from one of the (eagerly-resolved) external
+/// dependencies of its introspection object
+fn external_field(
+    name: &str,
+    type_: TypeFull,
+    external_dependency_name: &str,
+    carver_or_populator: CarverOrPopulator,
+) -> Field {
+    FieldBuilder::default()
+        .name(name)
+        .type_(type_)
+        .resolver(FieldResolver::new(
+            vec![ExternalDependency::new(
+                external_dependency_name.to_owned(),
+                DependencyType::String,
+            )],
+            vec![],
+            carver_or_populator,
+        ))
+        .build()
+        .unwrap()
+}
+
+fn string_field(name: &str, external_dependency_name: &str) -> Field {
+    external_field(
+        name,
+        named("String"),
+        external_dependency_name,
+        CarverOrPopulator::Carver(Box::new(StringCarver::new(
+            external_dependency_name.to_owned(),
+        ))),
+    )
+}
+
+fn non_null_string_field(name: &str, external_dependency_name: &str) -> Field {
+    external_field(
+        name,
+        non_null(named("String")),
+        external_dependency_name,
+        CarverOrPopulator::Carver(Box::new(StringCarver::new(
+            external_dependency_name.to_owned(),
+        ))),
+    )
+}
+
+fn boolean_field(name: &str, external_dependency_name: &str) -> Field {
+    external_field(
+        name,
+        non_null(named("Boolean")),
+        external_dependency_name,
+        CarverOrPopulator::Carver(Box::new(BooleanCarver::new(
+            external_dependency_name.to_owned(),
+        ))),
+    )
+}
+
+fn type_reference_field() -> Field {
+    external_field(
+        "type",
+        non_null(named("__Type")),
+        "type",
+        CarverOrPopulator::OptionalPopulator(Box::new(IntrospectionTypePopulator::new("type"))),
+    )
+}
+
+fn literal_null_field(name: &str) -> Field {
+    FieldBuilder::default()
+        .name(name)
+        .type_(named("String"))
+        .resolver(FieldResolver::new(
+            vec![],
+            vec![InternalDependency::new(
+                "value".to_owned(),
+                DependencyType::String,
+                InternalDependencyResolver::LiteralValue(LiteralValueInternalDependencyResolver(
+                    DependencyValue::Null,
+                )),
+            )],
+            CarverOrPopulator::Carver(Box::new(StringCarver::new("value".to_owned()))),
+        ))
+        .build()
+        .unwrap()
+}
+
+/// A `__Type` field resolved (against the schema) from the type reference
+/// that the `__Type` was populated with
+fn type_field(
+    name: &str,
+    type_: TypeFull,
+    internal_dependency_name: &str,
+    resolver: InternalDependencyResolver,
+    carver_or_populator: CarverOrPopulator,
+) -> FieldBuilder {
+    FieldBuilder::default()
+        .name(name)
+        .type_(type_)
+        .resolver(FieldResolver::new(
+            vec![ExternalDependency::new(
+                "type".to_owned(),
+                DependencyType::String,
+            )],
+            vec![InternalDependency::new(
+                internal_dependency_name.to_owned(),
+                DependencyType::ListOfStrings,
+                resolver,
+            )],
+            carver_or_populator,
+        ))
+}
+
+/// Strips the outermost `!`/`[]` wrapper off a printed type reference
+fn of_type(type_reference: &str) -> Option<&str> {
+    type_reference.strip_suffix('!').or_else(|| {
+        type_reference
+            .strip_prefix('[')
+            .and_then(|type_reference| type_reference.strip_suffix(']'))
+    })
+}
+
+/// Populates a `__Type` from a (possibly `null`) type reference
+pub(super) struct IntrospectionTypePopulator {
+    key: String,
+}
+
+impl IntrospectionTypePopulator {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+        }
+    }
+}
+
+impl OptionalPopulator for IntrospectionTypePopulator {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Option<ExternalDependencyValues>> {
+        Ok(
+            match get_dependency_value(&self.key, external_dependencies, internal_dependencies)? {
+                DependencyValue::Null => None,
+                type_reference => Some(type_reference_values(type_reference.as_string())),
+            },
+        )
+    }
+}
+
+struct IntrospectionTypesPopulatorList {
+    key: String,
+}
+
+impl IntrospectionTypesPopulatorList {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+        }
+    }
+}
+
+impl OptionalPopulatorList for IntrospectionTypesPopulatorList {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Option<Vec<ExternalDependencyValues>>> {
+        Ok(
+            match get_dependency_value(&self.key, external_dependencies, internal_dependencies)? {
+                DependencyValue::Null => None,
+                type_references => Some(
+                    type_references
+                        .as_list()
+                        .iter()
+                        .map(|type_reference| type_reference_values(type_reference.as_string()))
+                        .collect(),
+                ),
+            },
+        )
+    }
+}
+
+struct IntrospectionOfTypePopulator {}
+
+impl OptionalPopulator for IntrospectionOfTypePopulator {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Option<ExternalDependencyValues>> {
+        Ok(of_type(
+            get_dependency_value("type", external_dependencies, internal_dependencies)?.as_string(),
+        )
+        .map(type_reference_values))
+    }
+}
+
+fn type_reference_values(type_reference: &str) -> ExternalDependencyValues {
+    let mut ret = ExternalDependencyValues::default();
+    ret.insert(
+        "type".to_owned(),
+        DependencyValue::String(type_reference.to_owned()),
+    )
+    .unwrap();
+    ret
+}
+
+/// Populates from a (possibly `null`) `DependencyValue::Object` whose
+/// entries become the external dependencies
+pub(super) struct IntrospectionObjectPopulator {
+    key: String,
+}
+
+impl IntrospectionObjectPopulator {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+        }
+    }
+}
+
+impl OptionalPopulator for IntrospectionObjectPopulator {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Option<ExternalDependencyValues>> {
+        Ok(
+            match get_dependency_value(&self.key, external_dependencies, internal_dependencies)? {
+                DependencyValue::Null => None,
+                value => Some(object_values(value)),
+            },
+        )
+    }
+}
+
+/// Populates from a (possibly `null`) list of `DependencyValue::Object`s
+struct IntrospectionObjectsPopulatorList {
+    key: String,
+    /// Whether to skip deprecated objects unless the `include_deprecated`
+    /// internal dependency is `true`
+    filters_deprecated: bool,
+}
+
+impl IntrospectionObjectsPopulatorList {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+            filters_deprecated: false,
+        }
+    }
+
+    pub fn new_filtering_deprecated(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+            filters_deprecated: true,
+        }
+    }
+}
+
+impl OptionalPopulatorList for IntrospectionObjectsPopulatorList {
+    fn populate(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Option<Vec<ExternalDependencyValues>>> {
+        Ok(
+            match get_dependency_value(&self.key, external_dependencies, internal_dependencies)? {
+                DependencyValue::Null => None,
+                values => {
+                    let include_deprecated = !self.filters_deprecated
+                        || internal_dependencies.get("include_deprecated")
+                            == Some(&DependencyValue::Boolean(true));
+                    Some(
+                        values
+                            .as_list()
+                            .iter()
+                            .filter(|value| {
+                                include_deprecated
+                                    || value.as_object().get("is_deprecated")
+                                        != Some(&DependencyValue::Boolean(true))
+                            })
+                            .map(object_values)
+                            .collect(),
+                    )
+                }
+            },
+        )
+    }
+}
+
+fn object_values(value: &DependencyValue) -> ExternalDependencyValues {
+    let mut ret = ExternalDependencyValues::default();
+    for (name, value) in value.as_object() {
+        ret.insert(name.clone(), value.clone()).unwrap();
+    }
+    ret
+}
+
+/// `null` for wrapping (ie list/non-null) types
+struct IntrospectionTypeNameCarver {}
+
+impl Carver for IntrospectionTypeNameCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        let type_reference =
+            get_dependency_value("type", external_dependencies, internal_dependencies)?.as_string();
+        Ok(match of_type(type_reference) {
+            Some(_) => ResponseValue::Null,
+            None => ResponseValue::String(type_reference.clone()),
+        })
+    }
+}
+
+struct EnumListCarver {
+    name: String,
+}
+
+impl Carver for EnumListCarver {
+    fn carve(
+        &self,
+        external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        Ok(ResponseValue::List(
+            get_dependency_value(&self.name, external_dependencies, internal_dependencies)?
+                .as_list()
+                .iter()
+                .map(|value| ResponseValue::EnumValue(value.as_string().clone()))
+                .collect(),
+        ))
+    }
+}
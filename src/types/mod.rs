@@ -1,19 +1,45 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use derive_builder::Builder;
+use rkyv::{Archive, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use squalid::{OptionExt, _d};
 
 use crate::{
     ArgumentInternalDependencyResolver, CarverOrPopulator, DependencyType, DependencyValue,
-    ExternalDependency, FieldResolver, IndexMap, InternalDependency, InternalDependencyResolver,
-    LiteralValueInternalDependencyResolver, OperationType, StringCarver, ValuePopulator,
-    ValuePopulatorList,
+    FieldResolver, IndexMap, InternalDependency, InternalDependencyResolver,
+    LiteralValueInternalDependencyResolver, OperationType, ResponseValue, StringCarver,
+    SubscriptionSource, Value,
 };
 
+mod introspection;
+use introspection::{
+    introspection_types, IntrospectionObjectPopulator, IntrospectionTypePopulator,
+};
+
+/// The reason reported for a `@deprecated` schema element that doesn't
+/// specify one
+pub const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+#[derive(Clone, Debug, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[rkyv(serialize_bounds(
+    __S: rkyv::ser::Writer,
+    __S: rkyv::ser::Allocator,
+    <__S as rkyv::rancor::Fallible>::Error: rkyv::rancor::Source,
+))]
+#[rkyv(deserialize_bounds(
+    <__D as rkyv::rancor::Fallible>::Error: rkyv::rancor::Source,
+))]
+#[rkyv(bytecheck(
+    bounds(
+        __C: rkyv::validation::ArchiveContext,
+        <__C as rkyv::rancor::Fallible>::Error: rkyv::rancor::Source,
+    )
+))]
 pub enum TypeFull {
     Type(String),
-    List(Box<TypeFull>),
-    NonNull(Box<TypeFull>),
+    List(#[rkyv(omit_bounds)] Box<TypeFull>),
+    NonNull(#[rkyv(omit_bounds)] Box<TypeFull>),
 }
 
 impl TypeFull {
@@ -26,9 +52,21 @@ impl TypeFull {
     }
 }
 
+impl fmt::Display for TypeFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Type(name) => write!(f, "{name}"),
+            Self::List(type_full) => write!(f, "[{type_full}]"),
+            Self::NonNull(type_full) => write!(f, "{type_full}!"),
+        }
+    }
+}
+
 pub enum Type {
     Object(ObjectType),
     Scalar(ScalarType),
+    Enum(EnumType),
+    InputObject(InputObjectType),
 }
 
 impl Type {
@@ -39,12 +77,54 @@ impl Type {
         )
     }
 
+    pub fn is_mutation_type(&self) -> bool {
+        matches!(
+            self,
+            Self::Object(type_) if type_.is_mutation_type()
+        )
+    }
+
+    pub fn is_subscription_type(&self) -> bool {
+        matches!(
+            self,
+            Self::Object(type_) if type_.is_subscription_type()
+        )
+    }
+
     pub fn as_object(&self) -> &ObjectType {
         match self {
             Self::Object(object) => object,
             _ => panic!("expected object"),
         }
     }
+
+    pub fn maybe_as_object(&self) -> Option<&ObjectType> {
+        match self {
+            Self::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    pub fn maybe_as_custom_scalar(&self) -> Option<&dyn CustomScalar> {
+        match self {
+            Self::Scalar(ScalarType::Custom(custom_scalar)) => Some(&**custom_scalar),
+            _ => None,
+        }
+    }
+
+    pub fn maybe_as_enum(&self) -> Option<&EnumType> {
+        match self {
+            Self::Enum(enum_type) => Some(enum_type),
+            _ => None,
+        }
+    }
+
+    pub fn maybe_as_input_object(&self) -> Option<&InputObjectType> {
+        match self {
+            Self::InputObject(input_object_type) => Some(input_object_type),
+            _ => None,
+        }
+    }
 }
 
 pub trait TypeInterface {
@@ -56,6 +136,8 @@ impl TypeInterface for Type {
         match self {
             Self::Object(type_) => type_.name(),
             Self::Scalar(type_) => type_.name(),
+            Self::Enum(type_) => type_.name(),
+            Self::InputObject(type_) => type_.name(),
         }
     }
 }
@@ -69,6 +151,8 @@ pub struct ObjectType {
     pub introspection_fields: Option<HashMap<String, Field>>,
     #[builder(setter(into))]
     pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
     #[builder(setter(strip_option), default)]
     pub is_top_level_type: Option<OperationType>,
     // TODO: are the fields on a type ordered?
@@ -99,9 +183,13 @@ impl ObjectTypeBuilder {
             .flatten()
             .if_is(OperationType::Query)
             .map(|_| {
-                [("__type".to_owned(), Field::new_introspection_type())]
-                    .into_iter()
-                    .collect()
+                [
+                    Field::new_introspection_schema(),
+                    Field::new_introspection_type(),
+                ]
+                .into_iter()
+                .map(|field| (field.name.clone(), field))
+                .collect()
             })
     }
 }
@@ -111,11 +199,19 @@ impl ObjectType {
         self.is_top_level_type.is(OperationType::Query)
     }
 
+    pub fn is_mutation_type(&self) -> bool {
+        self.is_top_level_type.is(OperationType::Mutation)
+    }
+
+    pub fn is_subscription_type(&self) -> bool {
+        self.is_top_level_type.is(OperationType::Subscription)
+    }
+
     pub fn maybe_field(&self, name: &str) -> Option<&Field> {
         match name {
             "__typename" => Some(&self.typename_field),
-            "__type" if self.introspection_fields.is_some() => {
-                Some(&self.introspection_fields.as_ref().unwrap()["__type"])
+            "__schema" | "__type" if self.introspection_fields.is_some() => {
+                Some(&self.introspection_fields.as_ref().unwrap()[name])
             }
             name => self.fields.get(name),
         }
@@ -134,24 +230,52 @@ impl TypeInterface for ObjectType {
 
 pub enum ScalarType {
     BuiltIn(BuiltInScalarType),
+    Custom(Box<dyn CustomScalar>),
 }
 
 impl TypeInterface for ScalarType {
     fn name(&self) -> &str {
         match self {
             Self::BuiltIn(type_) => type_.name(),
+            Self::Custom(type_) => type_.name(),
         }
     }
 }
 
+/// A user-defined scalar type (eg `DateTime`), which controls how its
+/// values get serialized into responses and parsed out of requests
+pub trait CustomScalar {
+    fn name(&self) -> &str;
+
+    /// Serializes a (eg `ColumnGetter`-loaded) dependency value into its
+    /// response value. An `Err` (eg for a value of the wrong shape) becomes a
+    /// field error
+    fn serialize(&self, value: &DependencyValue) -> Result<ResponseValue, String>;
+
+    /// Parses an argument value (either a literal or an already-coerced
+    /// variable value). An `Err` for a literal becomes a validation error
+    fn parse_value(&self, value: &Value) -> Result<DependencyValue, String>;
+
+    /// Parses a variable value as provided in the request's JSON variables
+    fn parse_variable_value(&self, value: &JsonValue) -> Result<DependencyValue, String>;
+}
+
 pub enum BuiltInScalarType {
     String(StringType),
+    Int(IntType),
+    Float(FloatType),
+    Boolean(BooleanType),
+    Id(IdType),
 }
 
 impl TypeInterface for BuiltInScalarType {
     fn name(&self) -> &str {
         match self {
             Self::String(type_) => type_.name(),
+            Self::Int(type_) => type_.name(),
+            Self::Float(type_) => type_.name(),
+            Self::Boolean(type_) => type_.name(),
+            Self::Id(type_) => type_.name(),
         }
     }
 }
@@ -170,15 +294,181 @@ impl TypeInterface for StringType {
     }
 }
 
+pub struct IntType {}
+
+impl IntType {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TypeInterface for IntType {
+    fn name(&self) -> &str {
+        "Int"
+    }
+}
+
+pub struct FloatType {}
+
+impl FloatType {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TypeInterface for FloatType {
+    fn name(&self) -> &str {
+        "Float"
+    }
+}
+
+pub struct BooleanType {}
+
+impl BooleanType {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TypeInterface for BooleanType {
+    fn name(&self) -> &str {
+        "Boolean"
+    }
+}
+
+pub struct IdType {}
+
+impl IdType {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TypeInterface for IdType {
+    fn name(&self) -> &str {
+        "ID"
+    }
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct EnumType {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(custom))]
+    pub values: IndexMap<String, EnumValue>,
+}
+
+impl EnumTypeBuilder {
+    pub fn values(self, values: impl IntoIterator<Item = EnumValue>) -> Self {
+        let mut new = self;
+        new.values = Some(
+            values
+                .into_iter()
+                .map(|value| (value.name.clone(), value))
+                .collect(),
+        );
+        new
+    }
+}
+
+impl EnumType {
+    pub fn has_value(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+}
+
+impl TypeInterface for EnumType {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct EnumValue {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
+    /// Deprecated iff set
+    #[builder(setter(into, strip_option), default)]
+    pub deprecation_reason: Option<String>,
+}
+
+impl EnumValue {
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_reason.is_some()
+    }
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct InputObjectType {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
+    #[builder(setter(custom))]
+    pub fields: IndexMap<String, InputField>,
+}
+
+impl InputObjectTypeBuilder {
+    pub fn fields(self, fields: impl IntoIterator<Item = InputField>) -> Self {
+        let mut new = self;
+        new.fields = Some(
+            fields
+                .into_iter()
+                .map(|field| (field.name.clone(), field))
+                .collect(),
+        );
+        new
+    }
+}
+
+impl TypeInterface for InputObjectType {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct InputField {
+    #[builder(setter(into))]
+    pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
+    pub type_: TypeFull,
+    #[builder(setter(strip_option), default)]
+    pub default_value: Option<Value>,
+}
+
+impl InputField {
+    pub fn is_required(&self) -> bool {
+        matches!(self.type_, TypeFull::NonNull(_)) && self.default_value.is_none()
+    }
+}
+
 #[derive(Builder)]
 #[builder(pattern = "owned")]
 pub struct Field {
     #[builder(setter(into))]
     pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
+    /// Deprecated iff set
+    #[builder(setter(into, strip_option), default)]
+    pub deprecation_reason: Option<String>,
     pub type_: TypeFull,
     pub resolver: FieldResolver,
     #[builder(setter(custom), default)]
     pub params: IndexMap<String, Param>,
+    /// Only for fields of the subscription type
+    #[builder(setter(strip_option), default)]
+    pub subscription_source: Option<Box<dyn SubscriptionSource>>,
 }
 
 impl FieldBuilder {
@@ -195,6 +485,10 @@ impl FieldBuilder {
 }
 
 impl Field {
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_reason.is_some()
+    }
+
     pub fn new_typename(type_name: String) -> Self {
         FieldBuilder::default()
             .name("__typename")
@@ -214,25 +508,54 @@ impl Field {
             .unwrap()
     }
 
+    pub fn new_introspection_schema() -> Self {
+        FieldBuilder::default()
+            .name("__schema")
+            .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                "__Schema".to_owned(),
+            ))))
+            .resolver(FieldResolver::new(
+                vec![],
+                vec![InternalDependency::new(
+                    "schema".to_owned(),
+                    DependencyType::Object,
+                    InternalDependencyResolver::IntrospectionSchema,
+                )],
+                CarverOrPopulator::OptionalPopulator(Box::new(IntrospectionObjectPopulator::new(
+                    "schema",
+                ))),
+            ))
+            .build()
+            .unwrap()
+    }
+
     pub fn new_introspection_type() -> Self {
         FieldBuilder::default()
             .name("__type")
             .type_(TypeFull::Type("__Type".to_owned()))
             .resolver(FieldResolver::new(
                 vec![],
-                vec![InternalDependency::new(
-                    "name".to_owned(),
-                    DependencyType::String,
-                    InternalDependencyResolver::Argument(ArgumentInternalDependencyResolver::new(
+                vec![
+                    InternalDependency::new(
                         "name".to_owned(),
-                    )),
-                )],
-                CarverOrPopulator::Populator(Box::new(ValuePopulator::new("name".to_owned()))),
+                        DependencyType::String,
+                        InternalDependencyResolver::Argument(
+                            ArgumentInternalDependencyResolver::new("name".to_owned()),
+                        ),
+                    ),
+                    InternalDependency::new(
+                        "type".to_owned(),
+                        DependencyType::String,
+                        InternalDependencyResolver::IntrospectionTypeByName,
+                    ),
+                ],
+                CarverOrPopulator::OptionalPopulator(Box::new(IntrospectionTypePopulator::new(
+                    "type",
+                ))),
             ))
             .params([Param::new(
                 "name".to_owned(),
-                // TODO: presumably non-null?
-                TypeFull::Type("String".to_owned()),
+                TypeFull::NonNull(Box::new(TypeFull::Type("String".to_owned()))),
             )])
             .build()
             .unwrap()
@@ -261,10 +584,15 @@ pub trait FieldInterface {
 
 pub fn builtin_types() -> HashMap<String, Type> {
     [
-        ("String".to_owned(), string_type()),
-        ("__Type".to_owned(), introspection_type_type()),
+        string_type(),
+        int_type(),
+        float_type(),
+        boolean_type(),
+        id_type(),
     ]
     .into_iter()
+    .chain(introspection_types())
+    .map(|type_| (type_.name().to_owned(), type_))
     .collect()
 }
 
@@ -274,59 +602,45 @@ pub fn string_type() -> Type {
     )))
 }
 
-pub fn introspection_type_type() -> Type {
-    Type::Object(
-        ObjectTypeBuilder::default()
-            .name("__Type")
-            .fields([
-                FieldBuilder::default()
-                    .name("name")
-                    .type_(TypeFull::Type("String".to_owned()))
-                    .resolver(FieldResolver::new(
-                        vec![ExternalDependency::new(
-                            "name".to_owned(),
-                            DependencyType::String,
-                        )],
-                        vec![],
-                        CarverOrPopulator::Carver(Box::new(StringCarver::new("name".to_owned()))),
-                    ))
-                    .build()
-                    .unwrap(),
-                FieldBuilder::default()
-                    .name("interfaces")
-                    .type_(TypeFull::List(Box::new(TypeFull::Type(
-                        "__Type".to_owned(),
-                    ))))
-                    .resolver(FieldResolver::new(
-                        vec![ExternalDependency::new(
-                            "name".to_owned(),
-                            DependencyType::String,
-                        )],
-                        vec![InternalDependency::new(
-                            "names".to_owned(),
-                            DependencyType::ListOfStrings,
-                            InternalDependencyResolver::IntrospectionTypeInterfaces,
-                        )],
-                        CarverOrPopulator::PopulatorList(Box::new(ValuePopulatorList::new(
-                            "name".to_owned(),
-                        ))),
-                    ))
-                    .build()
-                    .unwrap(),
-            ])
-            .build()
-            .unwrap(),
-    )
+pub fn int_type() -> Type {
+    Type::Scalar(ScalarType::BuiltIn(BuiltInScalarType::Int(IntType::new())))
+}
+
+pub fn float_type() -> Type {
+    Type::Scalar(ScalarType::BuiltIn(BuiltInScalarType::Float(
+        FloatType::new(),
+    )))
+}
+
+pub fn boolean_type() -> Type {
+    Type::Scalar(ScalarType::BuiltIn(BuiltInScalarType::Boolean(
+        BooleanType::new(),
+    )))
+}
+
+pub fn id_type() -> Type {
+    Type::Scalar(ScalarType::BuiltIn(BuiltInScalarType::Id(IdType::new())))
 }
 
 pub struct Union {
     pub name: String,
     pub types: Vec<String>,
+    pub description: Option<String>,
 }
 
 impl Union {
     pub fn new(name: String, types: Vec<String>) -> Self {
-        Self { name, types }
+        Self {
+            name,
+            types,
+            description: _d(),
+        }
+    }
+
+    pub fn description(self, description: impl Into<String>) -> Self {
+        let mut new = self;
+        new.description = Some(description.into());
+        new
     }
 }
 
@@ -337,6 +651,8 @@ pub struct Interface {
     pub typename_field: InterfaceField,
     #[builder(setter(into))]
     pub name: String,
+    #[builder(setter(into, strip_option), default)]
+    pub description: Option<String>,
     // TODO: are the fields on a type/interface ordered?
     #[builder(setter(custom))]
     pub fields: IndexMap<String, InterfaceField>,
@@ -378,6 +694,9 @@ pub struct InterfaceField {
     pub name: String,
     pub type_: TypeFull,
     pub params: IndexMap<String, Param>,
+    pub description: Option<String>,
+    /// Deprecated iff set
+    pub deprecation_reason: Option<String>,
 }
 
 impl InterfaceField {
@@ -389,15 +708,29 @@ impl InterfaceField {
                 .into_iter()
                 .map(|param| (param.name.clone(), param))
                 .collect(),
+            description: _d(),
+            deprecation_reason: _d(),
         }
     }
 
     pub fn new_typename() -> Self {
-        Self {
-            name: "__typename".to_owned(),
-            type_: TypeFull::Type("String".to_owned()),
-            params: _d(),
-        }
+        Self::new(
+            "__typename".to_owned(),
+            TypeFull::Type("String".to_owned()),
+            [],
+        )
+    }
+
+    pub fn description(self, description: impl Into<String>) -> Self {
+        let mut new = self;
+        new.description = Some(description.into());
+        new
+    }
+
+    pub fn deprecation_reason(self, deprecation_reason: impl Into<String>) -> Self {
+        let mut new = self;
+        new.deprecation_reason = Some(deprecation_reason.into());
+        new
     }
 }
 
@@ -448,6 +781,24 @@ impl<'a> FieldInterface for TypeOrInterfaceField<'a> {
     }
 }
 
+impl TypeOrInterfaceField<'_> {
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Self::Type(type_) => type_.description.as_deref(),
+            Self::Interface(interface) => interface.description.as_deref(),
+            Self::Union(_) => None,
+        }
+    }
+
+    pub fn deprecation_reason(&self) -> Option<&str> {
+        match self {
+            Self::Type(type_) => type_.deprecation_reason.as_deref(),
+            Self::Interface(interface) => interface.deprecation_reason.as_deref(),
+            Self::Union(_) => None,
+        }
+    }
+}
+
 impl<'a> From<&'a Field> for TypeOrInterfaceField<'a> {
     fn from(value: &'a Field) -> Self {
         Self::Type(value)
@@ -469,11 +820,48 @@ impl<'a> From<&'a DummyUnionTypenameField> for TypeOrInterfaceField<'a> {
 pub struct Param {
     pub name: String,
     pub type_: TypeFull,
+    pub description: Option<String>,
+    /// Used when the argument is omitted
+    pub default_value: Option<Value>,
+    /// Deprecated iff set
+    pub deprecation_reason: Option<String>,
 }
 
 impl Param {
     pub fn new(name: String, type_: TypeFull) -> Self {
-        Self { name, type_ }
+        Self {
+            name,
+            type_,
+            description: _d(),
+            default_value: _d(),
+            deprecation_reason: _d(),
+        }
+    }
+
+    pub fn description(self, description: impl Into<String>) -> Self {
+        let mut new = self;
+        new.description = Some(description.into());
+        new
+    }
+
+    pub fn default_value(self, default_value: Value) -> Self {
+        let mut new = self;
+        new.default_value = Some(default_value);
+        new
+    }
+
+    pub fn deprecation_reason(self, deprecation_reason: impl Into<String>) -> Self {
+        let mut new = self;
+        new.deprecation_reason = Some(deprecation_reason.into());
+        new
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_reason.is_some()
+    }
+
+    pub fn is_required(&self) -> bool {
+        matches!(self.type_, TypeFull::NonNull(_)) && self.default_value.is_none()
     }
 }
 
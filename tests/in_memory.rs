@@ -0,0 +1,937 @@
+use std::{cell::Cell, collections::HashMap};
+
+use futures::future::LocalBoxFuture;
+use sauvignon::{
+    json_from_response, BooleanCarver, Carver, CarverOrPopulator, ColumnGetter, ColumnGetterList,
+    CustomScalarCarver, DataSource, DependencyType, DependencyValue, EnumCarver, EnumTypeBuilder,
+    EnumValueBuilder, FieldResolver, FieldResult, Id, IdCarver, InMemoryDataSource,
+    InternalDependency, InternalDependencyResolver, ListQuery,
+    LiteralValueInternalDependencyResolver, ObjectTypeBuilder, OperationType, ScalarType, Schema,
+    StringCarver, Type, TypeField, TypeFieldBuilder, TypeFull,
+};
+
+mod shared;
+
+use shared::{get_in_memory_data_source, get_offline_schema, pretty_print_json, DecimalScalar};
+
+/// Counts the queries made against the wrapped data source
+struct CountingDataSource {
+    data_source: InMemoryDataSource,
+    query_count: Cell<usize>,
+}
+
+impl DataSource for CountingDataSource {
+    fn get_column_values_by_id<'a>(
+        &'a self,
+        table_name: &'a str,
+        columns: &'a [(String, DependencyType)],
+        row_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<DependencyValue>>>> {
+        self.query_count.set(self.query_count.get() + 1);
+        self.data_source
+            .get_column_values_by_id(table_name, columns, row_ids)
+    }
+
+    fn get_column_values<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_name: &'a str,
+        type_: DependencyType,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<DependencyValue>>> {
+        self.query_count.set(self.query_count.get() + 1);
+        self.data_source
+            .get_column_values(table_name, column_name, type_, list_query)
+    }
+
+    fn get_ids_by_foreign_key<'a>(
+        &'a self,
+        table_name: &'a str,
+        foreign_key_column_name: &'a str,
+        parent_ids: &'a [Id],
+    ) -> LocalBoxFuture<'a, FieldResult<HashMap<Id, Vec<Id>>>> {
+        self.query_count.set(self.query_count.get() + 1);
+        self.data_source
+            .get_ids_by_foreign_key(table_name, foreign_key_column_name, parent_ids)
+    }
+
+    fn get_ids_via_join_table<'a>(
+        &'a self,
+        join_table_name: &'a str,
+        parent_column_name: &'a str,
+        target_column_name: &'a str,
+        target_table_name: &'a str,
+        parent_id: Id,
+        list_query: &'a ListQuery,
+    ) -> LocalBoxFuture<'a, FieldResult<Vec<Id>>> {
+        self.query_count.set(self.query_count.get() + 1);
+        self.data_source.get_ids_via_join_table(
+            join_table_name,
+            parent_column_name,
+            target_column_name,
+            target_table_name,
+            parent_id,
+            list_query,
+        )
+    }
+
+    fn insert_row<'a>(
+        &'a self,
+        table_name: &'a str,
+        column_values: Vec<(String, DependencyValue)>,
+    ) -> LocalBoxFuture<'a, FieldResult<Id>> {
+        self.query_count.set(self.query_count.get() + 1);
+        self.data_source.insert_row(table_name, column_values)
+    }
+}
+
+async fn in_memory_request_test(request: &str, expected: &str) {
+    let schema = get_offline_schema().unwrap();
+    let response = schema.request(request, &get_in_memory_data_source()).await;
+    let json = json_from_response(&response);
+    assert_eq!(pretty_print_json(&json), pretty_print_json(expected));
+}
+
+#[tokio::test]
+async fn test_column_getters() {
+    in_memory_request_test(
+        r#"
+            {
+              actors {
+                name
+                birthYear
+                heightInMeters
+                isMainCast
+                externalId
+                debutedAt
+                salaryPerEpisode
+                castStatus
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy",
+                    "birthYear": 1986,
+                    "heightInMeters": 1.68,
+                    "isMainCast": false,
+                    "externalId": "c1a7f8a2-6b0e-4c38-9d0b-3f1b2a4d5e61",
+                    "debutedAt": "2010-09-13T21:00:00Z",
+                    "salaryPerEpisode": "35000.50",
+                    "castStatus": "RECURRING"
+                  },
+                  {
+                    "name": "Jessica Szohr",
+                    "birthYear": 1985,
+                    "heightInMeters": 1.65,
+                    "isMainCast": true,
+                    "externalId": "5f0c2d4e-8a1b-4e7f-b3c9-0d6e2a9f1b84",
+                    "debutedAt": "2007-09-19T21:00:00Z",
+                    "salaryPerEpisode": "60000.00",
+                    "castStatus": "MAIN"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_union() {
+    in_memory_request_test(
+        r#"
+            {
+              actor(id: 2) {
+                favoriteActorOrDesigner {
+                  ... on Actor {
+                    name
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "favoriteActorOrDesigner": {
+                    "name": "Katie Cassidy"
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_missing_row() {
+    in_memory_request_test(
+        r#"
+            {
+              actor(id: 999) {
+                expression
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "expression": null
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_row_inserter() {
+    in_memory_request_test(
+        r#"
+            mutation {
+              postBlast(input: {content: "Spotted: S and B", sentAt: "2008-05-19T20:00:00-04:00", tone: SCANDAL}) {
+                content
+                sentAt
+                tone
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "postBlast": {
+                  "content": "Spotted: S and B",
+                  "sentAt": "2008-05-20T00:00:00Z",
+                  "tone": "SCANDAL"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_list_conditions_ordering_and_limit() {
+    in_memory_request_test(
+        r#"
+            {
+              actors(orderBy: NAME) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Jessica Szohr"
+                  },
+                  {
+                    "name": "Katie Cassidy"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+    in_memory_request_test(
+        r#"
+            {
+              actors(orderBy: BIRTH_YEAR_DESC, first: 1) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+    in_memory_request_test(
+        r#"
+            {
+              actors(isMainCast: true) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Jessica Szohr"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_has_many_via_foreign_key() {
+    in_memory_request_test(
+        r#"
+            {
+              actors {
+                name
+                leadMovies {
+                  title
+                  releaseYear
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy",
+                    "leadMovies": [
+                      {
+                        "title": "Black Christmas",
+                        "releaseYear": 2006
+                      },
+                      {
+                        "title": "Monte Carlo",
+                        "releaseYear": 2011
+                      }
+                    ]
+                  },
+                  {
+                    "name": "Jessica Szohr",
+                    "leadMovies": [
+                      {
+                        "title": "Piranha 3D",
+                        "releaseYear": 2010
+                      }
+                    ]
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_many_to_many_via_join_table() {
+    in_memory_request_test(
+        r#"
+            {
+              actor(id: 1) {
+                movies(orderBy: RELEASE_YEAR_DESC) {
+                  title
+                  cast {
+                    name
+                  }
+                }
+                movieCount
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "movies": [
+                    {
+                      "title": "Monte Carlo",
+                      "cast": [
+                        {
+                          "name": "Jessica Szohr"
+                        },
+                        {
+                          "name": "Katie Cassidy"
+                        }
+                      ]
+                    },
+                    {
+                      "title": "Black Christmas",
+                      "cast": [
+                        {
+                          "name": "Katie Cassidy"
+                        }
+                      ]
+                    }
+                  ],
+                  "movieCount": 2
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+    in_memory_request_test(
+        r#"
+            {
+              actor(id: 2) {
+                movies(releaseYear: 2010) {
+                  title
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "movies": [
+                    {
+                      "title": "Piranha 3D"
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_connection() {
+    in_memory_request_test(
+        r#"
+            {
+              actorsConnection(first: 1) {
+                edges {
+                  cursor
+                  node {
+                    name
+                  }
+                }
+                pageInfo {
+                  hasNextPage
+                  hasPreviousPage
+                  startCursor
+                  endCursor
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "cursor": "Y3Vyc29yOjE=",
+                      "node": {
+                        "name": "Katie Cassidy"
+                      }
+                    }
+                  ],
+                  "pageInfo": {
+                    "hasNextPage": true,
+                    "hasPreviousPage": false,
+                    "startCursor": "Y3Vyc29yOjE=",
+                    "endCursor": "Y3Vyc29yOjE="
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    in_memory_request_test(
+        r#"
+            {
+              actorsConnection(first: 1, after: "Y3Vyc29yOjE=") {
+                edges {
+                  node {
+                    name
+                  }
+                }
+                pageInfo {
+                  hasNextPage
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ],
+                  "pageInfo": {
+                    "hasNextPage": false
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    in_memory_request_test(
+        r#"
+            {
+              actorsConnection(last: 1) {
+                edges {
+                  node {
+                    name
+                  }
+                }
+                pageInfo {
+                  hasPreviousPage
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ],
+                  "pageInfo": {
+                    "hasPreviousPage": true
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    in_memory_request_test(
+        r#"
+            {
+              actorsConnection(isMainCast: true) {
+                edges {
+                  node {
+                    name
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    in_memory_request_test(
+        r#"
+            {
+              actor(id: 1) {
+                leadMoviesConnection(first: 1, after: "Y3Vyc29yOjE=") {
+                  edges {
+                    node {
+                      title
+                    }
+                  }
+                  pageInfo {
+                    hasNextPage
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "leadMoviesConnection": {
+                    "edges": [
+                      {
+                        "node": {
+                          "title": "Monte Carlo"
+                        }
+                      }
+                    ],
+                    "pageInfo": {
+                      "hasNextPage": false
+                    }
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    in_memory_request_test(
+        r#"
+            {
+              actorsConnection(after: "nope") {
+                edges {
+                  cursor
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid cursor `nope`",
+                  "path": ["actorsConnection"]
+                }
+              ],
+              "data": null
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_has_many_via_foreign_key_batching() {
+    let schema = get_offline_schema().unwrap();
+    let data_source = CountingDataSource {
+        data_source: get_in_memory_data_source(),
+        query_count: Cell::new(0),
+    };
+    let response = schema
+        .request(
+            r#"
+                {
+                  actors {
+                    name
+                    leadMovies {
+                      title
+                    }
+                  }
+                }
+            "#,
+            &data_source,
+        )
+        .await;
+    assert!(response.errors.is_empty());
+    // the actor ids, then the actors' names and lead movie ids, then the
+    // movies' titles
+    assert_eq!(data_source.query_count.get(), 4);
+}
+
+fn get_literal_enum_field(name: &str, value: DependencyValue) -> TypeField {
+    TypeFieldBuilder::default()
+        .name(name)
+        .type_(TypeFull::Type("Mood".to_owned()))
+        .resolver(FieldResolver::new(
+            vec![],
+            vec![InternalDependency::new(
+                "mood".to_owned(),
+                DependencyType::String,
+                InternalDependencyResolver::LiteralValue(LiteralValueInternalDependencyResolver(
+                    value,
+                )),
+            )],
+            CarverOrPopulator::Carver(Box::new(EnumCarver::new("mood".to_owned()))),
+        ))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_invalid_enum_values() {
+    let schema = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([
+                        get_literal_enum_field("mood", DependencyValue::String("HAPPY".to_owned())),
+                        get_literal_enum_field(
+                            "unknownMood",
+                            DependencyValue::String("LOUD".to_owned()),
+                        ),
+                        get_literal_enum_field("nonStringMood", DependencyValue::Int(1)),
+                    ])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Enum(
+                EnumTypeBuilder::default()
+                    .name("Mood")
+                    .values([EnumValueBuilder::default().name("HAPPY").build().unwrap()])
+                    .build()
+                    .unwrap(),
+            ),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let response = schema
+        .request(
+            r#"
+                {
+                  mood
+                  unknownMood
+                  nonStringMood
+                }
+            "#,
+            &get_in_memory_data_source(),
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "errors": [
+                    {
+                      "message": "Invalid value `LOUD` for enum `Mood`",
+                      "path": ["unknownMood"]
+                    },
+                    {
+                      "message": "Expected enum value",
+                      "path": ["nonStringMood"]
+                    }
+                  ],
+                  "data": {
+                    "mood": "HAPPY",
+                    "unknownMood": null,
+                    "nonStringMood": null
+                  }
+                }
+            "#
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_misconfigured_column_getters() {
+    let schema = Schema::try_new(
+        vec![Type::Object(
+            ObjectTypeBuilder::default()
+                .name("Query")
+                .fields([
+                    // there's no parent `id` to look up by
+                    TypeFieldBuilder::default()
+                        .name("actorName")
+                        .type_(TypeFull::Type("String".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "name".to_owned(),
+                                DependencyType::String,
+                                InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                    "actors".to_owned(),
+                                    "name".to_owned(),
+                                )),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                                "name".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap(),
+                    TypeFieldBuilder::default()
+                        .name("actorIds")
+                        .type_(TypeFull::Type("ID".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "ids".to_owned(),
+                                DependencyType::Id,
+                                InternalDependencyResolver::ColumnGetterList(
+                                    ColumnGetterList::new("actors".to_owned(), "id".to_owned()),
+                                ),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(IdCarver::new("ids".to_owned()))),
+                        ))
+                        .build()
+                        .unwrap(),
+                ])
+                .is_top_level_type(OperationType::Query)
+                .build()
+                .unwrap(),
+        )],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let response = schema
+        .request(
+            r#"
+                {
+                  actorName
+                  actorIds
+                }
+            "#,
+            &get_in_memory_data_source(),
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "errors": [
+                    {
+                      "message": "Expected `id` external dependency",
+                      "path": ["actorName"]
+                    },
+                    {
+                      "message": "Expected `ids` to be a list of ids or strings",
+                      "path": ["actorIds"]
+                    }
+                  ],
+                  "data": {
+                    "actorName": null,
+                    "actorIds": null
+                  }
+                }
+            "#
+        )
+    );
+}
+
+fn get_literal_field(
+    name: &str,
+    type_name: &str,
+    value: DependencyValue,
+    carver: Box<dyn Carver>,
+) -> TypeField {
+    TypeFieldBuilder::default()
+        .name(name)
+        .type_(TypeFull::Type(type_name.to_owned()))
+        .resolver(FieldResolver::new(
+            vec![],
+            vec![InternalDependency::new(
+                "value".to_owned(),
+                DependencyType::String,
+                InternalDependencyResolver::LiteralValue(LiteralValueInternalDependencyResolver(
+                    value,
+                )),
+            )],
+            CarverOrPopulator::Carver(carver),
+        ))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_carver_value_mismatches() {
+    let schema = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([
+                        get_literal_field(
+                            "isMainCast",
+                            "Boolean",
+                            DependencyValue::String("yes".to_owned()),
+                            Box::new(BooleanCarver::new("value".to_owned())),
+                        ),
+                        get_literal_field(
+                            "name",
+                            "String",
+                            DependencyValue::Int(1),
+                            Box::new(StringCarver::new("value".to_owned())),
+                        ),
+                        get_literal_field(
+                            "salaryPerEpisode",
+                            "Decimal",
+                            DependencyValue::String("lots".to_owned()),
+                            Box::new(CustomScalarCarver::new(
+                                "value".to_owned(),
+                                DecimalScalar {},
+                            )),
+                        ),
+                    ])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Scalar(ScalarType::Custom(Box::new(DecimalScalar {}))),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let response = schema
+        .request(
+            r#"
+                {
+                  isMainCast
+                  name
+                  salaryPerEpisode
+                }
+            "#,
+            &get_in_memory_data_source(),
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "errors": [
+                    {
+                      "message": "Expected boolean",
+                      "path": ["isMainCast"]
+                    },
+                    {
+                      "message": "Expected string",
+                      "path": ["name"]
+                    },
+                    {
+                      "message": "Expected a decimal",
+                      "path": ["salaryPerEpisode"]
+                    }
+                  ],
+                  "data": {
+                    "isMainCast": null,
+                    "name": null,
+                    "salaryPerEpisode": null
+                  }
+                }
+            "#
+        )
+    );
+}
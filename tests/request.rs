@@ -1,8 +1,20 @@
-use sauvignon::json_from_response;
+use futures::StreamExt;
+use sauvignon::{
+    json_from_response, ArgumentInternalDependencyResolver, CarverOrPopulator, ColumnGetter,
+    CustomScalarCarver, DependencyType, DependencyValue, ExternalDependency,
+    ExternalDependencyValues, FieldResolver, IntCarver, InternalDependency,
+    InternalDependencyResolver, LiteralValueInternalDependencyResolver, ObjectTypeBuilder,
+    OperationType, Param, RequestOptions, RequestOptionsBuilder, ScalarType, Schema, Type,
+    TypeFieldBuilder, TypeFull, ValuePopulator,
+};
+use tokio::sync::broadcast;
 
 mod shared;
 
-use shared::{get_db_pool, get_schema, pretty_print_json};
+use shared::{
+    get_db_pool, get_in_memory_data_source, get_schema, get_schema_with_actor_updates,
+    pretty_print_json, DecimalScalar,
+};
 
 async fn request_test(request: &str, expected: &str) {
     let db_pool = get_db_pool().await.unwrap();
@@ -12,6 +24,40 @@ async fn request_test(request: &str, expected: &str) {
     assert_eq!(pretty_print_json(&json), pretty_print_json(expected));
 }
 
+async fn request_with_options_test(request: &str, options: RequestOptions, expected: &str) {
+    let db_pool = get_db_pool().await.unwrap();
+    let schema = get_schema(&db_pool).await.unwrap();
+    let response = schema
+        .request_with_options(request, &options, &db_pool)
+        .await;
+    let json = json_from_response(&response);
+    assert_eq!(pretty_print_json(&json), pretty_print_json(expected));
+}
+
+async fn request_with_variables_test(request: &str, variables: &str, expected: &str) {
+    request_with_options_test(
+        request,
+        RequestOptionsBuilder::default()
+            .variables(serde_json::from_str(variables).unwrap())
+            .build()
+            .unwrap(),
+        expected,
+    )
+    .await;
+}
+
+async fn request_with_operation_name_test(request: &str, operation_name: &str, expected: &str) {
+    request_with_options_test(
+        request,
+        RequestOptionsBuilder::default()
+            .operation_name(operation_name)
+            .build()
+            .unwrap(),
+        expected,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn test_object_field() {
     request_test(
@@ -351,22 +397,117 @@ async fn test_argument() {
 }
 
 #[tokio::test]
-async fn test_parse_error() {
+async fn test_list_conditions_ordering_and_limit() {
     request_test(
-        r#"query abc 1"#,
         r#"
             {
-              "errors": [
-                {
-                  "message": "Expected selection set",
-                  "locations": [
-                    {
-                      "line": 1,
-                      "column": 11
-                    }
-                  ]
+              actors(orderBy: NAME) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Jessica Szohr"
+                  },
+                  {
+                    "name": "Katie Cassidy"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+    request_test(
+        r#"
+            {
+              actors(orderBy: BIRTH_YEAR_DESC, first: 1) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+    request_test(
+        r#"
+            {
+              actors(isMainCast: true) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Jessica Szohr"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_has_many_via_foreign_key() {
+    request_test(
+        r#"
+            {
+              actors {
+                name
+                leadMovies {
+                  title
+                  releaseYear
                 }
-              ]
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy",
+                    "leadMovies": [
+                      {
+                        "title": "Black Christmas",
+                        "releaseYear": 2006
+                      },
+                      {
+                        "title": "Monte Carlo",
+                        "releaseYear": 2011
+                      }
+                    ]
+                  },
+                  {
+                    "name": "Jessica Szohr",
+                    "leadMovies": [
+                      {
+                        "title": "Piranha 3D",
+                        "releaseYear": 2010
+                      }
+                    ]
+                  }
+                ]
+              }
             }
         "#,
     )
@@ -374,22 +515,74 @@ async fn test_parse_error() {
 }
 
 #[tokio::test]
-async fn test_lex_error() {
+async fn test_many_to_many_via_join_table() {
     request_test(
-        r#""abc"#,
         r#"
             {
-              "errors": [
-                {
-                  "message": "expected closing double-quote",
-                  "locations": [
+              actor(id: 1) {
+                movies(orderBy: RELEASE_YEAR_DESC) {
+                  title
+                  cast {
+                    name
+                  }
+                }
+                movieCount
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "movies": [
                     {
-                      "line": 1,
-                      "column": 4
+                      "title": "Monte Carlo",
+                      "cast": [
+                        {
+                          "name": "Jessica Szohr"
+                        },
+                        {
+                          "name": "Katie Cassidy"
+                        }
+                      ]
+                    },
+                    {
+                      "title": "Black Christmas",
+                      "cast": [
+                        {
+                          "name": "Katie Cassidy"
+                        }
+                      ]
+                    }
+                  ],
+                  "movieCount": 2
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+    request_test(
+        r#"
+            {
+              actor(id: 2) {
+                movies(releaseYear: 2010) {
+                  title
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "movies": [
+                    {
+                      "title": "Piranha 3D"
                     }
                   ]
                 }
-              ]
+              }
             }
         "#,
     )
@@ -397,21 +590,44 @@ async fn test_lex_error() {
 }
 
 #[tokio::test]
-async fn test_skip_include() {
+async fn test_connection() {
     request_test(
         r#"
-            query {
-              actorKatie {
-                name @skip(if: true)
-                expression
+            {
+              actorsConnection(first: 1) {
+                edges {
+                  cursor
+                  node {
+                    name
+                  }
+                }
+                pageInfo {
+                  hasNextPage
+                  hasPreviousPage
+                  startCursor
+                  endCursor
+                }
               }
             }
         "#,
         r#"
             {
               "data": {
-                "actorKatie": {
-                  "expression": "no Serena you can't have the key"
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "cursor": "Y3Vyc29yOjE=",
+                      "node": {
+                        "name": "Katie Cassidy"
+                      }
+                    }
+                  ],
+                  "pageInfo": {
+                    "hasNextPage": true,
+                    "hasPreviousPage": false,
+                    "startCursor": "Y3Vyc29yOjE=",
+                    "endCursor": "Y3Vyc29yOjE="
+                  }
                 }
               }
             }
@@ -421,18 +637,33 @@ async fn test_skip_include() {
 
     request_test(
         r#"
-            query {
-              actorKatie {
-                name @include(if: false)
-                expression
+            {
+              actorsConnection(first: 1, after: "Y3Vyc29yOjE=") {
+                edges {
+                  node {
+                    name
+                  }
+                }
+                pageInfo {
+                  hasNextPage
+                }
               }
             }
         "#,
         r#"
             {
               "data": {
-                "actorKatie": {
-                  "expression": "no Serena you can't have the key"
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ],
+                  "pageInfo": {
+                    "hasNextPage": false
+                  }
                 }
               }
             }
@@ -442,19 +673,33 @@ async fn test_skip_include() {
 
     request_test(
         r#"
-            query {
-              actorKatie {
-                name @skip(if: false)
-                expression @include(if: true)
+            {
+              actorsConnection(last: 1) {
+                edges {
+                  node {
+                    name
+                  }
+                }
+                pageInfo {
+                  hasPreviousPage
+                }
               }
             }
         "#,
         r#"
             {
               "data": {
-                "actorKatie": {
-                  "name": "Katie Cassidy",
-                  "expression": "no Serena you can't have the key"
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ],
+                  "pageInfo": {
+                    "hasPreviousPage": true
+                  }
                 }
               }
             }
@@ -464,22 +709,2277 @@ async fn test_skip_include() {
 
     request_test(
         r#"
-            query {
-              actorKatie {
-                name @include(if: false) @skip(if: false)
-                expression
+            {
+              actorsConnection(isMainCast: true) {
+                edges {
+                  node {
+                    name
+                  }
+                }
               }
             }
         "#,
         r#"
             {
               "data": {
-                "actorKatie": {
-                  "expression": "no Serena you can't have the key"
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              actor(id: 1) {
+                leadMoviesConnection(first: 1, after: "Y3Vyc29yOjE=") {
+                  edges {
+                    node {
+                      title
+                    }
+                  }
+                  pageInfo {
+                    hasNextPage
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "leadMoviesConnection": {
+                    "edges": [
+                      {
+                        "node": {
+                          "title": "Monte Carlo"
+                        }
+                      }
+                    ],
+                    "pageInfo": {
+                      "hasNextPage": false
+                    }
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              actorsConnection(after: "nope") {
+                edges {
+                  cursor
                 }
               }
             }
         "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid cursor `nope`",
+                  "path": ["actorsConnection"]
+                }
+              ],
+              "data": null
+            }
+        "#,
     )
     .await;
 }
+
+#[tokio::test]
+async fn test_connection_null_arguments() {
+    request_test(
+        r#"
+            {
+              actorsConnection(first: 10, after: null) {
+                edges {
+                  node {
+                    name
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Katie Cassidy"
+                      }
+                    },
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              actorsConnection(first: null) {
+                edges {
+                  node {
+                    name
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Katie Cassidy"
+                      }
+                    },
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            query ($after: String) {
+              actorsConnection(first: 10, after: $after) {
+                edges {
+                  node {
+                    name
+                  }
+                }
+              }
+            }
+        "#,
+        r#"{"after": null}"#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Katie Cassidy"
+                      }
+                    },
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_parse_error() {
+    request_test(
+        r#"query abc 1"#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Expected selection set",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 11
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_lex_error() {
+    request_test(
+        r#""abc"#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "expected closing double-quote",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 4
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_skip_include() {
+    request_test(
+        r#"
+            query {
+              actorKatie {
+                name @skip(if: true)
+                expression
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "expression": "no Serena you can't have the key"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            query {
+              actorKatie {
+                name @include(if: false)
+                expression
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "expression": "no Serena you can't have the key"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            query {
+              actorKatie {
+                name @skip(if: false)
+                expression @include(if: true)
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "name": "Katie Cassidy",
+                  "expression": "no Serena you can't have the key"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            query {
+              actorKatie {
+                name @include(if: false) @skip(if: false)
+                expression
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "expression": "no Serena you can't have the key"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_mutation() {
+    request_test(
+        r#"
+            mutation {
+              sendBlast(content: "Spotted: lonely boy") {
+                __typename
+                content
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "sendBlast": {
+                  "__typename": "Blast",
+                  "content": "Spotted: lonely boy"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_subscription() {
+    let db_pool = get_db_pool().await.unwrap();
+    let (actor_updates, _) = broadcast::channel(16);
+    let schema = get_schema_with_actor_updates(&db_pool, actor_updates.clone())
+        .await
+        .unwrap();
+    let mut responses = schema.subscribe(
+        r#"
+            subscription {
+              actorUpdated {
+                name
+              }
+            }
+        "#,
+        &db_pool,
+    );
+
+    for (id, expected) in [
+        (
+            1,
+            r#"
+                {
+                  "data": {
+                    "actorUpdated": {
+                      "name": "Katie Cassidy"
+                    }
+                  }
+                }
+            "#,
+        ),
+        (
+            2,
+            r#"
+                {
+                  "data": {
+                    "actorUpdated": {
+                      "name": "Jessica Szohr"
+                    }
+                  }
+                }
+            "#,
+        ),
+    ] {
+        let mut event = ExternalDependencyValues::default();
+        event
+            .insert("id".to_owned(), DependencyValue::Id(id))
+            .unwrap();
+        actor_updates.send(event).unwrap();
+        let response = responses.next().await.unwrap();
+        let json = json_from_response(&response);
+        assert_eq!(pretty_print_json(&json), pretty_print_json(expected));
+    }
+}
+
+#[tokio::test]
+async fn test_variables() {
+    request_with_variables_test(
+        r#"
+            query ($id: ID!) {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "id": 2
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "name": "Jessica Szohr"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            query ($id: ID = 1) {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {}
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "name": "Katie Cassidy"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            query ($skipName: Boolean!, $includeExpression: Boolean!) {
+              actorKatie {
+                name @skip(if: $skipName)
+                expression @include(if: $includeExpression)
+              }
+            }
+        "#,
+        r#"
+            {
+              "skipName": true,
+              "includeExpression": true
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "expression": "no Serena you can't have the key"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_null_variable() {
+    request_with_variables_test(
+        r#"
+            query ($first: Int) {
+              actors(orderBy: NAME, first: $first) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "first": null
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Jessica Szohr"
+                  },
+                  {
+                    "name": "Katie Cassidy"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_variable_coercion_errors() {
+    request_with_variables_test(
+        r#"
+            query ($id: ID!) {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {}
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Variable `$id` of required type `ID!` was not provided"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            query ($id: ID!) {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "id": null
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Variable `$id` got invalid value `null`; expected type `ID!`"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            query ($skipName: Boolean!) {
+              actorKatie {
+                name @skip(if: $skipName)
+              }
+            }
+        "#,
+        r#"
+            {
+              "skipName": "yes"
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Variable `$skipName` got invalid value `\"yes\"`; expected type `Boolean!`"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_operation_name() {
+    let document = r#"
+        query Katie {
+          actorKatie {
+            name
+          }
+        }
+
+        query Actors {
+          actors {
+            name
+          }
+        }
+    "#;
+
+    request_with_operation_name_test(
+        document,
+        "Actors",
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy"
+                  },
+                  {
+                    "name": "Jessica Szohr"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_operation_name_test(
+        document,
+        "Katie",
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "name": "Katie Cassidy"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_operation_name_test(
+        document,
+        "Whee",
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Unknown operation: `Whee`"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        document,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Must provide operation name if document contains multiple operations"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_builtin_scalars() {
+    request_test(
+        r#"
+            {
+              actorKatie {
+                birthYear
+                heightInMeters
+                isMainCast
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "birthYear": 1986,
+                  "heightInMeters": 1.68,
+                  "isMainCast": false
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              actors {
+                id
+                isMainCast
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "id": "1",
+                    "isMainCast": false
+                  },
+                  {
+                    "id": "2",
+                    "isMainCast": true
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_string_id_arguments() {
+    request_test(
+        r#"
+            {
+              actor(id: "1") {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "name": "Katie Cassidy"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            query ($id: ID!) {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "id": "2"
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "name": "Jessica Szohr"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              actor(id: "katie") {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Expected numeric ID, got `katie`",
+                  "path": ["actor"]
+                }
+              ],
+              "data": {
+                "actor": null
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_custom_scalars() {
+    request_test(
+        r#"
+            {
+              actorKatie {
+                externalId
+                debutedAt
+                metadata
+                salaryPerEpisode
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "externalId": "c1a7f8a2-6b0e-4c38-9d0b-3f1b2a4d5e61",
+                  "debutedAt": "2010-09-13T21:00:00Z",
+                  "metadata": {
+                    "character": "Juliet Sharp",
+                    "seasons": [4]
+                  },
+                  "salaryPerEpisode": "35000.50"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_custom_scalar_arguments() {
+    request_test(
+        r#"
+            mutation {
+              sendBlast(content: "Spotted: S and B", sentAt: "2008-05-19T20:00:00-04:00") {
+                sentAt
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "sendBlast": {
+                  "sentAt": "2008-05-20T00:00:00Z"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            mutation($sentAt: DateTime) {
+              sendBlast(content: "Spotted: S and B", sentAt: $sentAt) {
+                sentAt
+              }
+            }
+        "#,
+        r#"
+            {
+              "sentAt": "2009-12-07T21:00:00Z"
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "sendBlast": {
+                  "sentAt": "2009-12-07T21:00:00Z"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            mutation($sentAt: DateTime) {
+              sendBlast(content: "Spotted: S and B", sentAt: $sentAt) {
+                sentAt
+              }
+            }
+        "#,
+        r#"
+            {
+              "sentAt": "last night"
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Variable `$sentAt` got invalid value `\"last night\"`; expected type `DateTime`"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_custom_scalar_variable_values() {
+    // `DecimalScalar::parse_value()` only accepts (32-bit) `Int` literals
+    // whereas its `parse_variable_value()` accepts any 64-bit integer
+    let schema = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([TypeFieldBuilder::default()
+                        .name("echo")
+                        .type_(TypeFull::Type("Decimal".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "amount".to_owned(),
+                                DependencyType::Decimal,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new("amount".to_owned()),
+                                ),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(CustomScalarCarver::new(
+                                "amount".to_owned(),
+                                DecimalScalar {},
+                            ))),
+                        ))
+                        .params([Param::new(
+                            "amount".to_owned(),
+                            TypeFull::NonNull(Box::new(TypeFull::Type("Decimal".to_owned()))),
+                        )])
+                        .build()
+                        .unwrap()])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Scalar(ScalarType::Custom(Box::new(DecimalScalar {}))),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let response = schema
+        .request_with_options(
+            r#"
+                query ($amount: Decimal!) {
+                  echo(amount: $amount)
+                }
+            "#,
+            &RequestOptionsBuilder::default()
+                .variables(serde_json::from_str(r#"{"amount": 5000000000}"#).unwrap())
+                .build()
+                .unwrap(),
+            &get_in_memory_data_source(),
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "data": {
+                    "echo": "5000000000"
+                  }
+                }
+            "#
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_enum() {
+    request_test(
+        r#"
+            {
+              actors {
+                name
+                castStatus
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy",
+                    "castStatus": "RECURRING"
+                  },
+                  {
+                    "name": "Jessica Szohr",
+                    "castStatus": "MAIN"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            mutation {
+              sendBlast(content: "Spotted: S and B", tone: SCANDAL) {
+                tone
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "sendBlast": {
+                  "tone": "SCANDAL"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            mutation($tone: BlastTone) {
+              sendBlast(content: "Spotted: S and B", tone: $tone) {
+                tone
+              }
+            }
+        "#,
+        r#"
+            {
+              "tone": "SCANDAL"
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "sendBlast": {
+                  "tone": "SCANDAL"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            mutation($tone: BlastTone) {
+              sendBlast(content: "Spotted: S and B", tone: $tone) {
+                tone
+              }
+            }
+        "#,
+        r#"
+            {
+              "tone": "LOUD"
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Variable `$tone` got invalid value `\"LOUD\"`; expected type `BlastTone`"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_enum_values() {
+    request_test(
+        r#"
+            {
+              __type(name: "CastStatus") {
+                enumValues {
+                  name
+                  isDeprecated
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "enumValues": [
+                    {
+                      "name": "MAIN",
+                      "isDeprecated": false
+                    },
+                    {
+                      "name": "RECURRING",
+                      "isDeprecated": false
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              __type(name: "CastStatus") {
+                enumValues(includeDeprecated: true) {
+                  name
+                  isDeprecated
+                  deprecationReason
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "enumValues": [
+                    {
+                      "name": "MAIN",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "RECURRING",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "GUEST",
+                      "isDeprecated": true,
+                      "deprecationReason": "Use `RECURRING`"
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_schema() {
+    request_test(
+        r#"
+            {
+              __schema {
+                queryType {
+                  kind
+                  name
+                }
+                mutationType {
+                  name
+                }
+                subscriptionType {
+                  name
+                }
+                directives {
+                  name
+                  locations
+                  args {
+                    name
+                    type {
+                      kind
+                      ofType {
+                        name
+                      }
+                    }
+                    defaultValue
+                  }
+                  isRepeatable
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__schema": {
+                  "queryType": {
+                    "kind": "OBJECT",
+                    "name": "Query"
+                  },
+                  "mutationType": {
+                    "name": "Mutation"
+                  },
+                  "subscriptionType": {
+                    "name": "Subscription"
+                  },
+                  "directives": [
+                    {
+                      "name": "skip",
+                      "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+                      "args": [
+                        {
+                          "name": "if",
+                          "type": {
+                            "kind": "NON_NULL",
+                            "ofType": {
+                              "name": "Boolean"
+                            }
+                          },
+                          "defaultValue": null
+                        }
+                      ],
+                      "isRepeatable": false
+                    },
+                    {
+                      "name": "include",
+                      "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+                      "args": [
+                        {
+                          "name": "if",
+                          "type": {
+                            "kind": "NON_NULL",
+                            "ofType": {
+                              "name": "Boolean"
+                            }
+                          },
+                          "defaultValue": null
+                        }
+                      ],
+                      "isRepeatable": false
+                    },
+                    {
+                      "name": "deprecated",
+                      "locations": [
+                        "FIELD_DEFINITION",
+                        "ARGUMENT_DEFINITION",
+                        "INPUT_FIELD_DEFINITION",
+                        "ENUM_VALUE"
+                      ],
+                      "args": [
+                        {
+                          "name": "reason",
+                          "type": {
+                            "kind": "SCALAR",
+                            "ofType": null
+                          },
+                          "defaultValue": "\"No longer supported\""
+                        }
+                      ],
+                      "isRepeatable": false
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_type_fields() {
+    request_test(
+        r#"
+            {
+              __type(name: "Designer") {
+                kind
+                name
+                fields {
+                  name
+                  args {
+                    name
+                  }
+                  type {
+                    kind
+                    name
+                    ofType {
+                      name
+                    }
+                  }
+                  isDeprecated
+                }
+                interfaces {
+                  name
+                }
+                possibleTypes {
+                  name
+                }
+                enumValues {
+                  name
+                }
+                inputFields {
+                  name
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "kind": "OBJECT",
+                  "name": "Designer",
+                  "fields": [
+                    {
+                      "name": "name",
+                      "args": [],
+                      "type": {
+                        "kind": "SCALAR",
+                        "name": "String",
+                        "ofType": null
+                      },
+                      "isDeprecated": false
+                    }
+                  ],
+                  "interfaces": [
+                    {
+                      "name": "HasName"
+                    }
+                  ],
+                  "possibleTypes": null,
+                  "enumValues": null,
+                  "inputFields": null
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_possible_types() {
+    request_test(
+        r#"
+            {
+              __type(name: "ActorOrDesigner") {
+                kind
+                possibleTypes {
+                  name
+                }
+                fields {
+                  name
+                }
+                interfaces {
+                  name
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "kind": "UNION",
+                  "possibleTypes": [
+                    {
+                      "name": "Actor"
+                    },
+                    {
+                      "name": "Designer"
+                    }
+                  ],
+                  "fields": null,
+                  "interfaces": null
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              __type(name: "HasName") {
+                kind
+                possibleTypes {
+                  name
+                }
+                fields {
+                  name
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "kind": "INTERFACE",
+                  "possibleTypes": [
+                    {
+                      "name": "Actor"
+                    },
+                    {
+                      "name": "Designer"
+                    }
+                  ],
+                  "fields": [
+                    {
+                      "name": "name"
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_input_fields() {
+    request_test(
+        r#"
+            {
+              __type(name: "StringFilter") {
+                kind
+                inputFields {
+                  name
+                  type {
+                    kind
+                    ofType {
+                      name
+                    }
+                  }
+                  defaultValue
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "kind": "INPUT_OBJECT",
+                  "inputFields": [
+                    {
+                      "name": "contains",
+                      "type": {
+                        "kind": "NON_NULL",
+                        "ofType": {
+                          "name": "String"
+                        }
+                      },
+                      "defaultValue": null
+                    },
+                    {
+                      "name": "caseSensitive",
+                      "type": {
+                        "kind": "NON_NULL",
+                        "ofType": {
+                          "name": "Boolean"
+                        }
+                      },
+                      "defaultValue": "false"
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_unknown_type() {
+    request_test(
+        r#"
+            {
+              __type(name: "Nope") {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": null
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_query() {
+    // the query GraphiQL et al send
+    let db_pool = get_db_pool().await.unwrap();
+    let schema = get_schema(&db_pool).await.unwrap();
+    let response = schema
+        .request(
+            r#"
+            query IntrospectionQuery {
+              __schema {
+                queryType { name }
+                mutationType { name }
+                subscriptionType { name }
+                types {
+                  ...FullType
+                }
+                directives {
+                  name
+                  description
+                  locations
+                  args {
+                    ...InputValue
+                  }
+                }
+              }
+            }
+
+            fragment FullType on __Type {
+              kind
+              name
+              description
+              fields(includeDeprecated: true) {
+                name
+                description
+                args {
+                  ...InputValue
+                }
+                type {
+                  ...TypeRef
+                }
+                isDeprecated
+                deprecationReason
+              }
+              inputFields {
+                ...InputValue
+              }
+              interfaces {
+                ...TypeRef
+              }
+              enumValues(includeDeprecated: true) {
+                name
+                description
+                isDeprecated
+                deprecationReason
+              }
+              possibleTypes {
+                ...TypeRef
+              }
+            }
+
+            fragment InputValue on __InputValue {
+              name
+              description
+              type { ...TypeRef }
+              defaultValue
+            }
+
+            fragment TypeRef on __Type {
+              kind
+              name
+              ofType {
+                kind
+                name
+                ofType {
+                  kind
+                  name
+                  ofType {
+                    kind
+                    name
+                    ofType {
+                      kind
+                      name
+                      ofType {
+                        kind
+                        name
+                        ofType {
+                          kind
+                          name
+                          ofType {
+                            kind
+                            name
+                          }
+                        }
+                      }
+                    }
+                  }
+                }
+              }
+            }
+            "#,
+            &db_pool,
+        )
+        .await;
+    let json: serde_json::Value = serde_json::from_str(&json_from_response(&response)).unwrap();
+    assert!(json.get("errors").is_none());
+    let types = json["data"]["__schema"]["types"].as_array().unwrap();
+    let actor_type = types.iter().find(|type_| type_["name"] == "Actor").unwrap();
+    assert_eq!(actor_type["kind"], "OBJECT");
+    assert_eq!(actor_type["interfaces"][0]["name"], "HasName");
+    let actor_name_field = actor_type["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|field| field["name"] == "fullName")
+        .unwrap();
+    assert_eq!(actor_name_field["type"]["kind"], "NON_NULL");
+    assert_eq!(actor_name_field["type"]["ofType"]["name"], "String");
+    let query_type = types.iter().find(|type_| type_["name"] == "Query").unwrap();
+    let actor_field = query_type["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|field| field["name"] == "actor")
+        .unwrap();
+    assert_eq!(actor_field["args"][0]["name"], "id");
+    assert_eq!(actor_field["args"][0]["type"]["kind"], "NON_NULL");
+    assert_eq!(actor_field["args"][0]["type"]["ofType"]["name"], "ID");
+    assert!(types.iter().any(|type_| type_["name"] == "__TypeKind"));
+}
+
+#[tokio::test]
+async fn test_input_object_arguments() {
+    request_test(
+        r#"
+            {
+              searchActors(filter: {name: {contains: "katie"}}) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "searchActors": [
+                  {
+                    "name": "Katie Cassidy"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              searchActors(filter: {name: {contains: "katie", caseSensitive: true}}) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "searchActors": []
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              searchActors(filter: {castStatuses: [MAIN]}) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "searchActors": [
+                  {
+                    "name": "Jessica Szohr"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            mutation {
+              postBlast(input: {content: "Spotted: S and B", sentAt: "2008-05-19T20:00:00-04:00"}) {
+                content
+                sentAt
+                tone
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "postBlast": {
+                  "content": "Spotted: S and B",
+                  "sentAt": "2008-05-20T00:00:00Z",
+                  "tone": "GOSSIP"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            mutation($input: BlastInput!) {
+              postBlast(input: $input) {
+                content
+                sentAt
+                tone
+              }
+            }
+        "#,
+        r#"
+            {
+              "input": {
+                "content": "Spotted: Lonely Boy",
+                "sentAt": "2009-12-07T21:00:00Z",
+                "tone": "SCANDAL"
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "postBlast": {
+                  "content": "Spotted: Lonely Boy",
+                  "sentAt": "2009-12-07T21:00:00Z",
+                  "tone": "SCANDAL"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_with_variables_test(
+        r#"
+            mutation($input: BlastInput!) {
+              postBlast(input: $input) {
+                content
+              }
+            }
+        "#,
+        r#"
+            {
+              "input": {
+                "content": "Spotted: Lonely Boy",
+                "mood": "SCANDAL"
+              }
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Variable `$input` got invalid value `{\"content\":\"Spotted: Lonely Boy\",\"mood\":\"SCANDAL\"}`; expected type `BlastInput!`"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_null_propagation() {
+    request_test(
+        r#"
+            {
+              actor(id: 999) {
+                expression
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "expression": null
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    request_test(
+        r#"
+            {
+              actor(id: 999) {
+                fullName
+                expression
+              }
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Cannot return null for non-nullable field `fullName`",
+                  "path": ["actor", "fullName"]
+                }
+              ],
+              "data": {
+                "actor": null
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_field_errors() {
+    request_test(
+        r#"
+            {
+              actors {
+                name
+                agentPhone
+              }
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Not authorized",
+                  "path": ["actors", 0, "agentPhone"]
+                },
+                {
+                  "message": "Not authorized",
+                  "path": ["actors", 1, "agentPhone"]
+                }
+              ],
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy",
+                    "agentPhone": null
+                  },
+                  {
+                    "name": "Jessica Szohr",
+                    "agentPhone": null
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_descriptions() {
+    request_test(
+        r#"
+            {
+              __type(name: "Actor") {
+                description
+                fields {
+                  name
+                  description
+                  args {
+                    name
+                    description
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "description": "A performer who can be cast in movies",
+                  "fields": [
+                    {
+                      "name": "name",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "expression",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "fullName",
+                      "description": "The actor's full name",
+                      "args": []
+                    },
+                    {
+                      "name": "id",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "birthYear",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "heightInMeters",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "isMainCast",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "externalId",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "debutedAt",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "metadata",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "salaryPerEpisode",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "castStatus",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "agentPhone",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "favoriteActorOrDesigner",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "leadMovies",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "movies",
+                      "description": null,
+                      "args": [
+                        {
+                          "name": "releaseYear",
+                          "description": "Only include movies released in this year"
+                        },
+                        {
+                          "name": "orderBy",
+                          "description": null
+                        }
+                      ]
+                    },
+                    {
+                      "name": "movieCount",
+                      "description": null,
+                      "args": []
+                    },
+                    {
+                      "name": "leadMoviesConnection",
+                      "description": null,
+                      "args": [
+                        {
+                          "name": "first",
+                          "description": null
+                        },
+                        {
+                          "name": "after",
+                          "description": null
+                        },
+                        {
+                          "name": "last",
+                          "description": null
+                        },
+                        {
+                          "name": "before",
+                          "description": null
+                        }
+                      ]
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_introspection_deprecated_fields() {
+    request_test(
+        r#"
+            {
+              __type(name: "Actor") {
+                fields(includeDeprecated: true) {
+                  name
+                  isDeprecated
+                  deprecationReason
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "__type": {
+                  "fields": [
+                    {
+                      "name": "name",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "expression",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "fullName",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "catchphrase",
+                      "isDeprecated": true,
+                      "deprecationReason": "Use `expression`"
+                    },
+                    {
+                      "name": "id",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "birthYear",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "heightInMeters",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "isMainCast",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "externalId",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "debutedAt",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "metadata",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "salaryPerEpisode",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "castStatus",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "agentPhone",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "favoriteActorOrDesigner",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "leadMovies",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "movies",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "movieCount",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    },
+                    {
+                      "name": "leadMoviesConnection",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_deprecated_field_warnings() {
+    request_with_options_test(
+        r#"
+            {
+              actorKatie {
+                name
+                catchphrase
+              }
+            }
+        "#,
+        RequestOptionsBuilder::default()
+            .warn_on_deprecated_fields(true)
+            .build()
+            .unwrap(),
+        r#"
+            {
+              "data": {
+                "actorKatie": {
+                  "name": "Katie Cassidy",
+                  "catchphrase": "no Serena you can't have the key"
+                }
+              },
+              "extensions": {
+                "warnings": [
+                  {
+                    "message": "Field `catchphrase` is deprecated: Use `expression`",
+                    "locations": [
+                      {
+                        "line": 5,
+                        "column": 17
+                      }
+                    ]
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_mixed_case_identifiers() {
+    // like unquoted identifiers in Postgres, these match the lowercase
+    // `actors.birth_year` column
+    let schema = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([TypeFieldBuilder::default()
+                        .name("actorKatie")
+                        .type_(TypeFull::Type("Actor".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "id".to_owned(),
+                                DependencyType::Id,
+                                InternalDependencyResolver::LiteralValue(
+                                    LiteralValueInternalDependencyResolver(DependencyValue::Id(1)),
+                                ),
+                            )],
+                            CarverOrPopulator::Populator(Box::new(ValuePopulator::new(
+                                "id".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap()])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Actor")
+                    .fields([TypeFieldBuilder::default()
+                        .name("birthYear")
+                        .type_(TypeFull::Type("Int".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                            vec![InternalDependency::new(
+                                "birth_year".to_owned(),
+                                DependencyType::Int,
+                                InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                    "Actors".to_owned(),
+                                    "Birth_Year".to_owned(),
+                                )),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(IntCarver::new(
+                                "birth_year".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap()])
+                    .build()
+                    .unwrap(),
+            ),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let db_pool = get_db_pool().await.unwrap();
+    let response = schema
+        .request(
+            r#"
+                {
+                  actorKatie {
+                    birthYear
+                  }
+                }
+            "#,
+            &db_pool,
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "data": {
+                    "actorKatie": {
+                      "birthYear": 1986
+                    }
+                  }
+                }
+            "#
+        )
+    );
+}
@@ -0,0 +1,318 @@
+use indoc::indoc;
+
+use sauvignon::{
+    json_from_response, parse_sdl, ArgumentInternalDependencyResolver, BroadcastSubscriptionSource,
+    CarverOrPopulator, ColumnGetter, DependencyType, DependencyValue, Error, ExternalDependency,
+    FieldResolver, InternalDependency, InternalDependencyResolver,
+    LiteralValueInternalDependencyResolver, ObjectTypeBuilder, OperationType, Param, Schema,
+    SdlResolvers, StringCarver, Type, TypeFieldBuilder, TypeFull, ValuePopulator,
+};
+use tokio::sync::broadcast;
+
+mod shared;
+
+use shared::{get_in_memory_data_source, get_offline_schema, pretty_print_json};
+
+const ACTOR_KATIE_SDL: &str = indoc!(
+    r#"
+    type Actor {
+      "The actor's full name"
+      name: String!
+      expression: String @deprecated(reason: "Expressions are no longer tracked")
+    }
+
+    type Query {
+      actorKatie: Actor
+    }
+    "#
+);
+
+fn actor_column_resolver(column: &str) -> FieldResolver {
+    FieldResolver::new(
+        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+        vec![InternalDependency::new(
+            column.to_owned(),
+            DependencyType::String,
+            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                "actors".to_owned(),
+                column.to_owned(),
+            )),
+        )],
+        CarverOrPopulator::Carver(Box::new(StringCarver::new(column.to_owned()))),
+    )
+}
+
+fn actor_katie_resolvers() -> SdlResolvers {
+    SdlResolvers::default()
+        .resolver("Actor.name", actor_column_resolver("name"))
+        .resolver("Actor.expression", actor_column_resolver("expression"))
+        .resolver(
+            "Query.actorKatie",
+            FieldResolver::new(
+                vec![],
+                vec![InternalDependency::new(
+                    "id".to_owned(),
+                    DependencyType::Id,
+                    InternalDependencyResolver::LiteralValue(
+                        LiteralValueInternalDependencyResolver(DependencyValue::Id(1)),
+                    ),
+                )],
+                CarverOrPopulator::Populator(Box::new(ValuePopulator::new("id".to_owned()))),
+            ),
+        )
+}
+
+#[test]
+fn test_sdl() {
+    let schema = get_offline_schema().unwrap();
+    assert_eq!(schema.sdl(), include_str!("schema.graphql"));
+}
+
+#[test]
+fn test_sdl_schema_definition_and_block_descriptions() {
+    let schema = Schema::try_new(
+        vec![Type::Object(
+            ObjectTypeBuilder::default()
+                .name("Root")
+                .description("The entry point.\n\nEverything starts here")
+                .fields([TypeFieldBuilder::default()
+                    .name("actorName")
+                    .type_(TypeFull::Type("String".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "name".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "name".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(StringCarver::new("name".to_owned()))),
+                    ))
+                    .params([Param::new(
+                        "id".to_owned(),
+                        TypeFull::NonNull(Box::new(TypeFull::Type("ID".to_owned()))),
+                    )
+                    .deprecation_reason("No longer supported")])
+                    .deprecation_reason("Use `actor` instead")
+                    .build()
+                    .unwrap()])
+                .is_top_level_type(OperationType::Query)
+                .build()
+                .unwrap(),
+        )],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(
+        schema.sdl(),
+        indoc!(
+            r#"
+            schema {
+              query: Root
+            }
+
+            """
+            The entry point.
+
+            Everything starts here
+            """
+            type Root {
+              actorName(id: ID! @deprecated): String @deprecated(reason: "Use `actor` instead")
+            }
+            "#
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_schema_from_sdl() {
+    let schema = Schema::try_from_sdl(ACTOR_KATIE_SDL, actor_katie_resolvers()).unwrap();
+    assert_eq!(schema.sdl(), ACTOR_KATIE_SDL);
+
+    let response = schema
+        .request(
+            r#"
+                {
+                  actorKatie {
+                    name
+                    expression
+                  }
+                }
+            "#,
+            &get_in_memory_data_source(),
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "data": {
+                    "actorKatie": {
+                      "name": "Katie Cassidy",
+                      "expression": "no Serena you can't have the key"
+                    }
+                  }
+                }
+            "#
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_schema_from_sdl_argument_default_value() {
+    let sdl = indoc!(
+        r#"
+        type Actor {
+          "The actor's full name"
+          name: String!
+          expression: String @deprecated(reason: "Expressions are no longer tracked")
+        }
+
+        type Query {
+          actorKatie: Actor
+          actor(id: ID! = 1): Actor
+        }
+        "#
+    );
+    let schema = Schema::try_from_sdl(
+        sdl,
+        actor_katie_resolvers().resolver(
+            "Query.actor",
+            FieldResolver::new(
+                vec![],
+                vec![InternalDependency::new(
+                    "id".to_owned(),
+                    DependencyType::Id,
+                    InternalDependencyResolver::Argument(ArgumentInternalDependencyResolver::new(
+                        "id".to_owned(),
+                    )),
+                )],
+                CarverOrPopulator::Populator(Box::new(ValuePopulator::new("id".to_owned()))),
+            ),
+        ),
+    )
+    .unwrap();
+    assert_eq!(schema.sdl(), sdl);
+
+    for document in [
+        "{ actor { name } }",
+        // an omitted variable falls back to the argument's default value
+        "query($id: ID) { actor(id: $id) { name } }",
+    ] {
+        let response = schema.request(document, &get_in_memory_data_source()).await;
+        assert_eq!(
+            pretty_print_json(&json_from_response(&response)),
+            pretty_print_json(
+                r#"
+                    {
+                      "data": {
+                        "actor": {
+                          "name": "Katie Cassidy"
+                        }
+                      }
+                    }
+                "#
+            )
+        );
+    }
+}
+
+#[test]
+fn test_schema_from_sdl_missing_resolver() {
+    let result = Schema::try_from_sdl(
+        ACTOR_KATIE_SDL,
+        SdlResolvers::default().resolver("Actor.name", actor_column_resolver("name")),
+    );
+    assert!(matches!(
+        result,
+        Err(errors) if matches!(
+            &errors[..],
+            [
+                Error::MissingResolver(expression_coordinate),
+                Error::MissingResolver(actor_katie_coordinate),
+            ] if expression_coordinate == "Actor.expression"
+                && actor_katie_coordinate == "Query.actorKatie"
+        )
+    ));
+}
+
+#[test]
+fn test_schema_from_sdl_extra_resolver() {
+    let result = Schema::try_from_sdl(
+        ACTOR_KATIE_SDL,
+        actor_katie_resolvers().resolver("Actor.birthYear", actor_column_resolver("birth_year")),
+    );
+    assert!(matches!(
+        result,
+        Err(errors) if matches!(
+            &errors[..],
+            [Error::ExtraResolver(coordinate)] if coordinate == "Actor.birthYear"
+        )
+    ));
+}
+
+#[test]
+fn test_schema_from_sdl_extra_subscription_source() {
+    let result = Schema::try_from_sdl(
+        ACTOR_KATIE_SDL,
+        actor_katie_resolvers().subscription_source(
+            "Query.actorKatie",
+            Box::new(BroadcastSubscriptionSource::new(broadcast::channel(1).0)),
+        ),
+    );
+    assert!(matches!(
+        result,
+        Err(errors) if matches!(
+            &errors[..],
+            [Error::ExtraSubscriptionSource(coordinate)] if coordinate == "Query.actorKatie"
+        )
+    ));
+}
+
+#[test]
+fn test_schema_from_sdl_resolver_and_type_system_errors() {
+    let result = Schema::try_from_sdl(
+        indoc!(
+            r#"
+            type Query {
+              actorKatie: Actor
+              nope: Nope
+            }
+            "#
+        ),
+        SdlResolvers::default().resolver("Query.nope", actor_column_resolver("name")),
+    );
+    assert_eq!(
+        match result {
+            Ok(_) => panic!("Expected schema errors"),
+            Err(errors) => errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        },
+        [
+            "missing resolver for field `Query.actorKatie`",
+            "`Query.actorKatie` refers to unknown type `Actor`",
+            "`Query.nope` refers to unknown type `Nope`",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_from_invalid_sdl() {
+    let result = Schema::try_from_sdl("type Query { actorKatie Actor }", SdlResolvers::default());
+    assert!(matches!(
+        result,
+        Err(errors) if matches!(
+            &errors[..],
+            [error @ Error::InvalidSdl(_)] if error.to_string() == "invalid SDL: Expected colon"
+        )
+    ));
+}
+
+#[test]
+fn test_parse_sdl() {
+    let document = parse_sdl(include_str!("schema.graphql").chars()).unwrap();
+    assert_eq!(document.definitions.len(), 25);
+}
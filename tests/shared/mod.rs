@@ -1,13 +1,28 @@
+#![allow(dead_code)]
+
+use std::str::FromStr;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{future::LocalBoxFuture, FutureExt};
+use rust_decimal::Decimal;
+use serde_json::Value as JsonValue;
 use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
+use tokio::sync::broadcast;
+use uuid::Uuid;
 
 use sauvignon::{
-    ArgumentInternalDependencyResolver, CarverOrPopulator, ColumnGetter, ColumnGetterList,
-    DependencyType, DependencyValue, ExternalDependency, ExternalDependencyValues, FieldResolver,
-    Id, InterfaceBuilder, InterfaceField, InternalDependency, InternalDependencyResolver,
-    InternalDependencyValues, LiteralValueInternalDependencyResolver, ObjectTypeBuilder,
-    OperationType, Param, PopulatorList, Schema, StringCarver, Type, TypeDepluralizer,
-    TypeFieldBuilder, TypeFull, Union, UnionOrInterfaceTypePopulatorList, ValuePopulator,
-    ValuePopulatorList, ValuesPopulator,
+    page_info_type, ArgumentInternalDependencyResolver, BooleanCarver, BroadcastSubscriptionSource,
+    Carver, CarverOrPopulator, ColumnGetter, ColumnGetterList, ColumnGetterListOrderBy,
+    ColumnGetterListValue, Connection, CustomInternalDependencyResolver, CustomScalar,
+    CustomScalarCarver, DataSource, DependencyType, DependencyValue, EnumCarver, EnumTypeBuilder,
+    EnumValueBuilder, ExternalDependency, ExternalDependencyValues, FieldError, FieldResolver,
+    FieldResult, FloatCarver, HasManyViaForeignKey, Id, IdCarver, InMemoryDataSource, InMemoryRow,
+    InputFieldBuilder, InputObjectTypeBuilder, IntCarver, InterfaceBuilder, InterfaceField,
+    InternalDependency, InternalDependencyResolver, InternalDependencyValues, ListOrder, ListQuery,
+    LiteralValueInternalDependencyResolver, ManyToManyViaJoinTable, ObjectTypeBuilder,
+    OperationType, OrderDirection, Param, PopulatorList, ResponseValue, RowInserter, ScalarType,
+    Schema, StringCarver, Type, TypeDepluralizer, TypeFieldBuilder, TypeFull, Union,
+    UnionOrInterfaceTypePopulatorList, Value, ValuePopulator, ValuePopulatorList, ValuesPopulator,
 };
 
 pub struct ActorsAndDesignersTypePopulator {}
@@ -23,8 +38,8 @@ impl UnionOrInterfaceTypePopulatorList for ActorsAndDesignersTypePopulator {
         &self,
         _external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> Vec<String> {
-        internal_dependencies
+    ) -> FieldResult<Vec<String>> {
+        Ok(internal_dependencies
             .get("actor_ids")
             .unwrap()
             .as_list()
@@ -38,7 +53,7 @@ impl UnionOrInterfaceTypePopulatorList for ActorsAndDesignersTypePopulator {
                     .into_iter()
                     .map(|_| "Designer".to_owned()),
             )
-            .collect()
+            .collect())
     }
 }
 
@@ -55,8 +70,8 @@ impl PopulatorList for ActorsAndDesignersPopulator {
         &self,
         _external_dependencies: &ExternalDependencyValues,
         internal_dependencies: &InternalDependencyValues,
-    ) -> Vec<ExternalDependencyValues> {
-        internal_dependencies
+    ) -> FieldResult<Vec<ExternalDependencyValues>> {
+        Ok(internal_dependencies
             .get("actor_ids")
             .unwrap()
             .as_list()
@@ -78,11 +93,303 @@ impl PopulatorList for ActorsAndDesignersPopulator {
                         ret
                     }),
             )
-            .collect()
+            .collect())
+    }
+}
+
+/// Filters by the `filter: ActorSearchFilter!` argument
+pub struct ActorSearchPopulator {}
+
+impl ActorSearchPopulator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PopulatorList for ActorSearchPopulator {
+    fn populate(
+        &self,
+        _external_dependencies: &ExternalDependencyValues,
+        internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<Vec<ExternalDependencyValues>> {
+        let filter = internal_dependencies.get("filter").unwrap().as_object();
+        let name_filter = filter.get("name").map(|name_filter| {
+            let name_filter = name_filter.as_object();
+            (
+                name_filter["contains"].as_string(),
+                *name_filter["caseSensitive"].as_boolean(),
+            )
+        });
+        let cast_statuses = filter.get("castStatuses").map(|cast_statuses| {
+            cast_statuses
+                .as_list()
+                .iter()
+                .map(|cast_status| cast_status.as_string())
+                .collect::<Vec<_>>()
+        });
+        let [ids, names, actor_cast_statuses] = ["ids", "names", "cast_statuses"]
+            .map(|name| internal_dependencies.get(name).unwrap().as_list());
+        Ok(ids
+            .iter()
+            .zip(names)
+            .zip(actor_cast_statuses)
+            .filter(|((_, name), cast_status)| {
+                name_filter.is_none_or(|(contains, is_case_sensitive)| {
+                    if is_case_sensitive {
+                        name.as_string().contains(contains.as_str())
+                    } else {
+                        name.as_string()
+                            .to_lowercase()
+                            .contains(&contains.to_lowercase())
+                    }
+                }) && cast_statuses
+                    .as_ref()
+                    .is_none_or(|cast_statuses| cast_statuses.contains(&cast_status.as_string()))
+            })
+            .map(|((id, _), _)| {
+                let mut ret = ExternalDependencyValues::default();
+                ret.insert("id".to_owned(), id.clone()).unwrap();
+                ret
+            })
+            .collect())
+    }
+}
+
+/// Always fails, for exercising field errors
+pub struct UnauthorizedCarver {}
+
+impl UnauthorizedCarver {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Carver for UnauthorizedCarver {
+    fn carve(
+        &self,
+        _external_dependencies: &ExternalDependencyValues,
+        _internal_dependencies: &InternalDependencyValues,
+    ) -> FieldResult<ResponseValue> {
+        Err(FieldError::new("Not authorized"))
+    }
+}
+
+/// Counts an actor's movies with a `DataSource` query of its own, for
+/// exercising custom internal dependency resolvers
+pub struct MovieCountResolver {}
+
+impl MovieCountResolver {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CustomInternalDependencyResolver for MovieCountResolver {
+    fn resolve<'a>(
+        &'a self,
+        external_dependencies: &'a ExternalDependencyValues,
+        _internal_dependencies: &'a InternalDependencyValues,
+        data_source: &'a dyn DataSource,
+    ) -> LocalBoxFuture<'a, FieldResult<DependencyValue>> {
+        async move {
+            let movie_ids = data_source
+                .get_ids_via_join_table(
+                    "actors_movies",
+                    "actor_id",
+                    "movie_id",
+                    "movies",
+                    *external_dependencies.get("id").unwrap().as_id(),
+                    &ListQuery::default(),
+                )
+                .await?;
+            Ok(DependencyValue::Int(movie_ids.len().try_into().unwrap()))
+        }
+        .boxed_local()
+    }
+}
+
+pub struct DateTimeScalar {}
+
+impl CustomScalar for DateTimeScalar {
+    fn name(&self) -> &str {
+        "DateTime"
+    }
+
+    fn serialize(&self, value: &DependencyValue) -> Result<ResponseValue, String> {
+        match value {
+            DependencyValue::DateTime(value) => Ok(ResponseValue::String(
+                value.to_rfc3339_opts(SecondsFormat::Secs, true),
+            )),
+            _ => Err("Expected a date-time".to_owned()),
+        }
+    }
+
+    fn parse_value(&self, value: &Value) -> Result<DependencyValue, String> {
+        match value {
+            Value::String(value) => parse_date_time(value),
+            _ => Err("Expected a date-time string".to_owned()),
+        }
+    }
+
+    fn parse_variable_value(&self, value: &JsonValue) -> Result<DependencyValue, String> {
+        match value {
+            JsonValue::String(value) => parse_date_time(value),
+            _ => Err("Expected a date-time string".to_owned()),
+        }
+    }
+}
+
+fn parse_date_time(value: &str) -> Result<DependencyValue, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date_time| DependencyValue::DateTime(date_time.with_timezone(&Utc)))
+        .map_err(|_| format!("Invalid date-time: `{value}`"))
+}
+
+pub struct UuidScalar {}
+
+impl CustomScalar for UuidScalar {
+    fn name(&self) -> &str {
+        "UUID"
+    }
+
+    fn serialize(&self, value: &DependencyValue) -> Result<ResponseValue, String> {
+        match value {
+            DependencyValue::Uuid(value) => Ok(ResponseValue::String(value.to_string())),
+            _ => Err("Expected a UUID".to_owned()),
+        }
+    }
+
+    fn parse_value(&self, value: &Value) -> Result<DependencyValue, String> {
+        match value {
+            Value::String(value) => parse_uuid(value),
+            _ => Err("Expected a UUID string".to_owned()),
+        }
+    }
+
+    fn parse_variable_value(&self, value: &JsonValue) -> Result<DependencyValue, String> {
+        match value {
+            JsonValue::String(value) => parse_uuid(value),
+            _ => Err("Expected a UUID string".to_owned()),
+        }
+    }
+}
+
+fn parse_uuid(value: &str) -> Result<DependencyValue, String> {
+    Uuid::parse_str(value)
+        .map(DependencyValue::Uuid)
+        .map_err(|_| format!("Invalid UUID: `{value}`"))
+}
+
+pub struct JsonScalar {}
+
+impl CustomScalar for JsonScalar {
+    fn name(&self) -> &str {
+        "JSON"
+    }
+
+    fn serialize(&self, value: &DependencyValue) -> Result<ResponseValue, String> {
+        match value {
+            DependencyValue::Json(value) => Ok(value.clone().into()),
+            _ => Err("Expected JSON".to_owned()),
+        }
+    }
+
+    fn parse_value(&self, value: &Value) -> Result<DependencyValue, String> {
+        Ok(DependencyValue::Json(json_from_value(value)))
+    }
+
+    fn parse_variable_value(&self, value: &JsonValue) -> Result<DependencyValue, String> {
+        Ok(DependencyValue::Json(value.clone()))
     }
 }
 
+fn json_from_value(value: &Value) -> JsonValue {
+    match value {
+        Value::Int(value) => (*value).into(),
+        Value::Float(value) => (*value).into(),
+        Value::String(value) | Value::Enum(value) => value.clone().into(),
+        Value::Null => JsonValue::Null,
+        Value::Bool(value) => (*value).into(),
+        Value::List(values) => values.iter().map(json_from_value).collect(),
+        Value::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), json_from_value(value)))
+                .collect(),
+        ),
+        Value::Variable(_) => unreachable!(),
+    }
+}
+
+/// Serialized as a string so as not to lose precision
+pub struct DecimalScalar {}
+
+impl CustomScalar for DecimalScalar {
+    fn name(&self) -> &str {
+        "Decimal"
+    }
+
+    fn serialize(&self, value: &DependencyValue) -> Result<ResponseValue, String> {
+        match value {
+            DependencyValue::Decimal(value) => Ok(ResponseValue::String(value.to_string())),
+            _ => Err("Expected a decimal".to_owned()),
+        }
+    }
+
+    fn parse_value(&self, value: &Value) -> Result<DependencyValue, String> {
+        match value {
+            Value::String(value) => parse_decimal(value),
+            Value::Int(value) => Ok(DependencyValue::Decimal((*value).into())),
+            _ => Err("Expected a decimal string or integer".to_owned()),
+        }
+    }
+
+    fn parse_variable_value(&self, value: &JsonValue) -> Result<DependencyValue, String> {
+        match value {
+            JsonValue::String(value) => parse_decimal(value),
+            JsonValue::Number(value) if value.is_i64() => {
+                Ok(DependencyValue::Decimal(value.as_i64().unwrap().into()))
+            }
+            _ => Err("Expected a decimal string or integer".to_owned()),
+        }
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<DependencyValue, String> {
+    Decimal::from_str(value)
+        .map(DependencyValue::Decimal)
+        .map_err(|_| format!("Invalid decimal: `{value}`"))
+}
+
 pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
+    get_schema_with_actor_updates(db_pool, broadcast::channel(16).0).await
+}
+
+pub async fn get_schema_with_actor_updates(
+    db_pool: &Pool<Postgres>,
+    actor_updates: broadcast::Sender<ExternalDependencyValues>,
+) -> anyhow::Result<Schema> {
+    let (katie_id,): (Id,) = sqlx::query_as("SELECT id FROM actors WHERE name = 'Katie Cassidy'")
+        .fetch_one(db_pool)
+        .await?;
+    let (proenza_schouler_id,): (Id,) =
+        sqlx::query_as("SELECT id FROM designers WHERE name = 'Proenza Schouler'")
+            .fetch_one(db_pool)
+            .await?;
+    build_schema(katie_id, proenza_schouler_id, actor_updates)
+}
+
+/// For data sources that have the same rows (and ids) as a freshly created
+/// database, eg `get_in_memory_data_source()`
+pub fn get_offline_schema() -> anyhow::Result<Schema> {
+    build_schema(1, 1, broadcast::channel(16).0)
+}
+
+fn build_schema(
+    katie_id: Id,
+    proenza_schouler_id: Id,
+    actor_updates: broadcast::Sender<ExternalDependencyValues>,
+) -> anyhow::Result<Schema> {
     let has_name_interface = InterfaceBuilder::default()
         .name("HasName")
         .fields(vec![InterfaceField::new(
@@ -93,9 +400,20 @@ pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
         .build()
         .unwrap();
 
+    let actors_connection = Connection::new("Actor".to_owned(), "actors".to_owned())
+        .argument_condition(
+            "is_main_cast".to_owned(),
+            "isMainCast".to_owned(),
+            TypeFull::Type("Boolean".to_owned()),
+            DependencyType::Boolean,
+        );
+    let movies_connection = Connection::new("Movie".to_owned(), "movies".to_owned())
+        .foreign_key("lead_actor_id".to_owned());
+
     let actor_type = Type::Object(
         ObjectTypeBuilder::default()
             .name("Actor")
+            .description("A performer who can be cast in movies")
             .fields([
                 TypeFieldBuilder::default()
                     .name("name")
@@ -141,6 +459,222 @@ pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
                     ))
                     .build()
                     .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("fullName")
+                    .description("The actor's full name")
+                    .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                        "String".to_owned(),
+                    ))))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "name".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "name".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(StringCarver::new("name".to_owned()))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("catchphrase")
+                    .type_(TypeFull::Type("String".to_owned()))
+                    .deprecation_reason("Use `expression`")
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "expression".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "expression".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                            "expression".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("id")
+                    .type_(TypeFull::Type("ID".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![],
+                        CarverOrPopulator::Carver(Box::new(IdCarver::new("id".to_owned()))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("birthYear")
+                    .type_(TypeFull::Type("Int".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "birth_year".to_owned(),
+                            DependencyType::Int,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "birth_year".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(IntCarver::new(
+                            "birth_year".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("heightInMeters")
+                    .type_(TypeFull::Type("Float".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "height_in_meters".to_owned(),
+                            DependencyType::Float,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "height_in_meters".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(FloatCarver::new(
+                            "height_in_meters".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("isMainCast")
+                    .type_(TypeFull::Type("Boolean".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "is_main_cast".to_owned(),
+                            DependencyType::Boolean,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "is_main_cast".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(BooleanCarver::new(
+                            "is_main_cast".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("externalId")
+                    .type_(TypeFull::Type("UUID".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "external_id".to_owned(),
+                            DependencyType::Uuid,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "external_id".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(CustomScalarCarver::new(
+                            "external_id".to_owned(),
+                            UuidScalar {},
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("debutedAt")
+                    .type_(TypeFull::Type("DateTime".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "debuted_at".to_owned(),
+                            DependencyType::DateTime,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "debuted_at".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(CustomScalarCarver::new(
+                            "debuted_at".to_owned(),
+                            DateTimeScalar {},
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("metadata")
+                    .type_(TypeFull::Type("JSON".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "metadata".to_owned(),
+                            DependencyType::Json,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "metadata".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(CustomScalarCarver::new(
+                            "metadata".to_owned(),
+                            JsonScalar {},
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("salaryPerEpisode")
+                    .type_(TypeFull::Type("Decimal".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "salary_per_episode".to_owned(),
+                            DependencyType::Decimal,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "salary_per_episode".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(CustomScalarCarver::new(
+                            "salary_per_episode".to_owned(),
+                            DecimalScalar {},
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("castStatus")
+                    .type_(TypeFull::Type("CastStatus".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "cast_status".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors".to_owned(),
+                                "cast_status".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(EnumCarver::new(
+                            "cast_status".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("agentPhone")
+                    .type_(TypeFull::Type("String".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![],
+                        vec![],
+                        CarverOrPopulator::Carver(Box::new(UnauthorizedCarver::new())),
+                    ))
+                    .build()
+                    .unwrap(),
                 TypeFieldBuilder::default()
                     .name("favoriteActorOrDesigner")
                     .type_(TypeFull::Type("ActorOrDesigner".to_owned()))
@@ -156,24 +690,146 @@ pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
                                 )),
                             ),
                             InternalDependency::new(
-                                "favorite_actor_or_designer_id".to_owned(),
-                                DependencyType::Id,
-                                InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
-                                    "actors".to_owned(),
-                                    "favorite_actor_or_designer_id".to_owned(),
-                                )),
+                                "favorite_actor_or_designer_id".to_owned(),
+                                DependencyType::Id,
+                                InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                    "actors".to_owned(),
+                                    "favorite_actor_or_designer_id".to_owned(),
+                                )),
+                            ),
+                        ],
+                        CarverOrPopulator::UnionOrInterfaceTypePopulator(
+                            Box::new(TypeDepluralizer::new()),
+                            Box::new(ValuesPopulator::new([(
+                                "favorite_actor_or_designer_id".to_owned(),
+                                "id".to_owned(),
+                            )])),
+                        ),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("leadMovies")
+                    .type_(TypeFull::NonNull(Box::new(TypeFull::List(Box::new(
+                        TypeFull::NonNull(Box::new(TypeFull::Type("Movie".to_owned()))),
+                    )))))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "ids".to_owned(),
+                            DependencyType::ListOfIds,
+                            InternalDependencyResolver::HasManyViaForeignKey(
+                                HasManyViaForeignKey::new(
+                                    "movies".to_owned(),
+                                    "lead_actor_id".to_owned(),
+                                ),
+                            ),
+                        )],
+                        CarverOrPopulator::PopulatorList(Box::new(ValuePopulatorList::new(
+                            "id".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("movies")
+                    .type_(TypeFull::NonNull(Box::new(TypeFull::List(Box::new(
+                        TypeFull::NonNull(Box::new(TypeFull::Type("Movie".to_owned()))),
+                    )))))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![
+                            InternalDependency::new(
+                                "release_year".to_owned(),
+                                DependencyType::Int,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new(
+                                        "releaseYear".to_owned(),
+                                    ),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "order_by".to_owned(),
+                                DependencyType::String,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new("orderBy".to_owned()),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "ids".to_owned(),
+                                DependencyType::ListOfIds,
+                                InternalDependencyResolver::ManyToManyViaJoinTable(
+                                    ManyToManyViaJoinTable::new(
+                                        "actors_movies".to_owned(),
+                                        "actor_id".to_owned(),
+                                        "movie_id".to_owned(),
+                                        "movies".to_owned(),
+                                    )
+                                    .condition(
+                                        "release_year".to_owned(),
+                                        ColumnGetterListValue::InternalDependency(
+                                            "release_year".to_owned(),
+                                        ),
+                                    )
+                                    .order_by(
+                                        ColumnGetterListOrderBy::InternalDependency(
+                                            "order_by".to_owned(),
+                                            [
+                                                (
+                                                    "TITLE".to_owned(),
+                                                    ListOrder::new(
+                                                        "title".to_owned(),
+                                                        OrderDirection::Asc,
+                                                    ),
+                                                ),
+                                                (
+                                                    "RELEASE_YEAR_DESC".to_owned(),
+                                                    ListOrder::new(
+                                                        "release_year".to_owned(),
+                                                        OrderDirection::Desc,
+                                                    ),
+                                                ),
+                                            ]
+                                            .into_iter()
+                                            .collect(),
+                                        ),
+                                    ),
+                                ),
                             ),
                         ],
-                        CarverOrPopulator::UnionOrInterfaceTypePopulator(
-                            Box::new(TypeDepluralizer::new()),
-                            Box::new(ValuesPopulator::new([(
-                                "favorite_actor_or_designer_id".to_owned(),
-                                "id".to_owned(),
-                            )])),
+                        CarverOrPopulator::PopulatorList(Box::new(ValuePopulatorList::new(
+                            "id".to_owned(),
+                        ))),
+                    ))
+                    .params([
+                        Param::new("releaseYear".to_owned(), TypeFull::Type("Int".to_owned()))
+                            .description("Only include movies released in this year"),
+                        Param::new(
+                            "orderBy".to_owned(),
+                            TypeFull::Type("MovieOrderBy".to_owned()),
                         ),
+                    ])
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("movieCount")
+                    .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                        "Int".to_owned(),
+                    ))))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "movie_count".to_owned(),
+                            DependencyType::Int,
+                            InternalDependencyResolver::Custom(Box::new(MovieCountResolver::new())),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(IntCarver::new(
+                            "movie_count".to_owned(),
+                        ))),
                     ))
                     .build()
                     .unwrap(),
+                movies_connection.field("leadMoviesConnection"),
             ])
             .implements(vec!["HasName".to_owned()])
             .build()
@@ -205,21 +861,89 @@ pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
             .unwrap(),
     );
 
+    let movie_type = Type::Object(
+        ObjectTypeBuilder::default()
+            .name("Movie")
+            .fields([
+                TypeFieldBuilder::default()
+                    .name("title")
+                    .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                        "String".to_owned(),
+                    ))))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "title".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "movies".to_owned(),
+                                "title".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(StringCarver::new("title".to_owned()))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("releaseYear")
+                    .type_(TypeFull::Type("Int".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "release_year".to_owned(),
+                            DependencyType::Int,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "movies".to_owned(),
+                                "release_year".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(IntCarver::new(
+                            "release_year".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("cast")
+                    .type_(TypeFull::NonNull(Box::new(TypeFull::List(Box::new(
+                        TypeFull::NonNull(Box::new(TypeFull::Type("Actor".to_owned()))),
+                    )))))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "ids".to_owned(),
+                            DependencyType::ListOfIds,
+                            InternalDependencyResolver::ManyToManyViaJoinTable(
+                                ManyToManyViaJoinTable::new(
+                                    "actors_movies".to_owned(),
+                                    "movie_id".to_owned(),
+                                    "actor_id".to_owned(),
+                                    "actors".to_owned(),
+                                )
+                                .order_by(
+                                    ColumnGetterListOrderBy::Fixed(ListOrder::new(
+                                        "name".to_owned(),
+                                        OrderDirection::Asc,
+                                    )),
+                                ),
+                            ),
+                        )],
+                        CarverOrPopulator::PopulatorList(Box::new(ValuePopulatorList::new(
+                            "id".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap(),
+    );
+
     let actor_or_designer = Union::new(
         "ActorOrDesigner".to_owned(),
         vec!["Actor".to_owned(), "Designer".to_owned()],
     );
 
-    let (katie_id,): (Id,) = sqlx::query_as("SELECT id FROM actors WHERE name = 'Katie Cassidy'")
-        .fetch_one(db_pool)
-        .await
-        .unwrap();
-    let (proenza_schouler_id,): (Id,) =
-        sqlx::query_as("SELECT id FROM designers WHERE name = 'Proenza Schouler'")
-            .fetch_one(db_pool)
-            .await
-            .unwrap();
-
     let query_type = Type::Object(
         ObjectTypeBuilder::default()
             .name("Query")
@@ -247,7 +971,7 @@ pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
                     ))
                     .params([Param::new(
                         "id".to_owned(),
-                        TypeFull::NonNull(Box::new(TypeFull::Type("Id".to_owned()))),
+                        TypeFull::NonNull(Box::new(TypeFull::Type("ID".to_owned()))),
                     )])
                     .build()
                     .unwrap(),
@@ -263,18 +987,137 @@ pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
                     // }
                     .resolver(FieldResolver::new(
                         vec![],
-                        vec![InternalDependency::new(
-                            "ids".to_owned(),
-                            DependencyType::ListOfIds,
-                            InternalDependencyResolver::ColumnGetterList(ColumnGetterList::new(
-                                "actors".to_owned(),
-                                "id".to_owned(),
-                            )),
-                        )],
+                        vec![
+                            InternalDependency::new(
+                                "is_main_cast".to_owned(),
+                                DependencyType::Boolean,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new(
+                                        "isMainCast".to_owned(),
+                                    ),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "order_by".to_owned(),
+                                DependencyType::String,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new("orderBy".to_owned()),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "first".to_owned(),
+                                DependencyType::Int,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new("first".to_owned()),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "ids".to_owned(),
+                                DependencyType::ListOfIds,
+                                InternalDependencyResolver::ColumnGetterList(
+                                    ColumnGetterList::new("actors".to_owned(), "id".to_owned())
+                                        .condition(
+                                            "is_main_cast".to_owned(),
+                                            ColumnGetterListValue::InternalDependency(
+                                                "is_main_cast".to_owned(),
+                                            ),
+                                        )
+                                        .order_by(ColumnGetterListOrderBy::InternalDependency(
+                                            "order_by".to_owned(),
+                                            [
+                                                (
+                                                    "NAME".to_owned(),
+                                                    ListOrder::new(
+                                                        "name".to_owned(),
+                                                        OrderDirection::Asc,
+                                                    ),
+                                                ),
+                                                (
+                                                    "BIRTH_YEAR".to_owned(),
+                                                    ListOrder::new(
+                                                        "birth_year".to_owned(),
+                                                        OrderDirection::Asc,
+                                                    ),
+                                                ),
+                                                (
+                                                    "BIRTH_YEAR_DESC".to_owned(),
+                                                    ListOrder::new(
+                                                        "birth_year".to_owned(),
+                                                        OrderDirection::Desc,
+                                                    ),
+                                                ),
+                                            ]
+                                            .into_iter()
+                                            .collect(),
+                                        ))
+                                        .limit(ColumnGetterListValue::InternalDependency(
+                                            "first".to_owned(),
+                                        )),
+                                ),
+                            ),
+                        ],
                         CarverOrPopulator::PopulatorList(Box::new(ValuePopulatorList::new(
                             "id".to_owned(),
                         ))),
                     ))
+                    .params([
+                        Param::new(
+                            "isMainCast".to_owned(),
+                            TypeFull::Type("Boolean".to_owned()),
+                        ),
+                        Param::new(
+                            "orderBy".to_owned(),
+                            TypeFull::Type("ActorOrderBy".to_owned()),
+                        ),
+                        Param::new("first".to_owned(), TypeFull::Type("Int".to_owned())),
+                    ])
+                    .build()
+                    .unwrap(),
+                actors_connection.field("actorsConnection"),
+                TypeFieldBuilder::default()
+                    .name("searchActors")
+                    .type_(TypeFull::List(Box::new(TypeFull::Type("Actor".to_owned()))))
+                    .resolver(FieldResolver::new(
+                        vec![],
+                        vec![
+                            InternalDependency::new(
+                                "ids".to_owned(),
+                                DependencyType::ListOfIds,
+                                InternalDependencyResolver::ColumnGetterList(
+                                    ColumnGetterList::new("actors".to_owned(), "id".to_owned()),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "names".to_owned(),
+                                DependencyType::ListOfStrings,
+                                InternalDependencyResolver::ColumnGetterList(
+                                    ColumnGetterList::new("actors".to_owned(), "name".to_owned()),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "cast_statuses".to_owned(),
+                                DependencyType::ListOfStrings,
+                                InternalDependencyResolver::ColumnGetterList(
+                                    ColumnGetterList::new(
+                                        "actors".to_owned(),
+                                        "cast_status".to_owned(),
+                                    ),
+                                ),
+                            ),
+                            InternalDependency::new(
+                                "filter".to_owned(),
+                                DependencyType::Object,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new("filter".to_owned()),
+                                ),
+                            ),
+                        ],
+                        CarverOrPopulator::PopulatorList(Box::new(ActorSearchPopulator::new())),
+                    ))
+                    .params([Param::new(
+                        "filter".to_owned(),
+                        TypeFull::NonNull(Box::new(TypeFull::Type("ActorSearchFilter".to_owned()))),
+                    )])
                     .build()
                     .unwrap(),
                 TypeFieldBuilder::default()
@@ -402,11 +1245,313 @@ pub async fn get_schema(db_pool: &Pool<Postgres>) -> anyhow::Result<Schema> {
             .unwrap(),
     );
 
-    Ok(Schema::try_new(
-        vec![query_type, actor_type, designer_type],
+    let blast_type = Type::Object(
+        ObjectTypeBuilder::default()
+            .name("Blast")
+            .fields([
+                TypeFieldBuilder::default()
+                    .name("content")
+                    .type_(TypeFull::Type("String".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "content".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "blasts".to_owned(),
+                                "content".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                            "content".to_owned(),
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("sentAt")
+                    .type_(TypeFull::Type("DateTime".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "sent_at".to_owned(),
+                            DependencyType::DateTime,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "blasts".to_owned(),
+                                "sent_at".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(CustomScalarCarver::new(
+                            "sent_at".to_owned(),
+                            DateTimeScalar {},
+                        ))),
+                    ))
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("tone")
+                    .type_(TypeFull::Type("BlastTone".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "tone".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "blasts".to_owned(),
+                                "tone".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(EnumCarver::new("tone".to_owned()))),
+                    ))
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap(),
+    );
+
+    let mutation_type = Type::Object(
+        ObjectTypeBuilder::default()
+            .name("Mutation")
+            .fields([
+                TypeFieldBuilder::default()
+                    .name("sendBlast")
+                    .type_(TypeFull::Type("Blast".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![],
+                        vec![InternalDependency::new(
+                            "id".to_owned(),
+                            DependencyType::Id,
+                            InternalDependencyResolver::RowInserter(RowInserter::new(
+                                "blasts".to_owned(),
+                                [
+                                    ("content".to_owned(), "content".to_owned()),
+                                    ("sent_at".to_owned(), "sentAt".to_owned()),
+                                    ("tone".to_owned(), "tone".to_owned()),
+                                ],
+                            )),
+                        )],
+                        CarverOrPopulator::Populator(Box::new(ValuePopulator::new(
+                            "id".to_owned(),
+                        ))),
+                    ))
+                    .params([
+                        Param::new(
+                            "content".to_owned(),
+                            TypeFull::NonNull(Box::new(TypeFull::Type("String".to_owned()))),
+                        ),
+                        Param::new("sentAt".to_owned(), TypeFull::Type("DateTime".to_owned())),
+                        Param::new("tone".to_owned(), TypeFull::Type("BlastTone".to_owned())),
+                    ])
+                    .build()
+                    .unwrap(),
+                TypeFieldBuilder::default()
+                    .name("postBlast")
+                    .type_(TypeFull::Type("Blast".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![],
+                        vec![InternalDependency::new(
+                            "id".to_owned(),
+                            DependencyType::Id,
+                            InternalDependencyResolver::RowInserter(
+                                RowInserter::new_from_input_object(
+                                    "blasts".to_owned(),
+                                    "input".to_owned(),
+                                    [
+                                        ("content".to_owned(), "content".to_owned()),
+                                        ("sent_at".to_owned(), "sentAt".to_owned()),
+                                        ("tone".to_owned(), "tone".to_owned()),
+                                    ],
+                                ),
+                            ),
+                        )],
+                        CarverOrPopulator::Populator(Box::new(ValuePopulator::new(
+                            "id".to_owned(),
+                        ))),
+                    ))
+                    .params([Param::new(
+                        "input".to_owned(),
+                        TypeFull::NonNull(Box::new(TypeFull::Type("BlastInput".to_owned()))),
+                    )])
+                    .build()
+                    .unwrap(),
+            ])
+            .is_top_level_type(OperationType::Mutation)
+            .build()
+            .unwrap(),
+    );
+
+    let subscription_type = Type::Object(
+        ObjectTypeBuilder::default()
+            .name("Subscription")
+            .fields([TypeFieldBuilder::default()
+                .name("actorUpdated")
+                .type_(TypeFull::Type("Actor".to_owned()))
+                .resolver(FieldResolver::new(
+                    vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                    vec![],
+                    CarverOrPopulator::Populator(Box::new(ValuePopulator::new("id".to_owned()))),
+                ))
+                .subscription_source(Box::new(BroadcastSubscriptionSource::new(actor_updates)))
+                .build()
+                .unwrap()])
+            .is_top_level_type(OperationType::Subscription)
+            .build()
+            .unwrap(),
+    );
+
+    Schema::try_new(
+        vec![
+            query_type,
+            mutation_type,
+            subscription_type,
+            actor_type,
+            designer_type,
+            movie_type,
+            blast_type,
+            page_info_type(),
+            Type::Scalar(ScalarType::Custom(Box::new(DateTimeScalar {}))),
+            Type::Scalar(ScalarType::Custom(Box::new(UuidScalar {}))),
+            Type::Scalar(ScalarType::Custom(Box::new(JsonScalar {}))),
+            Type::Scalar(ScalarType::Custom(Box::new(DecimalScalar {}))),
+            Type::Enum(
+                EnumTypeBuilder::default()
+                    .name("CastStatus")
+                    .values([
+                        EnumValueBuilder::default().name("MAIN").build().unwrap(),
+                        EnumValueBuilder::default()
+                            .name("RECURRING")
+                            .build()
+                            .unwrap(),
+                        EnumValueBuilder::default()
+                            .name("GUEST")
+                            .deprecation_reason("Use `RECURRING`")
+                            .build()
+                            .unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+            Type::InputObject(
+                InputObjectTypeBuilder::default()
+                    .name("ActorSearchFilter")
+                    .fields([
+                        InputFieldBuilder::default()
+                            .name("name")
+                            .type_(TypeFull::Type("StringFilter".to_owned()))
+                            .build()
+                            .unwrap(),
+                        InputFieldBuilder::default()
+                            .name("castStatuses")
+                            .type_(TypeFull::List(Box::new(TypeFull::NonNull(Box::new(
+                                TypeFull::Type("CastStatus".to_owned()),
+                            )))))
+                            .build()
+                            .unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+            Type::InputObject(
+                InputObjectTypeBuilder::default()
+                    .name("StringFilter")
+                    .fields([
+                        InputFieldBuilder::default()
+                            .name("contains")
+                            .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                                "String".to_owned(),
+                            ))))
+                            .build()
+                            .unwrap(),
+                        InputFieldBuilder::default()
+                            .name("caseSensitive")
+                            .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                                "Boolean".to_owned(),
+                            ))))
+                            .default_value(Value::Bool(false))
+                            .build()
+                            .unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+            Type::InputObject(
+                InputObjectTypeBuilder::default()
+                    .name("BlastInput")
+                    .fields([
+                        InputFieldBuilder::default()
+                            .name("content")
+                            .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                                "String".to_owned(),
+                            ))))
+                            .build()
+                            .unwrap(),
+                        InputFieldBuilder::default()
+                            .name("sentAt")
+                            .type_(TypeFull::Type("DateTime".to_owned()))
+                            .build()
+                            .unwrap(),
+                        InputFieldBuilder::default()
+                            .name("tone")
+                            .type_(TypeFull::NonNull(Box::new(TypeFull::Type(
+                                "BlastTone".to_owned(),
+                            ))))
+                            .default_value(Value::Enum("GOSSIP".to_owned()))
+                            .build()
+                            .unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Enum(
+                EnumTypeBuilder::default()
+                    .name("ActorOrderBy")
+                    .values([
+                        EnumValueBuilder::default().name("NAME").build().unwrap(),
+                        EnumValueBuilder::default()
+                            .name("BIRTH_YEAR")
+                            .build()
+                            .unwrap(),
+                        EnumValueBuilder::default()
+                            .name("BIRTH_YEAR_DESC")
+                            .build()
+                            .unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Enum(
+                EnumTypeBuilder::default()
+                    .name("MovieOrderBy")
+                    .values([
+                        EnumValueBuilder::default().name("TITLE").build().unwrap(),
+                        EnumValueBuilder::default()
+                            .name("RELEASE_YEAR_DESC")
+                            .build()
+                            .unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Enum(
+                EnumTypeBuilder::default()
+                    .name("BlastTone")
+                    .values([
+                        EnumValueBuilder::default().name("GOSSIP").build().unwrap(),
+                        EnumValueBuilder::default().name("SCANDAL").build().unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+        ]
+        .into_iter()
+        .chain(actors_connection.types())
+        .chain(movies_connection.types())
+        .collect(),
         vec![actor_or_designer],
         vec![has_name_interface],
-    )?)
+    )
+    .map_err(|errors| anyhow::anyhow!("invalid schema: {errors:?}"))
 }
 
 pub async fn get_db_pool() -> anyhow::Result<Pool<Postgres>> {
@@ -418,6 +1563,156 @@ pub async fn get_db_pool() -> anyhow::Result<Pool<Postgres>> {
     Ok(db_pool)
 }
 
+/// Mirrors the rows inserted by `create_db.sql`
+pub fn get_in_memory_data_source() -> InMemoryDataSource {
+    InMemoryDataSource::new([
+        (
+            "designers".to_owned(),
+            vec![
+                in_memory_row([
+                    ("id", DependencyValue::Id(1)),
+                    ("name", string("Proenza Schouler")),
+                ]),
+                in_memory_row([
+                    ("id", DependencyValue::Id(2)),
+                    ("name", string("Ralph Lauren")),
+                ]),
+            ],
+        ),
+        (
+            "actors".to_owned(),
+            vec![
+                in_memory_row([
+                    ("id", DependencyValue::Id(1)),
+                    ("name", string("Katie Cassidy")),
+                    ("expression", string("no Serena you can't have the key")),
+                    ("favorite_actor_or_designer_type", string("designers")),
+                    ("favorite_actor_or_designer_id", DependencyValue::Id(1)),
+                    ("birth_year", DependencyValue::Int(1986)),
+                    ("height_in_meters", DependencyValue::Float(1.68)),
+                    ("is_main_cast", DependencyValue::Boolean(false)),
+                    (
+                        "external_id",
+                        DependencyValue::Uuid(
+                            Uuid::parse_str("c1a7f8a2-6b0e-4c38-9d0b-3f1b2a4d5e61").unwrap(),
+                        ),
+                    ),
+                    (
+                        "debuted_at",
+                        parse_date_time("2010-09-13T21:00:00Z").unwrap(),
+                    ),
+                    (
+                        "metadata",
+                        DependencyValue::Json(
+                            serde_json::json!({"character": "Juliet Sharp", "seasons": [4]}),
+                        ),
+                    ),
+                    (
+                        "salary_per_episode",
+                        DependencyValue::Decimal(Decimal::from_str("35000.50").unwrap()),
+                    ),
+                    ("cast_status", string("RECURRING")),
+                ]),
+                in_memory_row([
+                    ("id", DependencyValue::Id(2)),
+                    ("name", string("Jessica Szohr")),
+                    (
+                        "expression",
+                        string("Dan where did you go I don't like you"),
+                    ),
+                    ("favorite_actor_or_designer_type", string("actors")),
+                    ("favorite_actor_or_designer_id", DependencyValue::Id(1)),
+                    ("birth_year", DependencyValue::Int(1985)),
+                    ("height_in_meters", DependencyValue::Float(1.65)),
+                    ("is_main_cast", DependencyValue::Boolean(true)),
+                    (
+                        "external_id",
+                        DependencyValue::Uuid(
+                            Uuid::parse_str("5f0c2d4e-8a1b-4e7f-b3c9-0d6e2a9f1b84").unwrap(),
+                        ),
+                    ),
+                    (
+                        "debuted_at",
+                        parse_date_time("2007-09-19T21:00:00Z").unwrap(),
+                    ),
+                    (
+                        "metadata",
+                        DependencyValue::Json(serde_json::json!({
+                            "character": "Vanessa Abrams",
+                            "seasons": [1, 2, 3, 4]
+                        })),
+                    ),
+                    (
+                        "salary_per_episode",
+                        DependencyValue::Decimal(Decimal::from_str("60000.00").unwrap()),
+                    ),
+                    ("cast_status", string("MAIN")),
+                ]),
+            ],
+        ),
+        ("blasts".to_owned(), vec![]),
+        (
+            "movies".to_owned(),
+            vec![
+                in_memory_row([
+                    ("id", DependencyValue::Id(1)),
+                    ("title", string("Black Christmas")),
+                    ("release_year", DependencyValue::Int(2006)),
+                    ("lead_actor_id", DependencyValue::Id(1)),
+                ]),
+                in_memory_row([
+                    ("id", DependencyValue::Id(2)),
+                    ("title", string("Piranha 3D")),
+                    ("release_year", DependencyValue::Int(2010)),
+                    ("lead_actor_id", DependencyValue::Id(2)),
+                ]),
+                in_memory_row([
+                    ("id", DependencyValue::Id(3)),
+                    ("title", string("Monte Carlo")),
+                    ("release_year", DependencyValue::Int(2011)),
+                    ("lead_actor_id", DependencyValue::Id(1)),
+                ]),
+            ],
+        ),
+        (
+            "actors_movies".to_owned(),
+            vec![
+                in_memory_row([
+                    ("id", DependencyValue::Id(1)),
+                    ("actor_id", DependencyValue::Id(1)),
+                    ("movie_id", DependencyValue::Id(1)),
+                ]),
+                in_memory_row([
+                    ("id", DependencyValue::Id(2)),
+                    ("actor_id", DependencyValue::Id(2)),
+                    ("movie_id", DependencyValue::Id(2)),
+                ]),
+                in_memory_row([
+                    ("id", DependencyValue::Id(3)),
+                    ("actor_id", DependencyValue::Id(1)),
+                    ("movie_id", DependencyValue::Id(3)),
+                ]),
+                in_memory_row([
+                    ("id", DependencyValue::Id(4)),
+                    ("actor_id", DependencyValue::Id(2)),
+                    ("movie_id", DependencyValue::Id(3)),
+                ]),
+            ],
+        ),
+    ])
+}
+
+fn in_memory_row<const N: usize>(values: [(&str, DependencyValue); N]) -> InMemoryRow {
+    values
+        .into_iter()
+        .map(|(column_name, value)| (column_name.to_owned(), value))
+        .collect()
+}
+
+fn string(value: &str) -> DependencyValue {
+    DependencyValue::String(value.to_owned())
+}
+
 pub fn pretty_print_json(json: &str) -> String {
     let parsed: serde_json::Value = serde_json::from_str(json).unwrap();
     serde_json::to_string_pretty(&parsed).unwrap()
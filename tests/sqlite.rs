@@ -0,0 +1,667 @@
+#![cfg(feature = "sqlite")]
+
+use sauvignon::{
+    json_from_response, ArgumentInternalDependencyResolver, CarverOrPopulator, ColumnGetter,
+    ColumnGetterList, ColumnGetterListValue, DependencyType, DependencyValue, ExternalDependency,
+    FieldResolver, IdCarver, InternalDependency, InternalDependencyResolver,
+    LiteralValueInternalDependencyResolver, ObjectTypeBuilder, OperationType, Param, RowInserter,
+    Schema, StringCarver, Type, TypeFieldBuilder, TypeFull, ValuePopulator,
+};
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+
+mod shared;
+
+use shared::{get_offline_schema, pretty_print_json};
+
+/// Creates a fresh file-based database per test
+async fn get_sqlite_db_pool(name: &str) -> Pool<Sqlite> {
+    let path = std::env::temp_dir().join(format!("sauvignon-{name}-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let db_pool = SqlitePoolOptions::new()
+        .connect(&format!("sqlite://{}?mode=rwc", path.display()))
+        .await
+        .unwrap();
+    sqlx::raw_sql(include_str!("../create_db.sqlite.sql"))
+        .execute(&db_pool)
+        .await
+        .unwrap();
+    db_pool
+}
+
+async fn sqlite_request_test(name: &str, request: &str, expected: &str) {
+    let db_pool = get_sqlite_db_pool(name).await;
+    let schema = get_offline_schema().unwrap();
+    let response = schema.request(request, &db_pool).await;
+    let json = json_from_response(&response);
+    assert_eq!(pretty_print_json(&json), pretty_print_json(expected));
+}
+
+#[tokio::test]
+async fn test_column_getters() {
+    sqlite_request_test(
+        "column_getters",
+        r#"
+            {
+              actors {
+                id
+                name
+                birthYear
+                heightInMeters
+                isMainCast
+                externalId
+                debutedAt
+                metadata
+                salaryPerEpisode
+                castStatus
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "id": "1",
+                    "name": "Katie Cassidy",
+                    "birthYear": 1986,
+                    "heightInMeters": 1.68,
+                    "isMainCast": false,
+                    "externalId": "c1a7f8a2-6b0e-4c38-9d0b-3f1b2a4d5e61",
+                    "debutedAt": "2010-09-13T21:00:00Z",
+                    "metadata": {
+                      "character": "Juliet Sharp",
+                      "seasons": [4]
+                    },
+                    "salaryPerEpisode": "35000.50",
+                    "castStatus": "RECURRING"
+                  },
+                  {
+                    "id": "2",
+                    "name": "Jessica Szohr",
+                    "birthYear": 1985,
+                    "heightInMeters": 1.65,
+                    "isMainCast": true,
+                    "externalId": "5f0c2d4e-8a1b-4e7f-b3c9-0d6e2a9f1b84",
+                    "debutedAt": "2007-09-19T21:00:00Z",
+                    "metadata": {
+                      "character": "Vanessa Abrams",
+                      "seasons": [1, 2, 3, 4]
+                    },
+                    "salaryPerEpisode": "60000.00",
+                    "castStatus": "MAIN"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_text_foreign_key() {
+    sqlite_request_test(
+        "text_foreign_key",
+        r#"
+            {
+              actors {
+                favoriteActorOrDesigner {
+                  ... on Actor {
+                    name
+                  }
+                  ... on Designer {
+                    name
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "favoriteActorOrDesigner": {
+                      "name": "Proenza Schouler"
+                    }
+                  },
+                  {
+                    "favoriteActorOrDesigner": {
+                      "name": "Katie Cassidy"
+                    }
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_missing_row() {
+    sqlite_request_test(
+        "missing_row",
+        r#"
+            {
+              actor(id: 999) {
+                expression
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "expression": null
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_row_inserter() {
+    sqlite_request_test(
+        "row_inserter",
+        r#"
+            mutation {
+              postBlast(input: {content: "Spotted: S and B", sentAt: "2008-05-19T20:00:00-04:00"}) {
+                content
+                sentAt
+                tone
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "postBlast": {
+                  "content": "Spotted: S and B",
+                  "sentAt": "2008-05-20T00:00:00Z",
+                  "tone": "GOSSIP"
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_list_conditions_ordering_and_limit() {
+    sqlite_request_test(
+        "list_conditions_ordering_and_limit",
+        r#"
+            {
+              actors(isMainCast: false, orderBy: BIRTH_YEAR, first: 1) {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy"
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_has_many_via_foreign_key() {
+    sqlite_request_test(
+        "has_many_via_foreign_key",
+        r#"
+            {
+              actors {
+                name
+                leadMovies {
+                  title
+                  releaseYear
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actors": [
+                  {
+                    "name": "Katie Cassidy",
+                    "leadMovies": [
+                      {
+                        "title": "Black Christmas",
+                        "releaseYear": 2006
+                      },
+                      {
+                        "title": "Monte Carlo",
+                        "releaseYear": 2011
+                      }
+                    ]
+                  },
+                  {
+                    "name": "Jessica Szohr",
+                    "leadMovies": [
+                      {
+                        "title": "Piranha 3D",
+                        "releaseYear": 2010
+                      }
+                    ]
+                  }
+                ]
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_many_to_many_via_join_table() {
+    sqlite_request_test(
+        "many_to_many_via_join_table_1",
+        r#"
+            {
+              actor(id: 1) {
+                movies(orderBy: RELEASE_YEAR_DESC) {
+                  title
+                  cast {
+                    name
+                  }
+                }
+                movieCount
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "movies": [
+                    {
+                      "title": "Monte Carlo",
+                      "cast": [
+                        {
+                          "name": "Jessica Szohr"
+                        },
+                        {
+                          "name": "Katie Cassidy"
+                        }
+                      ]
+                    },
+                    {
+                      "title": "Black Christmas",
+                      "cast": [
+                        {
+                          "name": "Katie Cassidy"
+                        }
+                      ]
+                    }
+                  ],
+                  "movieCount": 2
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+    sqlite_request_test(
+        "many_to_many_via_join_table_2",
+        r#"
+            {
+              actor(id: 2) {
+                movies(releaseYear: 2010) {
+                  title
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "movies": [
+                    {
+                      "title": "Piranha 3D"
+                    }
+                  ]
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_connection() {
+    sqlite_request_test(
+        "connection_1",
+        r#"
+            {
+              actorsConnection(first: 1, after: "Y3Vyc29yOjE=") {
+                edges {
+                  node {
+                    name
+                  }
+                }
+                pageInfo {
+                  hasNextPage
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actorsConnection": {
+                  "edges": [
+                    {
+                      "node": {
+                        "name": "Jessica Szohr"
+                      }
+                    }
+                  ],
+                  "pageInfo": {
+                    "hasNextPage": false
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+
+    sqlite_request_test(
+        "connection_2",
+        r#"
+            {
+              actor(id: 1) {
+                leadMoviesConnection(first: 1, after: "Y3Vyc29yOjE=") {
+                  edges {
+                    node {
+                      title
+                    }
+                  }
+                  pageInfo {
+                    hasNextPage
+                  }
+                }
+              }
+            }
+        "#,
+        r#"
+            {
+              "data": {
+                "actor": {
+                  "leadMoviesConnection": {
+                    "edges": [
+                      {
+                        "node": {
+                          "title": "Monte Carlo"
+                        }
+                      }
+                    ],
+                    "pageInfo": {
+                      "hasNextPage": false
+                    }
+                  }
+                }
+              }
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_row_inserter_unsupported_column_value() {
+    let db_pool = get_sqlite_db_pool("row_inserter_unsupported_column_value").await;
+    let schema = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([TypeFieldBuilder::default()
+                        .name("greeting")
+                        .type_(TypeFull::Type("String".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "greeting".to_owned(),
+                                DependencyType::String,
+                                InternalDependencyResolver::LiteralValue(
+                                    LiteralValueInternalDependencyResolver(
+                                        DependencyValue::String("XOXO".to_owned()),
+                                    ),
+                                ),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                                "greeting".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap()])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Mutation")
+                    .fields([TypeFieldBuilder::default()
+                        .name("sendBlasts")
+                        .type_(TypeFull::Type("ID".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "id".to_owned(),
+                                DependencyType::Id,
+                                InternalDependencyResolver::RowInserter(RowInserter::new(
+                                    "blasts".to_owned(),
+                                    [("content".to_owned(), "contents".to_owned())],
+                                )),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(IdCarver::new("id".to_owned()))),
+                        ))
+                        .params([Param::new(
+                            "contents".to_owned(),
+                            TypeFull::NonNull(Box::new(TypeFull::List(Box::new(TypeFull::Type(
+                                "String".to_owned(),
+                            ))))),
+                        )])
+                        .build()
+                        .unwrap()])
+                    .is_top_level_type(OperationType::Mutation)
+                    .build()
+                    .unwrap(),
+            ),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let response = schema
+        .request(
+            r#"
+                mutation {
+                  sendBlasts(contents: ["Spotted: S", "Spotted: B"])
+                }
+            "#,
+            &db_pool,
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "data": {
+                    "sendBlasts": null
+                  },
+                  "errors": [
+                    {
+                      "message": "Unsupported value for column `content`",
+                      "path": ["sendBlasts"]
+                    }
+                  ]
+                }
+            "#
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_unsupported_column_getter_values() {
+    let db_pool = get_sqlite_db_pool("unsupported_column_getter_values").await;
+    let schema = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([
+                        TypeFieldBuilder::default()
+                            .name("actorIdsByName")
+                            .type_(TypeFull::Type("ID".to_owned()))
+                            .resolver(FieldResolver::new(
+                                vec![],
+                                vec![
+                                    InternalDependency::new(
+                                        "names".to_owned(),
+                                        DependencyType::ListOfStrings,
+                                        InternalDependencyResolver::Argument(
+                                            ArgumentInternalDependencyResolver::new(
+                                                "names".to_owned(),
+                                            ),
+                                        ),
+                                    ),
+                                    InternalDependency::new(
+                                        "ids".to_owned(),
+                                        DependencyType::ListOfIds,
+                                        InternalDependencyResolver::ColumnGetterList(
+                                            ColumnGetterList::new(
+                                                "actors".to_owned(),
+                                                "id".to_owned(),
+                                            )
+                                            .condition(
+                                                "name".to_owned(),
+                                                ColumnGetterListValue::InternalDependency(
+                                                    "names".to_owned(),
+                                                ),
+                                            ),
+                                        ),
+                                    ),
+                                ],
+                                CarverOrPopulator::Carver(Box::new(IdCarver::new(
+                                    "ids".to_owned(),
+                                ))),
+                            ))
+                            .params([Param::new(
+                                "names".to_owned(),
+                                TypeFull::List(Box::new(TypeFull::Type("String".to_owned()))),
+                            )])
+                            .build()
+                            .unwrap(),
+                        TypeFieldBuilder::default()
+                            .name("actor")
+                            .type_(TypeFull::Type("Actor".to_owned()))
+                            .resolver(FieldResolver::new(
+                                vec![],
+                                vec![InternalDependency::new(
+                                    "id".to_owned(),
+                                    DependencyType::Id,
+                                    InternalDependencyResolver::LiteralValue(
+                                        LiteralValueInternalDependencyResolver(
+                                            DependencyValue::Id(1),
+                                        ),
+                                    ),
+                                )],
+                                CarverOrPopulator::Populator(Box::new(ValuePopulator::new(
+                                    "id".to_owned(),
+                                ))),
+                            ))
+                            .build()
+                            .unwrap(),
+                    ])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Actor")
+                    .fields([TypeFieldBuilder::default()
+                        .name("name")
+                        .type_(TypeFull::Type("String".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                            vec![InternalDependency::new(
+                                "name".to_owned(),
+                                DependencyType::ListOfStrings,
+                                InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                    "actors".to_owned(),
+                                    "name".to_owned(),
+                                )),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                                "name".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap()])
+                    .build()
+                    .unwrap(),
+            ),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let response = schema
+        .request(
+            r#"
+                {
+                  actorIdsByName(names: ["Katie Cassidy", "Jessica Szohr"])
+                  actor {
+                    name
+                  }
+                }
+            "#,
+            &db_pool,
+        )
+        .await;
+    assert_eq!(
+        pretty_print_json(&json_from_response(&response)),
+        pretty_print_json(
+            r#"
+                {
+                  "errors": [
+                    {
+                      "message": "Unsupported condition value for column `name`",
+                      "path": ["actorIdsByName"]
+                    },
+                    {
+                      "message": "error occurred while decoding column \"name\": unsupported dependency type",
+                      "path": ["actor", "name"]
+                    }
+                  ],
+                  "data": {
+                    "actorIdsByName": null,
+                    "actor": {
+                      "name": null
+                    }
+                  }
+                }
+            "#
+        )
+    );
+}
@@ -1,15 +1,39 @@
 use indoc::indoc;
 
-use sauvignon::json_from_response;
+use sauvignon::{
+    json_from_response, ArgumentInternalDependencyResolver, BroadcastSubscriptionSource,
+    CarverOrPopulator, ColumnGetter, DependencyType, DependencyValue, Document, EnumTypeBuilder,
+    EnumValueBuilder, Error, ExecutableDefinition, ExternalDependency, FieldResolver,
+    InternalDependency, InternalDependencyResolver, LiteralValueInternalDependencyResolver,
+    ObjectTypeBuilder, OperationDefinitionBuilder, OperationType, Param, Request,
+    RequestOptionsBuilder, Schema, SdlResolvers, Selection, SelectionFieldBuilder, StringCarver,
+    Type, TypeFieldBuilder, TypeFull,
+};
+use tokio::sync::broadcast;
 
 mod shared;
 
-use shared::{get_db_pool, get_schema, pretty_print_json};
+use shared::{get_in_memory_data_source, get_offline_schema, pretty_print_json};
 
 async fn validation_test(request: &str, expected: &str) {
-    let db_pool = get_db_pool().await.unwrap();
-    let schema = get_schema(&db_pool).await.unwrap();
-    let response = schema.request(request, &db_pool).await;
+    let schema = get_offline_schema().unwrap();
+    let response = schema.request(request, &get_in_memory_data_source()).await;
+    let json = json_from_response(&response);
+    assert_eq!(pretty_print_json(&json), pretty_print_json(expected));
+}
+
+async fn validation_with_operation_name_test(request: &str, operation_name: &str, expected: &str) {
+    let schema = get_offline_schema().unwrap();
+    let response = schema
+        .request_with_options(
+            request,
+            &RequestOptionsBuilder::default()
+                .operation_name(operation_name)
+                .build()
+                .unwrap(),
+            &get_in_memory_data_source(),
+        )
+        .await;
     let json = json_from_response(&response);
     assert_eq!(pretty_print_json(&json), pretty_print_json(expected));
 }
@@ -112,7 +136,7 @@ async fn test_operation_name_uniqueness() {
     )
     .await;
 
-    validation_test(
+    validation_with_operation_name_test(
         r#"
             query Whee {
               actorKatie {
@@ -126,6 +150,7 @@ async fn test_operation_name_uniqueness() {
               }
             }
         "#,
+        "Whee",
         r#"
             {
               "data": {
@@ -139,6 +164,35 @@ async fn test_operation_name_uniqueness() {
     .await;
 }
 
+#[tokio::test]
+async fn test_ambiguous_operation() {
+    validation_test(
+        r#"
+            query Whee {
+              actorKatie {
+                name
+              }
+            }
+
+            query Whoa {
+              actors {
+                name
+              }
+            }
+        "#,
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Must provide operation name if document contains multiple operations"
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn test_lone_anonymous_operation() {
     validation_test(
@@ -1069,3 +1123,719 @@ async fn test_directive_unknown_argument() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn test_subscription_single_root_field() {
+    validation_test(
+        indoc!(
+            r#"
+            subscription Whee {
+              actorUpdated {
+                name
+              }
+              __typename
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Subscription `Whee` must select only one top-level field",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 1
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            subscription {
+              ...typenameFragment
+            }
+
+            fragment typenameFragment on Subscription {
+              __typename
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Anonymous subscription can't select introspection top-level field `__typename`",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 1
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[test]
+fn test_subscription_single_root_field_response_keys() {
+    let schema = get_offline_schema().unwrap();
+    // built by hand since the parser doesn't support aliases
+    let aliased_field = |alias: &str| {
+        Selection::Field(
+            SelectionFieldBuilder::default()
+                .alias(alias.to_owned())
+                .name("actorUpdated")
+                .selection_set(vec![Selection::Field(
+                    SelectionFieldBuilder::default()
+                        .name("name")
+                        .build()
+                        .unwrap(),
+                )])
+                .build()
+                .unwrap(),
+        )
+    };
+    let request = Request::new(Document::new(vec![ExecutableDefinition::Operation(
+        OperationDefinitionBuilder::default()
+            .operation_type(OperationType::Subscription)
+            .selection_set(vec![aliased_field("a"), aliased_field("b")])
+            .build()
+            .unwrap(),
+    )]));
+    assert_eq!(
+        schema
+            .validate(&request)
+            .into_errors()
+            .into_iter()
+            .map(|error| error.message)
+            .collect::<Vec<_>>(),
+        ["Anonymous subscription must select only one top-level field"]
+    );
+}
+
+#[tokio::test]
+async fn test_variable_name_uniqueness() {
+    validation_test(
+        indoc!(
+            r#"
+            query Whee($id: ID!, $id: ID!) {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Non-unique variable names: `$id`",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 12
+                    },
+                    {
+                      "line": 1,
+                      "column": 22
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_variable_usages() {
+    validation_test(
+        indoc!(
+            r#"
+            query {
+              ...actorFragment
+            }
+
+            fragment actorFragment on Query {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Undefined variable: `$id`",
+                  "locations": [
+                    {
+                      "line": 6,
+                      "column": 9
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            query Whee($id: ID!, $skipName: Boolean) {
+              actor(id: $id) {
+                name
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Unused variable: `$skipName`",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 22
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            query Whee($id: ID, $skipName: Boolean) {
+              actor(id: $id) {
+                name @skip(if: $skipName)
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Variable `$id` of type `ID` can't be used where type `ID!` is expected",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 9
+                    }
+                  ]
+                },
+                {
+                  "message": "Variable `$skipName` of type `Boolean` can't be used where type `Boolean!` is expected",
+                  "locations": [
+                    {
+                      "line": 3,
+                      "column": 10
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_custom_scalar_argument_values() {
+    validation_test(
+        indoc!(
+            r#"
+            mutation {
+              sendBlast(content: "Spotted: S and B", sentAt: "last night") {
+                content
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `sentAt`: Invalid date-time: `last night`",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 42
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            mutation {
+              sendBlast(content: "Spotted: S and B", sentAt: 1211241600) {
+                content
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `sentAt`: Expected a date-time string",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 42
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_enum_argument_values() {
+    validation_test(
+        indoc!(
+            r#"
+            mutation {
+              sendBlast(content: "Spotted: S and B", tone: LOUD) {
+                content
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `tone`: `LOUD` isn't a value of enum `BlastTone`",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 42
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            mutation {
+              sendBlast(content: "Spotted: S and B", tone: "SCANDAL") {
+                content
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `tone`: Expected a value of enum `BlastTone`",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 42
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_input_object_argument_values() {
+    validation_test(
+        indoc!(
+            r#"
+            {
+              searchActors(filter: {name: {contains: "katie"}, nickname: "K"}) {
+                name
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `filter`: Non-existent field `nickname` of input type `ActorSearchFilter`",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 16
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            {
+              searchActors(filter: {name: {caseSensitive: true}}) {
+                name
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `filter`: Missing required field `contains` of input type `StringFilter`",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 16
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            {
+              searchActors(filter: {name: {contains: 3}}) {
+                name
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `filter`: Expected a value of type `String`",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 16
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+
+    validation_test(
+        indoc!(
+            r#"
+            {
+              searchActors(filter: "katie") {
+                name
+              }
+            }
+        "#
+        ),
+        r#"
+            {
+              "errors": [
+                {
+                  "message": "Invalid value for argument `filter`: Expected an object of input type `ActorSearchFilter`",
+                  "locations": [
+                    {
+                      "line": 2,
+                      "column": 16
+                    }
+                  ]
+                }
+              ]
+            }
+        "#,
+    )
+    .await;
+}
+
+#[test]
+fn test_invalid_sql_identifier() {
+    let result = Schema::try_new(
+        vec![Type::Object(
+            ObjectTypeBuilder::default()
+                .name("Query")
+                .fields([TypeFieldBuilder::default()
+                    .name("actorName")
+                    .type_(TypeFull::Type("String".to_owned()))
+                    .resolver(FieldResolver::new(
+                        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                        vec![InternalDependency::new(
+                            "name".to_owned(),
+                            DependencyType::String,
+                            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                "actors; DROP TABLE actors".to_owned(),
+                                "name".to_owned(),
+                            )),
+                        )],
+                        CarverOrPopulator::Carver(Box::new(StringCarver::new("name".to_owned()))),
+                    ))
+                    .build()
+                    .unwrap()])
+                .is_top_level_type(OperationType::Query)
+                .build()
+                .unwrap(),
+        )],
+        vec![],
+        vec![],
+    );
+    assert!(matches!(
+        result,
+        Err(errors) if matches!(
+            &errors[..],
+            [Error::InvalidSqlIdentifier(identifier)] if identifier == "actors; DROP TABLE actors"
+        )
+    ));
+}
+
+fn actor_column_resolver(column: &str) -> FieldResolver {
+    FieldResolver::new(
+        vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+        vec![InternalDependency::new(
+            column.to_owned(),
+            DependencyType::String,
+            InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                "actors".to_owned(),
+                column.to_owned(),
+            )),
+        )],
+        CarverOrPopulator::Carver(Box::new(StringCarver::new(column.to_owned()))),
+    )
+}
+
+fn schema_error_messages(result: Result<Schema, Vec<Error>>) -> Vec<String> {
+    match result {
+        Ok(_) => panic!("Expected schema errors"),
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn test_schema_type_system_errors() {
+    let result = Schema::try_from_sdl(
+        indoc!(
+            r#"
+            type Query {
+              actor: Actor
+              nope: Nope
+            }
+
+            type Actor implements HasName & Missing {
+              name: Int
+              search(filter: Actor): String
+            }
+
+            interface HasName {
+              name: String!
+            }
+
+            union Anything = Actor | HasName | Gone
+            "#
+        ),
+        SdlResolvers::default()
+            .resolver("Query.actor", actor_column_resolver("id"))
+            .resolver("Query.nope", actor_column_resolver("id"))
+            .resolver("Actor.name", actor_column_resolver("name"))
+            .resolver("Actor.search", actor_column_resolver("name")),
+    );
+    assert_eq!(
+        schema_error_messages(result),
+        [
+            "`Query.nope` refers to unknown type `Nope`",
+            "`Actor.search(filter:)` has non-input type `Actor`",
+            "`Actor.name` has a type incompatible with `HasName.name`",
+            "`Actor` implements unknown interface `Missing`",
+            "union `Anything` has non-object member type `HasName`",
+            "union `Anything` has unknown member type `Gone`",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_dependency_errors() {
+    let result = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([TypeFieldBuilder::default()
+                        .name("actorName")
+                        .type_(TypeFull::Type("String".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![ExternalDependency::new("id".to_owned(), DependencyType::Id)],
+                            vec![
+                                InternalDependency::new(
+                                    "name".to_owned(),
+                                    DependencyType::String,
+                                    InternalDependencyResolver::ColumnGetter(ColumnGetter::new(
+                                        "actors".to_owned(),
+                                        "name".to_owned(),
+                                    )),
+                                ),
+                                InternalDependency::new(
+                                    "name".to_owned(),
+                                    DependencyType::String,
+                                    InternalDependencyResolver::LiteralValue(
+                                        LiteralValueInternalDependencyResolver(
+                                            DependencyValue::String("Katie Cassidy".to_owned()),
+                                        ),
+                                    ),
+                                ),
+                                InternalDependency::new(
+                                    "actor_id".to_owned(),
+                                    DependencyType::Id,
+                                    InternalDependencyResolver::Argument(
+                                        ArgumentInternalDependencyResolver::new("id".to_owned()),
+                                    ),
+                                ),
+                            ],
+                            CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                                "nmae".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap()])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Enum(
+                EnumTypeBuilder::default()
+                    .name("String")
+                    .values([EnumValueBuilder::default().name("ONE").build().unwrap()])
+                    .build()
+                    .unwrap(),
+            ),
+        ],
+        vec![],
+        vec![],
+    );
+    assert_eq!(
+        schema_error_messages(result),
+        [
+            "duplicate type name `String`",
+            "`Query.actorName` has duplicate dependency `name`",
+            "`Query.actorName` depends on unknown argument `id`",
+            "`Query.actorName` uses unknown dependency `nmae`",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_argument_dependency_type_and_subscription_source_errors() {
+    let result = Schema::try_new(
+        vec![
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Query")
+                    .fields([TypeFieldBuilder::default()
+                        .name("actorName")
+                        .type_(TypeFull::Type("String".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "actor_id".to_owned(),
+                                DependencyType::String,
+                                InternalDependencyResolver::Argument(
+                                    ArgumentInternalDependencyResolver::new("id".to_owned()),
+                                ),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                                "actor_id".to_owned(),
+                            ))),
+                        ))
+                        .params([Param::new(
+                            "id".to_owned(),
+                            TypeFull::NonNull(Box::new(TypeFull::Type("ID".to_owned()))),
+                        )])
+                        .subscription_source(Box::new(BroadcastSubscriptionSource::new(
+                            broadcast::channel(1).0,
+                        )))
+                        .build()
+                        .unwrap()])
+                    .is_top_level_type(OperationType::Query)
+                    .build()
+                    .unwrap(),
+            ),
+            Type::Object(
+                ObjectTypeBuilder::default()
+                    .name("Subscription")
+                    .fields([TypeFieldBuilder::default()
+                        .name("greeting")
+                        .type_(TypeFull::Type("String".to_owned()))
+                        .resolver(FieldResolver::new(
+                            vec![],
+                            vec![InternalDependency::new(
+                                "greeting".to_owned(),
+                                DependencyType::String,
+                                InternalDependencyResolver::LiteralValue(
+                                    LiteralValueInternalDependencyResolver(
+                                        DependencyValue::String("XOXO".to_owned()),
+                                    ),
+                                ),
+                            )],
+                            CarverOrPopulator::Carver(Box::new(StringCarver::new(
+                                "greeting".to_owned(),
+                            ))),
+                        ))
+                        .build()
+                        .unwrap()])
+                    .is_top_level_type(OperationType::Subscription)
+                    .build()
+                    .unwrap(),
+            ),
+        ],
+        vec![],
+        vec![],
+    );
+    assert_eq!(
+        schema_error_messages(result),
+        [
+            "`Query.actorName` depends on argument `id` with an incompatible type",
+            "subscription source provided for non-subscription field `Query.actorName`",
+            "missing subscription source for field `Subscription.greeting`",
+        ]
+    );
+}